@@ -1,7 +1,7 @@
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use std::hint::black_box;
 use std::sync::Arc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 use swmr_epoch::{EpochGcDomain, EpochPtr};
@@ -156,6 +156,17 @@ fn bench_pin_latency(c: &mut Criterion) {
         });
     });
 
+    group.bench_function("swmr_epoch_pin_latency_cached", |b| {
+        let (_gc, domain) = EpochGcDomain::builder().reader_epoch_cache(true).build();
+        let local_epoch = domain.register_reader();
+
+        b.iter(|| {
+            let guard = local_epoch.pin();
+            black_box(&guard);
+            drop(guard);
+        });
+    });
+
     group.bench_function("crossbeam_epoch_pin_latency", |b| {
         b.iter(|| {
             let guard = crossbeam_epoch::pin();
@@ -199,6 +210,58 @@ fn bench_high_contention(c: &mut Criterion) {
         });
     });
 
+    // Readers pinning while the writer keeps advancing the epoch, so pins have to retry.
+    // Run with and without `--features backoff` to compare the two retry strategies.
+    let pin_wait = if cfg!(feature = "backoff") {
+        "backoff"
+    } else {
+        "spin"
+    };
+    group.bench_function(
+        format!("swmr_epoch_high_contention_collecting_{pin_wait}"),
+        |b| {
+            b.iter(|| {
+                let (mut gc, domain) = EpochGcDomain::new();
+                let epoch_ptr = Arc::new(EpochPtr::new(0u64));
+                let done = Arc::new(AtomicBool::new(false));
+
+                let handles: Vec<_> = (0..16)
+                    .map(|_| {
+                        let d = domain.clone();
+                        let ep = epoch_ptr.clone();
+
+                        thread::spawn(move || {
+                            let local_epoch = d.register_reader();
+                            for _ in 0..1000 {
+                                let guard = local_epoch.pin();
+                                let _val = ep.load(&guard);
+                            }
+                        })
+                    })
+                    .collect();
+
+                let writer = {
+                    let done = done.clone();
+                    let epoch_ptr = epoch_ptr.clone();
+                    thread::spawn(move || {
+                        let mut value = 0u64;
+                        while !done.load(Ordering::Relaxed) {
+                            value += 1;
+                            epoch_ptr.store(value, &mut gc);
+                            gc.collect();
+                        }
+                    })
+                };
+
+                for handle in handles {
+                    let _ = handle.join();
+                }
+                done.store(true, Ordering::Relaxed);
+                let _ = writer.join();
+            });
+        },
+    );
+
     group.bench_function("crossbeam_epoch_high_contention", |b| {
         b.iter(|| {
             let atomic = Arc::new(crossbeam_epoch::Atomic::new(0u64));
@@ -5,7 +5,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 
 // Import our epoch-based GC implementation
-use swmr_epoch::{EpochGcDomain, EpochPtr};
+use swmr_epoch::{AtomicEpochCell, EpochGcDomain, EpochPtr, ReadersBackend};
 
 // Benchmark 1: Single-threaded pin/unpin overhead
 fn bench_single_thread_pin_unpin(c: &mut Criterion) {
@@ -80,6 +80,24 @@ fn bench_reader_registration(c: &mut Criterion) {
     group.finish();
 }
 
+// Benchmark 3: Registration throughput per readers backend (including the merge in collect)
+fn bench_readers_backend(c: &mut Criterion) {
+    let mut group = c.benchmark_group("readers_backend_registration");
+
+    for backend in [ReadersBackend::Mutex, ReadersBackend::LockFree] {
+        group.bench_function(format!("{backend:?}"), |b| {
+            b.iter(|| {
+                let (mut gc, domain) = EpochGcDomain::builder().readers_backend(backend).build();
+                let readers: Vec<_> = (0..64).map(|_| domain.register_reader()).collect();
+                gc.collect();
+                black_box(readers);
+            });
+        });
+    }
+
+    group.finish();
+}
+
 // Benchmark 4: Epoch pointer operations
 fn bench_atomic_operations(c: &mut Criterion) {
     let mut group = c.benchmark_group("atomic_operations");
@@ -106,10 +124,69 @@ fn bench_atomic_operations(c: &mut Criterion) {
         });
     });
 
+    // A long-lived guard: pin once, then read a table of pointers
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let table: Vec<_> = (0..64u64).map(EpochPtr::new).collect();
+
+    group.bench_function("swmr_epoch_load_table_individually", |b| {
+        b.iter(|| {
+            let guard = local_epoch.pin();
+            let mut sum = 0;
+            for ptr in black_box(&table) {
+                sum += *ptr.load(&guard);
+            }
+            black_box(sum);
+        });
+    });
+
+    group.bench_function("swmr_epoch_load_many_table", |b| {
+        b.iter(|| {
+            let guard = local_epoch.pin();
+            let sum: u64 = EpochPtr::load_many(black_box(&table), &guard).sum();
+            black_box(sum);
+        });
+    });
+
+    group.finish();
+}
+
+// Benchmark 5: Inline cell vs. boxed pointer for small Copy values
+fn bench_inline_cell_vs_boxed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inline_cell_vs_boxed");
+
+    group.bench_function("epoch_ptr_store_load", |b| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let local_epoch = domain.register_reader();
+        let epoch_ptr = EpochPtr::new(0u64);
+        let mut i = 0u64;
+
+        b.iter(|| {
+            i += 1;
+            epoch_ptr.store(i, &mut gc);
+            let guard = local_epoch.pin();
+            black_box(*epoch_ptr.load(&guard));
+        });
+    });
+
+    group.bench_function("atomic_epoch_cell_store_load", |b| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let local_epoch = domain.register_reader();
+        let cell = AtomicEpochCell::new(0u64);
+        let mut i = 0u64;
+
+        b.iter(|| {
+            i += 1;
+            cell.store(i, &mut gc);
+            let guard = local_epoch.pin();
+            black_box(cell.load(&guard));
+        });
+    });
+
     group.finish();
 }
 
-// Benchmark 5: Concurrent read-heavy workload
+// Benchmark 6: Concurrent read-heavy workload
 fn bench_concurrent_reads(c: &mut Criterion) {
     let mut group = c.benchmark_group("concurrent_reads");
     group.sample_size(10);
@@ -186,7 +263,9 @@ criterion_group!(
     benches,
     bench_single_thread_pin_unpin,
     bench_reader_registration,
+    bench_readers_backend,
     bench_atomic_operations,
+    bench_inline_cell_vs_boxed,
     bench_concurrent_reads
 );
 criterion_main!(benches);
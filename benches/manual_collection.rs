@@ -193,12 +193,57 @@ fn bench_auto_vs_manual(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: Storing a large value by value versus initializing it in place
+///
+/// `store` builds the value on the stack and copies it into its box, while
+/// `store_in_place` writes it straight into the heap allocation.
+fn bench_store_large(c: &mut Criterion) {
+    const LEN: usize = 16 * 1024;
+    let mut group = c.benchmark_group("store_large");
+
+    group.bench_function("store", |b| {
+        let (mut gc, _domain) = EpochGcDomain::new();
+        let epoch_ptr = EpochPtr::new([0u64; LEN]);
+        let mut round = 0u64;
+
+        b.iter(|| {
+            round += 1;
+            epoch_ptr.store(black_box([round; LEN]), &mut gc);
+        });
+    });
+
+    group.bench_function("store_in_place", |b| {
+        let (mut gc, _domain) = EpochGcDomain::new();
+        let epoch_ptr = EpochPtr::new([0u64; LEN]);
+        let mut round = 0u64;
+
+        b.iter(|| {
+            round += 1;
+            let value = black_box(round);
+            unsafe {
+                epoch_ptr.store_in_place(
+                    |slot| {
+                        let data = slot.as_mut_ptr().cast::<u64>();
+                        for i in 0..LEN {
+                            data.add(i).write(value);
+                        }
+                    },
+                    &mut gc,
+                );
+            }
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_manual_collection,
     bench_collection_with_readers,
     bench_multiple_collections,
     bench_collection_latency,
-    bench_auto_vs_manual
+    bench_auto_vs_manual,
+    bench_store_large
 );
 criterion_main!(benches);
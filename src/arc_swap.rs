@@ -0,0 +1,106 @@
+use crate::garbage::GcHandle;
+use crate::ptr::EpochPtr;
+use crate::reader::PinGuard;
+use std::sync::Arc;
+
+/// An `arc-swap`-style container whose hot read path uses epochs instead of reference counts.
+///
+/// Offers the familiar `load` / `store` / `rcu` API on top of an `EpochPtr<Arc<T>>`. Readers
+/// borrow the value under a `PinGuard`, which touches no shared reference count, and can
+/// still take their own `Arc` with `load_full()` when the value must outlive the guard.
+///
+/// **Tradeoff versus `arc-swap`**: `ArcSwap::load` is lock-free but must manage a debt or
+/// reference count on every read, and any thread can store. Here a load is a single atomic
+/// read, but updates go through the single writer's `GcHandle`, and replaced values are
+/// released by epoch reclamation rather than as soon as the last reference is dropped.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use swmr_epoch::{EpochArcSwap, EpochGcDomain};
+///
+/// let (mut gc, domain) = EpochGcDomain::new();
+/// let config = EpochArcSwap::from_pointee(1);
+///
+/// config.rcu(|value| value + 1, &mut gc);
+/// config.store(Arc::new(10), &mut gc);
+///
+/// let local_epoch = domain.register_reader();
+/// assert_eq!(*config.load(&local_epoch.pin()), 10);
+/// ```
+///
+/// 一个 `arc-swap` 风格的容器，其热读取路径使用纪元而不是引用计数。
+///
+/// 在 `EpochPtr<Arc<T>>` 之上提供熟悉的 `load` / `store` / `rcu` API。读者在 `PinGuard` 下借用值，
+/// 不会触及任何共享的引用计数；当值必须比守卫存活得更久时，仍可通过 `load_full()` 获取自己的 `Arc`。
+///
+/// **与 `arc-swap` 的权衡**：`ArcSwap::load` 是无锁的，但每次读取都必须管理一个债务或引用计数，且任何线程都可以 store。
+/// 这里的 load 只是一次原子读取，但更新必须通过唯一写入者的 `GcHandle` 进行，
+/// 被替换的值通过纪元回收释放，而不是在最后一个引用被 drop 时立即释放。
+pub struct EpochArcSwap<T: 'static> {
+    ptr: EpochPtr<Arc<T>>,
+}
+
+impl<T: 'static> EpochArcSwap<T> {
+    /// Create a new container holding `value`.
+    /// 创建一个持有 `value` 的新容器。
+    #[inline]
+    pub fn new(value: Arc<T>) -> Self {
+        Self {
+            ptr: EpochPtr::new(value),
+        }
+    }
+
+    /// Create a new container holding `Arc::new(value)`.
+    /// 创建一个持有 `Arc::new(value)` 的新容器。
+    #[inline]
+    pub fn from_pointee(value: T) -> Self {
+        Self::new(Arc::new(value))
+    }
+
+    /// Reader load: borrow the current value for the lifetime of the guard.
+    /// 读取者 load：在守卫的生命周期内借用当前值。
+    #[inline]
+    pub fn load<'guard>(&self, guard: &'guard PinGuard) -> &'guard T {
+        self.ptr.load(guard)
+    }
+
+    /// Reader load of an owned `Arc` that stays valid after the guard is dropped.
+    /// 读取者 load 一个在守卫被 drop 之后仍然有效的自有 `Arc`。
+    #[inline]
+    pub fn load_full(&self, guard: &PinGuard) -> Arc<T> {
+        Arc::clone(self.ptr.load(guard))
+    }
+
+    /// Writer store: replace the value and retire the old `Arc`.
+    /// 写入者 store：替换值并退休旧的 `Arc`。
+    #[inline]
+    pub fn store(&self, value: Arc<T>, gc: &mut GcHandle)
+    where
+        T: Send + Sync,
+    {
+        self.ptr.store(value, gc);
+    }
+
+    /// Writer read-copy-update: replace the value with `f` applied to the current one.
+    ///
+    /// Returns the previous value. Unlike `ArcSwap::rcu`, `f` runs exactly once, because the
+    /// single writer cannot race with another update.
+    ///
+    /// 写入者读-复制-更新：用 `f` 作用于当前值的结果替换值。
+    /// 返回之前的值。与 `ArcSwap::rcu` 不同，`f` 恰好运行一次，因为唯一的写入者不会与其他更新竞争。
+    #[inline]
+    pub fn rcu<F>(&self, f: F, gc: &mut GcHandle) -> Arc<T>
+    where
+        T: Send + Sync,
+        F: FnOnce(&T) -> T,
+    {
+        self.ptr.rcu(f, gc)
+    }
+}
+
+impl<T: std::fmt::Debug + 'static> std::fmt::Debug for EpochArcSwap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EpochArcSwap").field(&self.ptr).finish()
+    }
+}
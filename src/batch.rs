@@ -0,0 +1,107 @@
+use crate::garbage::{GcHandle, RetiredNode, RetiredObject};
+use crate::ptr::EpochPtr;
+use crate::sync::Ordering;
+use std::boxed::Box;
+use std::vec::Vec;
+
+/// A group of pointer updates whose old values are retired into a single epoch bag.
+///
+/// Each `store()` publishes its new value immediately, exactly like `EpochPtr::store()`, but
+/// the old values are held back and enqueued together when the batch is committed, either
+/// explicitly with `commit()` or when the batch is dropped. No automatic collection can run in
+/// between, so all old values of a multi-pointer update land in the same epoch and are
+/// reclaimed together.
+///
+/// Created with `GcHandle::batch()`.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::{EpochGcDomain, EpochPtr};
+///
+/// let (mut gc, _domain) = EpochGcDomain::new();
+/// let name = EpochPtr::new(String::from("old"));
+/// let limit = EpochPtr::new(10u32);
+///
+/// let mut batch = gc.batch();
+/// batch.store(&name, String::from("new"));
+/// batch.store(&limit, 20);
+/// batch.commit();
+///
+/// assert_eq!(gc.pending_epochs(), 1);
+/// ```
+///
+/// 一组指针更新，其旧值被退休到同一个纪元袋子中。
+///
+/// 每次 `store()` 都会立即发布新值，与 `EpochPtr::store()` 完全相同，但旧值会被暂存，
+/// 并在批次提交时（通过显式调用 `commit()` 或在批次被 drop 时）一起入队。
+/// 其间不会运行自动回收，因此多指针更新的所有旧值都会落在同一个纪元中并一起被回收。
+///
+/// 通过 `GcHandle::batch()` 创建。
+pub struct WriteBatch<'a> {
+    gc: &'a mut GcHandle,
+    retired: Vec<RetiredNode>,
+}
+
+impl WriteBatch<'_> {
+    /// Replace the value of `ptr`, deferring retirement of the old value to the commit.
+    /// 替换 `ptr` 的值，将旧值的退休推迟到提交时。
+    #[inline]
+    pub fn store<T: Send + 'static>(&mut self, ptr: &EpochPtr<T>, data: T) {
+        let new_ptr = Box::into_raw(Box::new(data));
+        let old_ptr = ptr.replace(new_ptr, Ordering::AcqRel);
+
+        if !old_ptr.is_null() {
+            self.retired
+                .push(RetiredObject::new(unsafe { Box::from_raw(old_ptr) }));
+        }
+    }
+
+    /// Get the number of old values waiting for the commit.
+    /// 获取等待提交的旧值数量。
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.retired.len()
+    }
+
+    /// Check whether the batch holds no old values.
+    /// 检查批次是否没有持有任何旧值。
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.retired.is_empty()
+    }
+
+    /// Retire all buffered old values into the current epoch.
+    ///
+    /// Equivalent to dropping the batch. With `max_pending_garbage` configured, the commit
+    /// waits for room like `EpochPtr::store()` does, then enqueues the whole batch at once,
+    /// which may temporarily exceed the cap by the size of the batch.
+    ///
+    /// 将所有暂存的旧值退休到当前纪元。
+    /// 等价于 drop 批次。配置了 `max_pending_garbage` 时，提交会像 `EpochPtr::store()` 一样等待空间，
+    /// 然后一次性入队整个批次，这可能会使垃圾数量暂时超出上限最多一个批次的大小。
+    #[inline]
+    pub fn commit(self) {}
+}
+
+impl Drop for WriteBatch<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.gc.retire_batch(&mut self.retired);
+    }
+}
+
+impl GcHandle {
+    /// Start a batch of pointer updates whose old values share one epoch bag.
+    ///
+    /// See `WriteBatch`.
+    ///
+    /// 开始一批指针更新，其旧值共享同一个纪元袋子。
+    /// 参见 `WriteBatch`。
+    #[inline]
+    pub fn batch(&mut self) -> WriteBatch<'_> {
+        WriteBatch {
+            gc: self,
+            retired: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::garbage::GcHandle;
+use crate::reader::PinGuard;
+use crate::sync::{AtomicU64, Ordering};
+use std::marker::PhantomData;
+use std::mem::{MaybeUninit, size_of};
+
+/// Values that can be stored inline in an `AtomicEpochCell`.
+///
+/// # Safety
+/// Implementors must be at most 8 bytes large and must not contain padding or other
+/// uninitialized bytes, so that every value can be copied bit-for-bit into a `u64`
+/// and back. Implemented for the primitive integer types, `bool`, `char`, `f32` and `f64`.
+/// A `#[repr(transparent)]` wrapper around one of those can implement it as well.
+///
+/// 可以内联存储在 `AtomicEpochCell` 中的值。
+///
+/// # 安全性
+/// 实现者的大小必须不超过 8 字节，并且不能包含填充或其他未初始化字节，
+/// 以便每个值都可以逐位复制到 `u64` 中并复制回来。
+/// 已为基本整数类型、`bool`、`char`、`f32` 和 `f64` 实现。
+/// 包装这些类型之一的 `#[repr(transparent)]` 类型也可以实现它。
+pub unsafe trait InlineValue: Copy + 'static {}
+
+macro_rules! impl_inline_value {
+    ($($ty:ty),* $(,)?) => {
+        $(unsafe impl InlineValue for $ty {})*
+    };
+}
+
+impl_inline_value!(
+    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, bool, char, f32, f64
+);
+
+/// An epoch-style cell for small `Copy` values that updates in place.
+///
+/// `EpochPtr<T>` allocates a box for every `store()` and retires the old one. For small
+/// values such as counters and flags this is wasteful: `AtomicEpochCell<T>` keeps the value
+/// inline in an atomic word, so `store()` neither allocates nor retires anything.
+///
+/// The API mirrors `EpochPtr` (loads take a `PinGuard`, stores take the `GcHandle`) so both
+/// can be used uniformly, but `load()` returns a copy instead of a reference.
+///
+/// 用于小型 `Copy` 值的原地更新的 epoch 风格单元。
+///
+/// `EpochPtr<T>` 每次 `store()` 都会分配一个 box 并退休旧的 box。对于计数器和标志等小值，
+/// 这是浪费的：`AtomicEpochCell<T>` 将值内联保存在一个原子字中，因此 `store()` 既不分配也不退休任何东西。
+///
+/// 此 API 与 `EpochPtr` 一致（load 需要 `PinGuard`，store 需要 `GcHandle`），
+/// 以便两者可以统一使用，但 `load()` 返回副本而不是引用。
+pub struct AtomicEpochCell<T: InlineValue> {
+    bits: AtomicU64,
+    _marker: PhantomData<T>,
+}
+
+impl<T: InlineValue> AtomicEpochCell<T> {
+    const FITS: () = assert!(
+        size_of::<T>() <= size_of::<u64>(),
+        "AtomicEpochCell values must fit in 8 bytes"
+    );
+
+    /// Create a new cell holding `value`.
+    /// 创建一个持有 `value` 的新单元。
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            bits: AtomicU64::new(Self::encode(value)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reader load: return a copy of the current value.
+    /// 读取者 load：返回当前值的副本。
+    #[inline]
+    pub fn load(&self, _guard: &PinGuard) -> T {
+        Self::decode(self.bits.load(Ordering::Acquire))
+    }
+
+    /// Writer store: replace the value in place, without allocation or retirement.
+    /// 写入者 store：原地替换值，不进行分配或退休。
+    #[inline]
+    pub fn store(&self, value: T, _gc: &mut GcHandle) {
+        self.bits.store(Self::encode(value), Ordering::Release);
+    }
+
+    #[inline(always)]
+    fn encode(value: T) -> u64 {
+        let () = Self::FITS;
+        let mut bits = 0u64;
+        // Safety: `T` fits in a `u64` and has no uninitialized bytes (`InlineValue` contract)
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                &value as *const T as *const u8,
+                &mut bits as *mut u64 as *mut u8,
+                size_of::<T>(),
+            );
+        }
+        bits
+    }
+
+    #[inline(always)]
+    fn decode(bits: u64) -> T {
+        let mut value = MaybeUninit::<T>::uninit();
+        // Safety: `bits` was produced by `encode` from a valid `T`
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                &bits as *const u64 as *const u8,
+                value.as_mut_ptr() as *mut u8,
+                size_of::<T>(),
+            );
+            value.assume_init()
+        }
+    }
+}
+
+impl<T: InlineValue + std::fmt::Debug> std::fmt::Debug for AtomicEpochCell<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = Self::decode(self.bits.load(Ordering::Relaxed));
+        f.debug_tuple("AtomicEpochCell").field(&value).finish()
+    }
+}
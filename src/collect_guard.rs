@@ -0,0 +1,72 @@
+use crate::garbage::GcHandle;
+use std::ops::{Deref, DerefMut};
+
+/// A guard that runs `GcHandle::collect()` once when it goes out of scope.
+///
+/// Dereferences to the `GcHandle` it borrows, so stores can go through the guard while it is
+/// alive. The collection runs however the scope is left, including early returns and `?`,
+/// which suits functions that make several updates and want them reclaimed promptly.
+///
+/// Created with `GcHandle::collect_on_drop()`.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::{EpochGcDomain, EpochPtr, GcHandle};
+///
+/// fn update(gc: &mut GcHandle, ptr: &EpochPtr<u32>, value: Option<u32>) -> Option<()> {
+///     let mut gc = gc.collect_on_drop();
+///     ptr.store(0, &mut gc);
+///     ptr.store(value?, &mut gc);
+///     Some(())
+/// }
+///
+/// let (mut gc, domain) = EpochGcDomain::new();
+/// let ptr = EpochPtr::new(1u32);
+/// assert_eq!(update(&mut gc, &ptr, None), None);
+/// assert_eq!(domain.epoch(), 1);
+/// ```
+///
+/// 一个在离开作用域时运行一次 `GcHandle::collect()` 的守卫。
+/// 解引用为其借用的 `GcHandle`，因此守卫存活期间可以通过它进行 store。无论以何种方式离开作用域
+///（包括提前返回和 `?`）都会运行回收，适用于进行多次更新并希望它们被及时回收的函数。
+///
+/// 通过 `GcHandle::collect_on_drop()` 创建。
+pub struct CollectGuard<'a> {
+    gc: &'a mut GcHandle,
+}
+
+impl Deref for CollectGuard<'_> {
+    type Target = GcHandle;
+
+    #[inline]
+    fn deref(&self) -> &GcHandle {
+        self.gc
+    }
+}
+
+impl DerefMut for CollectGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut GcHandle {
+        self.gc
+    }
+}
+
+impl Drop for CollectGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.gc.collect();
+    }
+}
+
+impl GcHandle {
+    /// Get a guard that collects when it is dropped.
+    ///
+    /// See `CollectGuard`.
+    ///
+    /// 获取一个在被 drop 时进行回收的守卫。
+    /// 参见 `CollectGuard`。
+    #[inline]
+    pub fn collect_on_drop(&mut self) -> CollectGuard<'_> {
+        CollectGuard { gc: self }
+    }
+}
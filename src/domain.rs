@@ -1,14 +1,40 @@
-use crate::garbage::{GarbageSet, GcHandle};
-use crate::reader::LocalEpoch;
+#[cfg(feature = "metrics")]
+use crate::garbage::COLLECT_LATENCY_BUCKETS;
+use crate::garbage::{GarbageSet, GcHandle, LagStats};
+use crate::idle::{IdleCollector, IdleNotify};
+use crate::metrics::GcMetrics;
+use crate::reader::{LocalEpoch, ReaderGroup};
+use crate::registry::{PendingSlots, ReaderList, ReaderStorage, ReadersBackend};
 use crate::state::{AUTO_RECLAIM_THRESHOLD, DEFAULT_CLEANUP_INTERVAL, SharedState};
-use crate::sync::{Arc, AtomicUsize, Mutex};
+#[cfg(feature = "metrics")]
+use crate::sync::AtomicU64;
+use crate::sync::{Arc, AtomicUsize, Mutex, Ordering};
+use std::time::Duration;
+#[cfg(debug_assertions)]
+use std::time::Instant;
 use std::vec::Vec;
+#[cfg(feature = "thread-local")]
+use std::{cell::RefCell, rc::Rc};
 
 /// Builder for configuring an `EpochGcDomain`.
 ///
 /// Use this builder to customize garbage collection behavior:
 /// - `auto_reclaim_threshold`: Set garbage count threshold for automatic collection
 /// - `cleanup_interval`: Set how often to cleanup dead reader slots
+/// - `promote_after`: Promote long-lived garbage to an old generation
+/// - `coalesce_threshold`: Merge adjacent garbage bags when too many epochs hold garbage
+/// - `collect_batch_size`: Bound how many objects an automatic collection reclaims
+/// - `max_pending_garbage`: Cap the number of unreclaimed objects, applying backpressure to the writer
+/// - `scan_chunk`: Scan reader slots in chunks, releasing the readers lock in between
+/// - `readers_backend`: Choose how new readers are registered
+/// - `reader_storage`: Choose how reader slots are stored
+/// - `reader_slot_recycling`: Reuse the slots of dropped readers for new registrations
+/// - `reader_epoch_cache`: Let readers revalidate their last pinned epoch instead of reloading the global epoch
+/// - `auto_collect_on_idle`: Ask the writer to collect when it has been idle while holding garbage
+/// - `panic_on_reader_leak`: Panic when the writer is dropped while a reader is still pinned (debug builds only)
+/// - `pin_timeout`: Detect readers that stay pinned too long (debug builds only)
+/// - `with_metrics`: Report collections, pins and registrations to a `GcMetrics` implementation
+/// - `name`: Name the domain, to tell several domains apart in diagnostics
 ///
 /// # Example
 /// ```
@@ -23,7 +49,21 @@ use std::vec::Vec;
 /// 用于配置 `EpochGcDomain` 的构建器。
 pub struct EpochGcDomainBuilder {
     auto_reclaim_threshold: Option<usize>,
+    collect_batch_size: Option<usize>,
+    max_pending_garbage: Option<usize>,
+    promote_after: Option<usize>,
+    coalesce_threshold: Option<usize>,
     cleanup_interval: usize,
+    scan_chunk: usize,
+    readers_backend: ReadersBackend,
+    reader_storage: ReaderStorage,
+    reader_slot_recycling: bool,
+    reader_epoch_cache: bool,
+    pin_timeout: Option<Duration>,
+    panic_on_reader_leak: bool,
+    auto_collect_on_idle: Option<(Duration, IdleNotify)>,
+    metrics: Option<std::sync::Arc<dyn GcMetrics>>,
+    name: std::sync::Arc<str>,
 }
 
 impl EpochGcDomainBuilder {
@@ -33,7 +73,21 @@ impl EpochGcDomainBuilder {
     pub fn new() -> Self {
         Self {
             auto_reclaim_threshold: Some(AUTO_RECLAIM_THRESHOLD),
+            collect_batch_size: None,
+            max_pending_garbage: None,
+            promote_after: None,
+            coalesce_threshold: None,
             cleanup_interval: DEFAULT_CLEANUP_INTERVAL,
+            scan_chunk: 0,
+            readers_backend: ReadersBackend::Mutex,
+            reader_storage: ReaderStorage::Vec,
+            reader_slot_recycling: false,
+            reader_epoch_cache: false,
+            pin_timeout: None,
+            panic_on_reader_leak: false,
+            auto_collect_on_idle: None,
+            metrics: None,
+            name: std::sync::Arc::from(""),
         }
     }
 
@@ -53,6 +107,117 @@ impl EpochGcDomainBuilder {
         self
     }
 
+    /// Bound the number of objects reclaimed by an automatic collection.
+    ///
+    /// When the auto-reclaim threshold is exceeded inside a `store()`, the resulting collection
+    /// normally reclaims all safe garbage at once, which shows up as a latency spike on that
+    /// store. With a batch size, automatically triggered collections reclaim at most `size`
+    /// objects and leave the rest for the next trigger, resuming where the previous one
+    /// stopped. Explicit calls to `GcHandle::collect()` still do a full pass.
+    ///
+    /// Default: unbounded
+    ///
+    /// # Panics
+    /// Panics if `size` is `0`.
+    ///
+    /// 限制一次自动回收所回收的对象数量。
+    /// 当 `store()` 中超过自动回收阈值时，所触发的回收通常会一次性回收所有安全的垃圾，这会表现为该次写入的延迟尖峰。
+    /// 设置批大小后，自动触发的回收最多回收 `size` 个对象，其余的留给下一次触发，并从上一次停止的位置继续。
+    /// 显式调用 `GcHandle::collect()` 仍然执行完整回收。
+    ///
+    /// # Panics
+    /// 如果 `size` 为 `0` 则 panic。
+    #[inline]
+    pub fn collect_batch_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "collect_batch_size must be at least 1");
+        self.collect_batch_size = Some(size);
+        self
+    }
+
+    /// Cap the number of retired objects that may wait for reclamation.
+    ///
+    /// Without a cap, a reader that never unpins makes the garbage queue grow without bound.
+    /// With a cap, a retirement that would exceed it first forces a `collect()`. If the queue
+    /// is still full afterwards, the policy depends on the API used:
+    /// - `EpochPtr::store()` and the other infallible writes **block**, repeatedly collecting
+    ///   and yielding until readers advance and room is freed. A reader that never unpins
+    ///   therefore stalls the writer instead of exhausting memory.
+    /// - `EpochPtr::try_store()` **fails** and hands the new value back, leaving the pointer
+    ///   unchanged, so the writer can decide what to do.
+    ///
+    /// Default: unbounded
+    ///
+    /// # Panics
+    /// Panics if `cap` is `0`.
+    ///
+    /// 限制可以等待回收的已退休对象数量。
+    /// 没有上限时，一个永不解除钉住的读者会使垃圾队列无限增长。
+    /// 设置上限后，会超过上限的退休操作会先强制执行一次 `collect()`。如果之后队列仍然已满，策略取决于所用的 API：
+    /// - `EpochPtr::store()` 及其他不会失败的写入会**阻塞**，反复回收并让出 CPU，直到读者前进并释放出空间。
+    ///   因此永不解除钉住的读者会使写入者停滞，而不是耗尽内存。
+    /// - `EpochPtr::try_store()` 会**失败**并交还新值，保持指针不变，由写入者决定如何处理。
+    ///
+    /// # Panics
+    /// 如果 `cap` 为 `0` 则 panic。
+    #[inline]
+    pub fn max_pending_garbage(mut self, cap: usize) -> Self {
+        assert!(cap > 0, "max_pending_garbage must be at least 1");
+        self.max_pending_garbage = Some(cap);
+        self
+    }
+
+    /// Promote garbage that survives `cycles` collections to the old generation.
+    ///
+    /// Promoted garbage is skipped by `GcHandle::collect_generation(Generation::Young)` and
+    /// only reclaimed by full collections, which saves rescanning garbage held back by a
+    /// persistently slow reader. Has no effect unless young collections are used.
+    ///
+    /// Default: disabled
+    ///
+    /// # Panics
+    /// Panics if `cycles` is `0`.
+    ///
+    /// 将经历 `cycles` 次回收后仍存活的垃圾提升到老年代。
+    /// `GcHandle::collect_generation(Generation::Young)` 会跳过被提升的垃圾，只有完整回收才会回收它们，
+    /// 从而避免重复扫描被持续缓慢的读者阻挡的垃圾。除非使用年轻代回收，否则没有效果。
+    ///
+    /// # Panics
+    /// 如果 `cycles` 为 `0` 则 panic。
+    #[inline]
+    pub fn promote_after(mut self, cycles: usize) -> Self {
+        assert!(cycles > 0, "promote_after must be at least 1");
+        self.promote_after = Some(cycles);
+        self
+    }
+
+    /// Merge adjacent garbage bags once more than `bags` epochs hold garbage.
+    ///
+    /// Garbage is kept in one bag per epoch. When a slow reader holds back reclamation, every
+    /// collection adds another small bag, which costs memory and scanning time. With this
+    /// option, a collection that leaves more than `bags` bags merges adjacent bags, those with
+    /// the closest epochs first, until `bags` remain. A merged bag takes the newer of the two
+    /// epochs, so the older objects in it may be reclaimed a little later than they would have
+    /// been, but never earlier.
+    ///
+    /// Default: disabled
+    ///
+    /// # Panics
+    /// Panics if `bags` is `0`.
+    ///
+    /// 一旦超过 `bags` 个纪元持有垃圾，就合并相邻的垃圾袋子。
+    /// 垃圾按纪元存放在各自的袋子中。当缓慢的读者阻挡回收时，每次回收都会再添加一个小袋子，这会消耗内存和扫描时间。
+    /// 启用此选项后，如果一次回收之后仍剩余超过 `bags` 个袋子，则会合并相邻的袋子（纪元最接近的优先），直到剩余 `bags` 个。
+    /// 合并后的袋子采用两个纪元中较新的那个，因此其中较旧的对象可能会比原本稍晚被回收，但绝不会更早。
+    ///
+    /// # Panics
+    /// 如果 `bags` 为 `0` 则 panic。
+    #[inline]
+    pub fn coalesce_threshold(mut self, bags: usize) -> Self {
+        assert!(bags > 0, "coalesce_threshold must be at least 1");
+        self.coalesce_threshold = Some(bags);
+        self
+    }
+
     /// Set the cleanup interval for dead reader slots.
     ///
     /// Dead reader slots are cleaned up every N collection cycles to reduce overhead.
@@ -69,6 +234,223 @@ impl EpochGcDomainBuilder {
         self
     }
 
+    /// Scan reader slots in chunks of `chunk` slots during `collect()`.
+    ///
+    /// `collect()` normally holds the readers lock while scanning every slot, which with
+    /// hundreds of readers can delay registrations. With a chunk size, the lock is released
+    /// and re-acquired between chunks. A reader that pins while the scan is in progress may be
+    /// missed, and may even publish an epoch it loaded before the advance. Missing it is still
+    /// safe: a pin publishes the slot and then issues a SeqCst fence that pairs with the one
+    /// the writer issues right after advancing, so a reader the scan missed sees every pointer
+    /// swap made before the writer's fence, and only garbage from those swaps can be reclaimed
+    /// with the computed minimum. Set to `0` to scan all slots under a single lock acquisition.
+    ///
+    /// Default: `0`
+    ///
+    /// 在 `collect()` 期间以每块 `chunk` 个槽的方式扫描读者槽。
+    /// `collect()` 通常在扫描每个槽时都持有读者锁，在有数百个读者时可能会延迟注册。
+    /// 设置块大小后，锁会在块之间被释放并重新获取。扫描过程中被钉住的读者可能被遗漏，甚至可能发布它在推进之前加载的纪元。
+    /// 遗漏它仍然是安全的：pin 先发布槽，再执行一个 SeqCst fence，与写入者在推进之后立即执行的 fence 配对，
+    /// 因此被扫描遗漏的读者能看到写入者 fence 之前进行的每一次指针交换，而用计算出的最小值只能回收这些交换产生的垃圾。
+    /// 设置为 `0` 则在一次加锁中扫描所有槽。
+    #[inline]
+    pub fn scan_chunk(mut self, chunk: usize) -> Self {
+        self.scan_chunk = chunk;
+        self
+    }
+
+    /// Choose the reader registration backend.
+    ///
+    /// `ReadersBackend::Mutex` registers readers under the readers lock, while
+    /// `ReadersBackend::LockFree` lets registration proceed without ever waiting for a running
+    /// collection, at the cost of merging new readers during the next `collect()`. Both
+    /// backends reclaim garbage identically.
+    ///
+    /// Default: `ReadersBackend::Mutex`
+    ///
+    /// 选择读者注册后端。
+    /// `ReadersBackend::Mutex` 在读者锁下注册读者，而 `ReadersBackend::LockFree`
+    /// 让注册无需等待正在运行的回收，代价是在下一次 `collect()` 中合并新读者。两种后端的垃圾回收行为完全相同。
+    #[inline]
+    pub fn readers_backend(mut self, backend: ReadersBackend) -> Self {
+        self.readers_backend = backend;
+        self
+    }
+
+    /// Choose how the registered reader slots are stored.
+    ///
+    /// `ReaderStorage::Vec` keeps the slots densely packed. `ReaderStorage::Slab` gives every
+    /// slot a stable index, so eagerly unregistered readers remove themselves in O(1) and a
+    /// chunked scan never has to restart because a slot moved.
+    ///
+    /// Default: `ReaderStorage::Vec`
+    ///
+    /// 选择已注册读者槽的存储方式。
+    /// `ReaderStorage::Vec` 使槽紧凑排列。`ReaderStorage::Slab` 为每个槽提供稳定的索引，
+    /// 因此急切注销的读者以 O(1) 移除自己，分块扫描也不会因为槽被移动而需要重新开始。
+    #[inline]
+    pub fn reader_storage(mut self, storage: ReaderStorage) -> Self {
+        self.reader_storage = storage;
+        self
+    }
+
+    /// Enable recycling of dead reader slots.
+    ///
+    /// By default, every `register_reader()` allocates a new slot and dead slots are freed
+    /// during cleanup. With recycling enabled, cleanup keeps dead slots on a free list and
+    /// registration reuses them, which avoids allocations for churny reader populations.
+    ///
+    /// Default: `false`
+    ///
+    /// 启用死读者槽的复用。
+    /// 默认情况下，每次 `register_reader()` 都会分配一个新槽，死槽在清理时被释放。
+    /// 启用复用后，清理会将死槽保存在空闲列表中，注册时复用它们，从而避免频繁变动的读者群体的分配开销。
+    #[inline]
+    pub fn reader_slot_recycling(mut self, enabled: bool) -> Self {
+        self.reader_slot_recycling = enabled;
+        self
+    }
+
+    /// Let readers reuse the epoch of their previous pin.
+    ///
+    /// An outermost `pin()` normally loads the global epoch, publishes it and checks it against
+    /// the minimum active epoch. With caching enabled, each reader remembers the epoch it last
+    /// pinned to and first tries to publish that one, skipping the global epoch load. The cached
+    /// epoch is accepted only if it passes the same check against the minimum active epoch;
+    /// otherwise the reader falls back to the global epoch and refreshes its cache.
+    ///
+    /// This is as safe as the uncached path: a cached epoch was read from the global epoch
+    /// earlier, so everything retired before it was already unlinked, and the check guarantees
+    /// the writer has not reclaimed past it. The trade-off is that a reader may pin to an
+    /// older epoch than necessary, holding back reclamation until a collection runs while it is
+    /// unpinned, which raises the minimum active epoch and invalidates the cache.
+    ///
+    /// Default: `false`
+    ///
+    /// 让读者复用其上一次 pin 的纪元。
+    /// 最外层的 `pin()` 通常会加载全局纪元、发布它并与最小活跃纪元进行检查。
+    /// 启用缓存后，每个读者会记住其上一次钉住的纪元，并先尝试发布该纪元，从而跳过全局纪元的加载。
+    /// 只有通过与最小活跃纪元的相同检查时，缓存的纪元才会被接受；否则读者回退到全局纪元并刷新其缓存。
+    ///
+    /// 这与不使用缓存的路径同样安全：缓存的纪元是之前从全局纪元读取的，因此在它之前退休的所有对象都已被解除链接，
+    /// 而检查保证了写入者没有越过它进行回收。代价是读者可能钉住到比必要更旧的纪元，从而阻挡回收，
+    /// 直到在其未被钉住时运行一次回收，提升最小活跃纪元并使缓存失效。
+    #[inline]
+    pub fn reader_epoch_cache(mut self, enabled: bool) -> Self {
+        self.reader_epoch_cache = enabled;
+        self
+    }
+
+    /// Set a watchdog timeout for pinned readers.
+    ///
+    /// In debug builds, each reader records when it becomes pinned, and `collect()` panics if
+    /// any reader has been pinned for longer than `timeout`. This catches guards that were
+    /// accidentally kept alive. In release builds this setting is ignored and costs nothing.
+    ///
+    /// Default: disabled
+    ///
+    /// 设置被钉住读者的看门狗超时。
+    /// 在调试构建中，每个读者会记录其被钉住的时间，如果有读者被钉住超过 `timeout`，
+    /// `collect()` 会 panic。这可以捕获意外保持存活的守卫。
+    /// 在发布构建中此设置被忽略且没有任何开销。
+    #[inline]
+    pub fn pin_timeout(mut self, timeout: Duration) -> Self {
+        self.pin_timeout = Some(timeout);
+        self
+    }
+
+    /// Panic when the `GcHandle` is dropped while a registered reader is still pinned.
+    ///
+    /// A reader that stays pinned forever, typically because a `PinGuard` was leaked or stored
+    /// somewhere long-lived, silently prevents all reclamation. With this flag, debug builds
+    /// scan the readers when the `GcHandle` is dropped and panic if any of them is still
+    /// pinned, turning that leak into an immediate failure. No check is made while the thread
+    /// is already panicking or after `GcHandle::shutdown()`, which reports such readers
+    /// instead, and release builds ignore this setting.
+    ///
+    /// Default: `false`
+    ///
+    /// 当 `GcHandle` 被 drop 时仍有已注册的读者被钉住则 panic。
+    /// 永远保持被钉住的读者（通常是因为 `PinGuard` 被泄漏或存放在某个长期存活的地方）会悄无声息地阻止所有回收。
+    /// 启用此标志后，调试构建会在 `GcHandle` 被 drop 时扫描读者，如果仍有读者被钉住则 panic，使这种泄漏立即暴露。
+    /// 线程已经在 panic 时或在 `GcHandle::shutdown()`（它会改为报告这类读者）之后不进行检查，发布构建会忽略此设置。
+    #[inline]
+    pub fn panic_on_reader_leak(mut self, enabled: bool) -> Self {
+        self.panic_on_reader_leak = enabled;
+        self
+    }
+
+    /// Ask the writer to collect when it has been idle for `idle` while holding garbage.
+    ///
+    /// With bursty writes, garbage retired by the last store of a burst is only reclaimed by
+    /// the next store that crosses the auto-reclaim threshold, which may come much later. This
+    /// option spawns a helper thread, owned by the `GcHandle` and stopped when it is dropped.
+    ///
+    /// **Threading model**: the `GcHandle` stays owned by the writer thread, and reclamation
+    /// still only happens there. The helper never touches the garbage: it watches the
+    /// writer's retirements, and once none happened for a full `idle` interval while garbage
+    /// may be pending, it raises a request and calls `notify` on the helper thread. `notify`
+    /// should wake the writer, e.g. by sending a message to its event loop, and the writer
+    /// then calls `GcHandle::collect_if_requested()`. `notify` is called once per request; if
+    /// the garbage cannot be reclaimed yet because a reader is pinned, a new request follows
+    /// after the next idle interval.
+    ///
+    /// Default: disabled
+    ///
+    /// 当写入者在持有垃圾的情况下空闲了 `idle` 时间时，请求写入者进行回收。
+    /// 在突发写入的场景下，一次突发中最后一次 store 退休的垃圾只有在下一次超过自动回收阈值的 store 时才会被回收，而这可能要晚得多。
+    /// 此选项会启动一个辅助线程，它由 `GcHandle` 持有，并在 `GcHandle` 被 drop 时停止。
+    ///
+    /// **线程模型**：`GcHandle` 仍由写入者线程持有，回收也仍然只在该线程进行。辅助线程从不触及垃圾：
+    /// 它观察写入者的退休操作，一旦在整个 `idle` 间隔内都没有退休发生且可能有待回收的垃圾，
+    /// 它就会发出请求并在辅助线程上调用 `notify`。`notify` 应当唤醒写入者，例如向其事件循环发送一条消息，
+    /// 然后由写入者调用 `GcHandle::collect_if_requested()`。每个请求只调用一次 `notify`；
+    /// 如果由于有读者被钉住而垃圾暂时无法回收，下一个空闲间隔之后会发出新的请求。
+    #[inline]
+    pub fn auto_collect_on_idle(
+        mut self,
+        idle: Duration,
+        notify: impl Fn() + Send + 'static,
+    ) -> Self {
+        self.auto_collect_on_idle = Some((idle, Box::new(notify)));
+        self
+    }
+
+    /// Report domain activity to a `GcMetrics` implementation.
+    ///
+    /// `on_collect` is called after every collection cycle run by the `GcHandle`, `on_pin`
+    /// whenever a reader enters its outermost pin, and `on_register` whenever a reader is
+    /// registered. The hooks run inline on the thread that triggered the event.
+    ///
+    /// Default: no hooks
+    ///
+    /// 将域的活动报告给一个 `GcMetrics` 实现。
+    /// `GcHandle` 每运行一个回收周期后调用 `on_collect`，读者每次进入最外层 pin 时调用 `on_pin`，
+    /// 每注册一个读者时调用 `on_register`。钩子在触发事件的线程上内联运行。
+    #[inline]
+    pub fn with_metrics(mut self, metrics: std::sync::Arc<dyn GcMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Name the domain.
+    ///
+    /// Applications with several domains, for example one per subsystem, can use the name to
+    /// tell them apart: it is returned by `EpochGcDomain::name()`, shown in the domain's
+    /// `Debug` output, and included in the warnings and panics of debug-build diagnostics
+    /// such as `pin_timeout`.
+    ///
+    /// Default: unnamed (empty)
+    ///
+    /// 为域命名。
+    /// 拥有多个域（例如每个子系统一个）的应用程序可以用名称区分它们：它由 `EpochGcDomain::name()` 返回，
+    /// 显示在域的 `Debug` 输出中，并包含在调试构建诊断（例如 `pin_timeout`）的警告和 panic 信息中。
+    #[inline]
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = std::sync::Arc::from(name);
+        self
+    }
+
     /// Build the `EpochGcDomain` with the configured settings.
     ///
     /// Returns both the `GcHandle` and the `EpochGcDomain`.
@@ -80,15 +462,47 @@ impl EpochGcDomainBuilder {
         let shared = Arc::new(SharedState {
             global_epoch: AtomicUsize::new(0),
             min_active_epoch: AtomicUsize::new(0),
-            readers: Mutex::new(Vec::new()),
+            readers: Mutex::new(ReaderList::new(self.reader_storage)),
+            reader_count: AtomicUsize::new(0),
+            slot_removals: AtomicUsize::new(0),
+            pending_readers: (self.readers_backend == ReadersBackend::LockFree)
+                .then(PendingSlots::new),
+            recycled_slots: self.reader_slot_recycling.then(|| Mutex::new(Vec::new())),
+            deferred: Mutex::new(Vec::new()),
+            reader_epoch_cache: self.reader_epoch_cache,
+            metrics: self.metrics,
+            name: self.name,
+            #[cfg(feature = "metrics")]
+            pin_spins: AtomicU64::new(0),
+            #[cfg(debug_assertions)]
+            created_at: Instant::now(),
+            #[cfg(debug_assertions)]
+            pin_timeout: self.pin_timeout,
         });
 
+        let mut garbage = GarbageSet::new();
+        garbage.coalesce_threshold = self.coalesce_threshold;
+
         let gc = GcHandle {
             shared: shared.clone(),
-            garbage: GarbageSet::new(),
+            garbage,
             auto_reclaim_threshold: self.auto_reclaim_threshold,
+            collect_batch_size: self.collect_batch_size,
+            max_pending_garbage: self.max_pending_garbage,
+            promote_after: self.promote_after,
             collection_counter: 0,
             cleanup_interval: self.cleanup_interval,
+            scan_chunk: self.scan_chunk,
+            min_holder: None,
+            last_epoch: 0,
+            auto_collections: 0,
+            idle: self
+                .auto_collect_on_idle
+                .map(|(idle, notify)| IdleCollector::spawn(idle, notify)),
+            #[cfg(feature = "metrics")]
+            collect_latency: [0; COLLECT_LATENCY_BUCKETS],
+            #[cfg(debug_assertions)]
+            panic_on_reader_leak: self.panic_on_reader_leak,
         };
 
         let domain = EpochGcDomain { shared };
@@ -134,12 +548,23 @@ impl Default for EpochGcDomainBuilder {
 /// - 全局纪元计数器。
 /// - 读者线程的注册。
 /// - 唯一垃圾回收器的创建。
+///
 /// 这个设计使用类型系统在编译时强制只创建一个 `GcHandle`。
 /// `EpochGcDomain` 是 `Clone` 的，可以安全地在线程间共享。
 /// 通常，你在启动时创建一个域并将其克隆到需要它的线程。
 #[derive(Clone)]
 pub struct EpochGcDomain {
-    shared: Arc<SharedState>,
+    pub(crate) shared: Arc<SharedState>,
+}
+
+impl std::fmt::Debug for EpochGcDomain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EpochGcDomain")
+            .field("name", &self.name())
+            .field("epoch", &self.epoch())
+            .field("readers", &self.shared.reader_count.load(Ordering::Relaxed))
+            .finish()
+    }
 }
 
 impl EpochGcDomain {
@@ -170,6 +595,117 @@ impl EpochGcDomain {
         EpochGcDomainBuilder::new()
     }
 
+    /// Get the current global epoch.
+    ///
+    /// The epoch advances by one on every collection cycle.
+    ///
+    /// # Example
+    /// ```
+    /// use swmr_epoch::EpochGcDomain;
+    ///
+    /// let (mut gc, domain) = EpochGcDomain::new();
+    /// assert_eq!(domain.epoch(), 0);
+    ///
+    /// gc.collect();
+    /// assert_eq!(domain.epoch(), 1);
+    /// ```
+    ///
+    /// 获取当前的全局纪元。
+    /// 每个回收周期纪元会增加一。
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.shared.global_epoch.load(Ordering::Acquire)
+    }
+
+    /// Get the name given with `EpochGcDomainBuilder::name()`, or `""` if the domain is unnamed.
+    /// 获取通过 `EpochGcDomainBuilder::name()` 指定的名称；如果域未命名则返回 `""`。
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// Get the epochs the currently pinned readers are pinned at, in ascending order.
+    ///
+    /// Unpinned readers are left out, so the first entry is the epoch holding back
+    /// reclamation. The readers keep running while their slots are scanned, so the result is
+    /// only a snapshot.
+    ///
+    /// 获取当前被钉住的读者所钉住的纪元，按升序排列。
+    /// 未被钉住的读者不包含在内，因此第一个条目就是阻碍回收的纪元。扫描读者槽时读者仍在运行，因此结果只是一个快照。
+    #[inline]
+    pub fn reader_epochs_sorted(&self) -> Vec<usize> {
+        self.shared.pinned_epochs_sorted()
+    }
+
+    /// Get the 99th percentile of how far pinned readers lag behind the global epoch.
+    ///
+    /// Returns `None` if no reader is pinned. Same as `GcHandle::reader_lag_stats().p99`, for
+    /// monitoring threads that do not own the handle.
+    ///
+    /// 获取被钉住的读者落后于全局纪元程度的第 99 百分位数。
+    /// 如果没有读者被钉住则返回 `None`。与 `GcHandle::reader_lag_stats().p99` 相同，供不持有句柄的监控线程使用。
+    pub fn p99_reader_lag(&self) -> Option<usize> {
+        let global_epoch = self.epoch();
+        let stats = LagStats::new(global_epoch, &self.shared.pinned_epochs_sorted());
+        (stats.pinned > 0).then_some(stats.p99)
+    }
+
+    /// Advance the global epoch without reclaiming anything, and return the new epoch.
+    ///
+    /// Can be called from any thread, without the `GcHandle`. Readers that pin afterwards see
+    /// the newer epoch. Reclamation still requires the handle: this only bumps the epoch, and
+    /// garbage retired since the writer's last collection stays pending until the writer's
+    /// next `collect()` has advanced past it.
+    ///
+    /// # Example
+    /// ```
+    /// use swmr_epoch::EpochGcDomain;
+    ///
+    /// let (_gc, domain) = EpochGcDomain::new();
+    /// assert_eq!(domain.advance_epoch_only(), 1);
+    /// assert_eq!(domain.epoch(), 1);
+    /// ```
+    ///
+    /// 推进全局纪元但不回收任何东西，并返回新纪元。
+    /// 可以在任何线程中调用，无需 `GcHandle`。之后钉住的读者会看到更新的纪元。回收仍然需要句柄：
+    /// 此方法只推进纪元，自写入者上次回收以来退休的垃圾会一直等待，直到写入者的下一次 `collect()` 推进越过它。
+    #[inline]
+    pub fn advance_epoch_only(&self) -> usize {
+        self.shared.global_epoch.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Get how many times each registered reader has been pinned.
+    ///
+    /// Only available with the `metrics` feature. Nested pins count once; only the transition
+    /// from unpinned to pinned is counted. The order of the entries is unspecified, and slots
+    /// of dropped readers are included until they are cleaned up.
+    ///
+    /// 获取每个已注册读者被钉住的次数。
+    /// 仅在启用 `metrics` feature 时可用。嵌套的 pin 只计数一次；只统计从未钉住到被钉住的转换。
+    /// 条目的顺序未指定，已 drop 读者的槽在被清理之前也会被包含在内。
+    #[cfg(feature = "metrics")]
+    pub fn reader_pin_counts(&self) -> Vec<u64> {
+        self.shared
+            .lock_readers()
+            .iter()
+            .map(|slot| slot.pin_total.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    /// Get the total number of spin iterations performed by `pin()` across all readers.
+    ///
+    /// Only available with the `metrics` feature. A pin spins when the writer advanced the
+    /// epoch past the value it was about to publish. A steadily growing count means the writer
+    /// is collecting faster than readers can pin, and collections should be spaced out.
+    ///
+    /// 获取所有读者的 `pin()` 执行的自旋迭代总次数。
+    /// 仅在启用 `metrics` feature 时可用。当写入者将纪元推进到读者即将发布的值之后时，pin 会自旋。
+    /// 持续增长的计数意味着写入者回收的速度快于读者 pin 的速度，应当拉开回收的间隔。
+    #[cfg(feature = "metrics")]
+    pub fn total_pin_spins(&self) -> u64 {
+        self.shared.pin_spins.load(Ordering::Relaxed)
+    }
+
     /// Register a new reader for the current thread.
     ///
     /// Returns a `LocalEpoch` that should be stored per-thread.
@@ -183,4 +719,109 @@ impl EpochGcDomain {
     pub fn register_reader(&self) -> LocalEpoch {
         LocalEpoch::new(self.shared.clone())
     }
+
+    /// Register a new reader as a member of `group`.
+    ///
+    /// Behaves like `register_reader()`; membership only matters to
+    /// `GcHandle::quiesce_group()`, which waits for the members of one group.
+    ///
+    /// 注册一个作为 `group` 成员的新读者。
+    /// 行为与 `register_reader()` 相同；成员身份只对 `GcHandle::quiesce_group()` 有意义，它会等待一个群组的成员。
+    #[inline]
+    pub fn register_reader_in_group(&self, group: &ReaderGroup) -> LocalEpoch {
+        LocalEpoch::new_in_group(self.shared.clone(), group.id)
+    }
+
+    /// Register a reader that unregisters its slot as soon as it is dropped.
+    ///
+    /// A reader from `register_reader()` leaves a dead slot behind when dropped, which is only
+    /// removed by a later collection (see `cleanup_interval`). An eager reader instead takes the
+    /// readers lock in its `Drop` and removes its own slot, trading a more expensive drop for
+    /// no lingering dead slots. Suited to short-lived readers, such as one thread per request.
+    ///
+    /// 注册一个在被 drop 时立即注销其槽的读者。
+    /// 通过 `register_reader()` 注册的读者在被 drop 时会留下一个死槽，只有之后的回收才会将其移除（参见 `cleanup_interval`）。
+    /// 急切读者则在其 `Drop` 中获取读者锁并移除自己的槽，以更昂贵的 drop 换取不残留死槽。
+    /// 适用于生命周期较短的读者，例如每个请求一个线程的场景。
+    #[inline]
+    pub fn register_reader_eager(&self) -> LocalEpoch {
+        let mut reader = LocalEpoch::new(self.shared.clone());
+        reader.eager = true;
+        reader
+    }
+
+    /// Register a new reader whose slot is placed on NUMA node `node`.
+    ///
+    /// Only available with the `numa` feature. Behaves like `register_reader()`, then asks the
+    /// kernel to migrate the memory page holding the reader's slot to `node`, typically the node
+    /// of the thread that will use the reader. The slot is written on every pin, so keeping it
+    /// local to its reader saves cross-node traffic on the reader side; each slot already sits
+    /// on its own cache line, so readers never falsely share one.
+    ///
+    /// Placement is best effort and never affects correctness:
+    /// - It is only implemented on Linux, via `move_pages(2)`; elsewhere it does nothing.
+    /// - It silently does nothing if the kernel lacks NUMA support, the process is not allowed
+    ///   to migrate pages (e.g. in some containers), or `node` does not exist.
+    /// - The whole page moves, so other allocations sharing it move too, and the allocator
+    ///   may later place unrelated data next to the slot.
+    ///
+    /// 注册一个新的读者，其槽被放置在 NUMA 节点 `node` 上。
+    /// 仅在启用 `numa` feature 时可用。行为与 `register_reader()` 相同，然后请求内核将持有读者槽的内存页迁移到 `node`，
+    /// 通常是将使用该读者的线程所在的节点。每次 pin 都会写入槽，因此让它位于其读者本地可以减少读者侧的跨节点流量；
+    /// 每个槽已经独占一个缓存行，因此读者之间永远不会发生伪共享。
+    ///
+    /// 放置是尽力而为的，永远不会影响正确性：
+    /// - 仅在 Linux 上通过 `move_pages(2)` 实现；在其他平台上不做任何事。
+    /// - 如果内核不支持 NUMA、进程不允许迁移页面（例如在某些容器中）或 `node` 不存在，则静默地不做任何事。
+    /// - 整个页面都会被移动，因此共享该页面的其他分配也会随之移动，分配器之后也可能将无关的数据放在槽旁边。
+    #[cfg(feature = "numa")]
+    pub fn register_reader_on_node(&self, node: usize) -> LocalEpoch {
+        let reader = LocalEpoch::new(self.shared.clone());
+        crate::numa::move_to_node(Arc::as_ptr(&reader.slot), node);
+        reader
+    }
+
+    /// Get the calling thread's reader for this domain, registering it on first use.
+    ///
+    /// Only available with the `thread-local` feature. The first call on a thread registers an
+    /// eager reader and stores it in a thread-local; later calls on that thread return the same
+    /// reader, so code that pins from many places need not pass a `LocalEpoch` around. When the
+    /// thread exits, the thread-local is destroyed and the reader removes its slot.
+    ///
+    /// The thread-local keeps the reader, and with it this domain's shared state, alive even
+    /// if the domain itself is dropped earlier: until the thread exits, or until a later call
+    /// on that thread, for any domain, finds that nothing but the thread-local still refers to
+    /// the domain and drops its reader. Like any live reader, it also makes
+    /// `GcHandle::reset()` fail.
+    ///
+    /// 获取调用线程在此域中的读者，首次使用时进行注册。
+    /// 仅在启用 `thread-local` feature 时可用。线程上的第一次调用会注册一个急切读者并将其存放在线程局部变量中；
+    /// 该线程之后的调用返回同一个读者，因此从许多地方进行 pin 的代码无需四处传递 `LocalEpoch`。
+    /// 线程退出时，线程局部变量被销毁，读者会移除自己的槽。
+    ///
+    /// 即使域本身更早被 drop，线程局部变量也会使读者以及此域的共享状态保持存活：直到线程退出，
+    /// 或者直到该线程上之后的一次调用（针对任意域）发现除线程局部变量之外已没有任何东西引用该域，并 drop 其读者。
+    /// 与任何存活的读者一样，它也会使 `GcHandle::reset()` 失败。
+    #[cfg(feature = "thread-local")]
+    pub fn thread_local_reader(&self) -> Rc<LocalEpoch> {
+        std::thread_local! {
+            static READERS: RefCell<Vec<Rc<LocalEpoch>>> = const { RefCell::new(Vec::new()) };
+        }
+
+        READERS.with(|readers| {
+            let mut readers = readers.borrow_mut();
+            // Forget the readers of domains whose handle and every other owner are gone, so a
+            // long-lived thread does not accumulate them
+            readers.retain(|reader| Arc::strong_count(&reader.shared) > 1);
+            if let Some(reader) = readers
+                .iter()
+                .find(|reader| Arc::ptr_eq(&reader.shared, &self.shared))
+            {
+                return Rc::clone(reader);
+            }
+            let reader = Rc::new(self.register_reader_eager());
+            readers.push(Rc::clone(&reader));
+            reader
+        })
+    }
 }
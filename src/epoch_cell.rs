@@ -0,0 +1,118 @@
+use crate::domain::EpochGcDomain;
+use crate::garbage::GcHandle;
+use crate::ptr::EpochPtr;
+use crate::reader::{LocalEpoch, PinGuard};
+use std::sync::Arc;
+
+/// A single epoch-protected value bundled with its own domain and writer handle.
+///
+/// Covers the common config-swap case, where there is one value and one writer, without the
+/// boilerplate of wiring a `GcHandle`, an `EpochGcDomain` and an `EpochPtr` together. The
+/// `EpochCell` itself is the writer; readers on other threads use an `EpochCellReader`
+/// obtained from `reader_handle()`.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::EpochCell;
+///
+/// let mut config = EpochCell::new(String::from("v1"));
+/// let readers = config.reader_handle();
+/// config.write(String::from("v2"));
+///
+/// let local_epoch = readers.reader();
+/// assert_eq!(readers.read(&local_epoch.pin()), "v2");
+/// ```
+///
+/// 一个与自己的域和写入者句柄捆绑在一起的受 epoch 保护的单个值。
+///
+/// 适用于常见的配置切换场景，即只有一个值和一个写入者，无需手动将 `GcHandle`、`EpochGcDomain`
+/// 和 `EpochPtr` 组装在一起。`EpochCell` 本身就是写入者；其他线程上的读者使用从 `reader_handle()` 获得的 `EpochCellReader`。
+pub struct EpochCell<T: 'static> {
+    gc: GcHandle,
+    shared: EpochCellReader<T>,
+}
+
+/// A clonable reader-side handle to an `EpochCell`.
+///
+/// 一个可克隆的 `EpochCell` 读者侧句柄。
+pub struct EpochCellReader<T: 'static> {
+    domain: EpochGcDomain,
+    ptr: Arc<EpochPtr<T>>,
+}
+
+impl<T: 'static> EpochCell<T> {
+    /// Create a new cell holding `value`.
+    /// 创建一个持有 `value` 的新单元。
+    #[inline]
+    pub fn new(value: T) -> Self {
+        let (gc, domain) = EpochGcDomain::new();
+        Self {
+            gc,
+            shared: EpochCellReader {
+                domain,
+                ptr: Arc::new(EpochPtr::new(value)),
+            },
+        }
+    }
+
+    /// Get a reader-side handle that can be sent to reader threads.
+    /// 获取一个可以发送到读者线程的读者侧句柄。
+    #[inline]
+    pub fn reader_handle(&self) -> EpochCellReader<T> {
+        self.shared.clone()
+    }
+
+    /// Register a reader, see `EpochCellReader::reader()`.
+    /// 注册一个读者，参见 `EpochCellReader::reader()`。
+    #[inline]
+    pub fn reader(&self) -> LocalEpoch {
+        self.shared.reader()
+    }
+
+    /// Read the current value, see `EpochCellReader::read()`.
+    /// 读取当前值，参见 `EpochCellReader::read()`。
+    #[inline]
+    pub fn read<'guard>(&self, guard: &'guard PinGuard) -> &'guard T {
+        self.shared.read(guard)
+    }
+
+    /// Replace the value, then collect so the old value is freed as soon as readers allow.
+    /// 替换值，然后进行回收，使旧值在读者允许时尽快被释放。
+    #[inline]
+    pub fn write(&mut self, value: T)
+    where
+        T: Send,
+    {
+        self.shared.ptr.store(value, &mut self.gc);
+        self.gc.collect();
+    }
+}
+
+impl<T: 'static> EpochCellReader<T> {
+    /// Register a reader for the current thread.
+    /// 为当前线程注册一个读者。
+    #[inline]
+    pub fn reader(&self) -> LocalEpoch {
+        self.domain.register_reader()
+    }
+
+    /// Read the current value for the lifetime of the guard.
+    ///
+    /// The guard must come from a `LocalEpoch` returned by `reader()` on this cell.
+    ///
+    /// 在守卫的生命周期内读取当前值。
+    /// 守卫必须来自此单元的 `reader()` 返回的 `LocalEpoch`。
+    #[inline]
+    pub fn read<'guard>(&self, guard: &'guard PinGuard) -> &'guard T {
+        self.ptr.load(guard)
+    }
+}
+
+impl<T: 'static> Clone for EpochCellReader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            domain: self.domain.clone(),
+            ptr: self.ptr.clone(),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::garbage::GcHandle;
+use crate::ptr::EpochPtr;
+use crate::reader::PinGuard;
+use crate::sync::Ordering;
+use std::sync::Arc;
+
+/// Extension methods for `EpochPtr<Arc<T>>`.
+///
+/// Storing an `Arc<T>` behind an `EpochPtr` lets a reader take its own strong reference and
+/// keep the value alive after unpinning, so long-running work does not hold up reclamation.
+///
+/// 针对 `EpochPtr<Arc<T>>` 的扩展方法。
+/// 在 `EpochPtr` 后面存储 `Arc<T>` 可以让读者获取自己的强引用，并在解除钉住后继续保持值存活，
+/// 因此耗时较长的工作不会阻碍回收。
+pub trait LoadFull<T> {
+    /// Load the current `Arc` and clone it, so the value outlives the guard.
+    /// Panics if the pointer is null, like `EpochPtr::load()`.
+    ///
+    /// 加载当前的 `Arc` 并克隆它，使值的生命周期长于守卫。
+    /// 与 `EpochPtr::load()` 一样，如果指针为 null 则 panic。
+    fn load_full(&self, guard: &PinGuard) -> Arc<T>;
+}
+
+impl<T: 'static> LoadFull<T> for EpochPtr<Arc<T>> {
+    #[inline]
+    fn load_full(&self, guard: &PinGuard) -> Arc<T> {
+        Arc::clone(self.load(guard))
+    }
+}
+
+impl<T: 'static> EpochPtr<Arc<T>> {
+    /// Writer read-copy-update: replace the value with `f` applied to the current one.
+    ///
+    /// Loads the current `Arc`, wraps `f`'s result in a new `Arc`, stores it and retires the
+    /// old `Arc`, which is released once readers have unpinned. Returns the previous value.
+    /// Mirrors `ArcSwap::rcu`, except that `f` runs exactly once: the single writer cannot
+    /// race with another update, so no retry loop is needed. The exception is a helper using
+    /// `EpochPtr::compare_and_set_with_backoff()`: an update it makes while `f` runs is
+    /// overwritten based on a stale value.
+    ///
+    /// # Panics
+    /// Panics if the pointer is null.
+    ///
+    /// 写入者读-复制-更新：用 `f` 作用于当前值的结果替换值。
+    /// 加载当前的 `Arc`，将 `f` 的结果包装到新的 `Arc` 中并存储，然后退休旧的 `Arc`，它会在读者解除钉住后被释放。返回之前的值。
+    /// 与 `ArcSwap::rcu` 相对应，只是 `f` 恰好运行一次：唯一的写入者不会与其他更新竞争，因此不需要重试循环。
+    /// 例外是使用 `EpochPtr::compare_and_set_with_backoff()` 的辅助线程：它在 `f` 运行期间所做的更新会基于过时的值被覆盖。
+    ///
+    /// # Panics
+    /// 如果指针为 null 则 panic。
+    #[inline]
+    pub fn rcu<F>(&self, f: F, gc: &mut GcHandle) -> Arc<T>
+    where
+        T: Send + Sync,
+        F: FnOnce(&T) -> T,
+    {
+        let (_, current) = self
+            .writer_current()
+            .expect("rcu called on a null EpochPtr");
+        let previous = Arc::clone(current);
+        self.store(Arc::new(f(&previous)), gc);
+        previous
+    }
+}
+
+/// Extension methods for `EpochPtr<Option<T>>`.
+///
+/// Unlike a null `EpochPtr`, which panics when loaded, an `EpochPtr<Option<T>>` always holds
+/// a value, and `None` is an explicit state that readers can observe. Each store retires the
+/// previous boxed `Option`, so a replaced `Some` payload is dropped on reclamation like any
+/// other value.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::{EpochGcDomain, EpochPtr, OptionPtr};
+///
+/// let (mut gc, domain) = EpochGcDomain::new();
+/// let ptr = EpochPtr::new(None);
+/// ptr.store_some(String::from("ready"), &mut gc);
+///
+/// let local_epoch = domain.register_reader();
+/// assert_eq!(ptr.load_some(&local_epoch.pin()).map(String::as_str), Some("ready"));
+/// ```
+///
+/// 针对 `EpochPtr<Option<T>>` 的扩展方法。
+/// 与不得被加载的空 `EpochPtr` 不同，`EpochPtr<Option<T>>` 总是持有一个值，`None` 是读者可以观察到的显式状态。
+/// 每次存储都会退休之前被装箱的 `Option`，因此被替换的 `Some` 负载会像其他值一样在回收时被 drop。
+pub trait OptionPtr<T> {
+    /// Load the current value, borrowing the payload if it is `Some`.
+    /// 加载当前值，如果为 `Some` 则借用其负载。
+    fn load_some<'guard>(&self, guard: &'guard PinGuard) -> Option<&'guard T>;
+
+    /// Writer: store `Some(value)`, retiring the previous value.
+    /// 写入者：存储 `Some(value)`，并退休之前的值。
+    fn store_some(&self, value: T, gc: &mut GcHandle)
+    where
+        T: Send;
+
+    /// Writer: store `None`, retiring the previous value.
+    /// 写入者：存储 `None`，并退休之前的值。
+    fn store_none(&self, gc: &mut GcHandle)
+    where
+        T: Send;
+}
+
+impl<T: 'static> OptionPtr<T> for EpochPtr<Option<T>> {
+    #[inline]
+    fn load_some<'guard>(&self, guard: &'guard PinGuard) -> Option<&'guard T> {
+        self.load(guard).as_ref()
+    }
+
+    #[inline]
+    fn store_some(&self, value: T, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        self.store(Some(value), gc);
+    }
+
+    #[inline]
+    fn store_none(&self, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        self.store(None, gc);
+    }
+}
+
+/// Conversions between boxed and `Arc`-backed storage.
+///
+/// # Boxed or `Arc`-backed?
+/// - `EpochPtr<T>` owns its value exclusively. Reads are guard-bound references with no
+///   reference counting, so they are the cheapest, but the reader stays pinned (and holds up
+///   reclamation) for as long as it uses the value.
+/// - `EpochPtr<Arc<T>>` adds a reference count. A reader can call `load_full()` to take its
+///   own `Arc` and unpin right away, so the value can escape the guard, be sent to other
+///   threads or be kept across long-running work. This is the natural choice when migrating
+///   code that already shares `Arc<T>`.
+///
+/// Both conversions consume the pointer, so no new reader can load from it, and then wait
+/// until every reader that loaded from it earlier has unpinned before moving the value, so
+/// no outstanding reference is left dangling. They block for as long as such a reader stays
+/// pinned. A null pointer converts to a null pointer, which like any null pointer has to be
+/// initialized, e.g. with `init_once()`, before `load()` or `load_full()`; loading it
+/// earlier panics.
+///
+/// 在 boxed 存储与基于 `Arc` 的存储之间进行转换。
+///
+/// # 使用 Box 还是 `Arc`？
+/// - `EpochPtr<T>` 独占地拥有其值。读取得到的是绑定到守卫的引用，没有引用计数，因此开销最小，
+///   但读者在使用该值期间一直保持被钉住（并阻碍回收）。
+/// - `EpochPtr<Arc<T>>` 增加了引用计数。读者可以调用 `load_full()` 获取自己的 `Arc` 并立即解除钉住，
+///   因此值可以逃离守卫、被发送到其他线程或在长时间运行的工作中保留。迁移已经共享 `Arc<T>` 的代码时，这是自然的选择。
+///
+/// 两种转换都会消耗指针，因此不会再有新的读者从中加载；然后等待所有之前从中加载过的读者都解除钉住后再移动值，
+/// 因此不会留下任何悬垂引用。只要有这样的读者保持被钉住，它们就会阻塞。空指针会被转换为空指针，
+/// 与任何空指针一样，在 `load()` 或 `load_full()` 之前必须先被初始化（例如通过 `init_once()`），提前加载会 panic。
+impl<T: 'static> EpochPtr<T> {
+    /// Convert into an `Arc`-backed pointer holding the same value.
+    ///
+    /// See the section above on choosing between the two representations.
+    ///
+    /// # Example
+    /// ```
+    /// use swmr_epoch::{EpochGcDomain, EpochPtr, LoadFull};
+    ///
+    /// let (mut gc, domain) = EpochGcDomain::new();
+    /// let ptr = EpochPtr::new(String::from("config")).into_arc_ptr(&mut gc);
+    ///
+    /// let local_epoch = domain.register_reader();
+    /// let snapshot = ptr.load_full(&local_epoch.pin());
+    /// assert_eq!(*snapshot, "config");
+    /// ```
+    ///
+    /// 转换为持有相同值的基于 `Arc` 的指针。
+    /// 关于如何在两种表示之间选择，参见上面的说明。
+    pub fn into_arc_ptr(self, gc: &mut GcHandle) -> EpochPtr<Arc<T>> {
+        match self.take_quiesced(gc) {
+            Some(value) => EpochPtr::new(Arc::new(value)),
+            None => EpochPtr::null(),
+        }
+    }
+
+    /// Take the value out of a pointer that is no longer shared, once no reader can still
+    /// observe it.
+    /// 从不再被共享的指针中取出值，前提是已经没有读者还能观察到它。
+    fn take_quiesced(self, gc: &mut GcHandle) -> Option<T> {
+        let ptr = self.replace(std::ptr::null_mut(), Ordering::AcqRel);
+        if ptr.is_null() {
+            return None;
+        }
+
+        gc.quiesce();
+        Some(*unsafe { Box::from_raw(ptr) })
+    }
+}
+
+impl<T: Clone + 'static> EpochPtr<Arc<T>> {
+    /// Convert into a boxed pointer holding the same value.
+    ///
+    /// The value is moved out of the `Arc` if this was its last strong reference, and cloned
+    /// otherwise, since readers that called `load_full()` keep their own `Arc`s.
+    ///
+    /// 转换为持有相同值的 boxed 指针。
+    /// 如果这是 `Arc` 的最后一个强引用，则将值移出，否则进行克隆，因为调用过 `load_full()` 的读者保有自己的 `Arc`。
+    pub fn into_boxed_ptr(self, gc: &mut GcHandle) -> EpochPtr<T> {
+        match self.take_quiesced(gc) {
+            Some(value) => EpochPtr::new(Arc::unwrap_or_clone(value)),
+            None => EpochPtr::null(),
+        }
+    }
+}
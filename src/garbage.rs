@@ -1,25 +1,61 @@
-use crate::state::{INACTIVE_EPOCH, SharedState};
-use crate::sync::{Arc, Ordering};
+use crate::idle::IdleCollector;
+use crate::ptr::{EpochPtr, Storable};
+#[cfg(any(test, feature = "testing"))]
+use crate::quarantine::{Quarantined, quarantine};
+use crate::reader::{LocalEpoch, ReaderGroup};
+use crate::registry::ReaderList;
+use crate::state::{CANCEL_CHECK_INTERVAL, INACTIVE_EPOCH, SharedState};
+use crate::sync::{Arc, AtomicBool, Ordering, fence, yield_now};
+use std::any::Any;
 use std::boxed::Box;
+#[cfg(debug_assertions)]
+use std::collections::HashMap;
+#[cfg(debug_assertions)]
+use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::time::Instant;
 use std::vec::Vec;
 
 /// Alias for the retired object type used in garbage lists.
 /// 垃圾列表中使用的已退休对象类型的别名。
-type RetiredNode = RetiredObject;
+pub(crate) type RetiredNode = RetiredObject;
+
+/// Type-erased conversion of a retired object into a `Box<dyn Any>`.
+/// 将已退休对象转换为 `Box<dyn Any>` 的类型擦除函数。
+type IntoAny = unsafe fn(*mut ()) -> Box<dyn Any>;
 
 /// An object that has been retired (removed from shared view) but not yet deleted.
 /// It stores the raw pointer and a destructor function to safely drop the concrete type.
 ///
 /// 一个已被退休（从共享视图中移除）但尚未删除的对象。
 /// 它存储原始指针和析构函数，以安全地 drop 具体类型。
-struct RetiredObject {
+pub(crate) struct RetiredObject {
     /// The raw pointer to the data.
     /// 数据的原始指针。
     ptr: *mut (),
     /// Function pointer to the type-specific destructor.
     /// 类型特定析构函数的函数指针。
     dtor: unsafe fn(*mut ()),
+    /// Name of the retired type, for leak debugging.
+    /// 已退休类型的名称，用于泄漏调试。
+    #[cfg(debug_assertions)]
+    type_name: &'static str,
+    /// Whether `ptr` identifies the allocation, which is not the case for zero-sized values.
+    /// Used to detect a pointer being retired twice.
+    /// `ptr` 是否唯一标识该分配，对于零大小的值并非如此。用于检测同一指针被退休两次。
+    #[cfg(debug_assertions)]
+    unique_addr: bool,
+    /// Function pointer that drops the value but keeps its memory poisoned.
+    /// 丢弃值但保留其被毒化内存的函数指针。
+    /// `None` for objects with a caller-supplied destructor, whose layout is unknown.
+    /// 对于具有调用者提供的析构函数的对象为 `None`，因为其布局未知。
+    #[cfg(any(test, feature = "testing"))]
+    quarantine: Option<unsafe fn(*mut ()) -> Quarantined>,
+    /// Function pointer that hands the value back as a `Box<dyn Any>`, for `drain_safe()`.
+    /// `None` for objects whose concrete sized type is unknown.
+    /// 将值作为 `Box<dyn Any>` 交还的函数指针，用于 `drain_safe()`。对于具体的有大小类型未知的对象为 `None`。
+    into_any: Option<IntoAny>,
 }
 
 // Safety: RetiredObject is Send because we only access the pointer through dtor
@@ -38,17 +74,135 @@ unsafe fn drop_value<T>(ptr: *mut ()) {
     }
 }
 
+/// Generic conversion of a retired object back into an owned `Box<dyn Any>`.
+/// 将已退休对象转换回拥有所有权的 `Box<dyn Any>` 的通用函数。
+#[inline(always)]
+unsafe fn box_any<T: 'static>(ptr: *mut ()) -> Box<dyn Any> {
+    unsafe { Box::from_raw(ptr as *mut T) }
+}
+
+/// Type-erased destructor for values stored behind a thin pointer.
+///
+/// 存储在瘦指针后面的值的类型擦除析构函数。
+#[inline(always)]
+unsafe fn drop_thin<P: ?Sized + Storable>(ptr: *mut ()) {
+    unsafe {
+        P::drop_thin(ptr as *mut P::Thin);
+    }
+}
+
 impl RetiredObject {
+    /// Create a new retired object from a thin pointer produced by `Storable::into_thin`.
+    /// 从 `Storable::into_thin` 产生的瘦指针创建一个新的已退休对象。
+    #[inline(always)]
+    fn from_thin<P: ?Sized + Storable + Send>(ptr: *mut P::Thin) -> Self {
+        // A pointer still carrying its version tag would be freed at the wrong address; with
+        // over-aligned types the tag spans many low bits, so catch it here rather than in the
+        // allocator
+        debug_assert!(ptr.is_aligned(), "retired pointer is tagged or misaligned");
+        RetiredObject {
+            ptr: ptr as *mut (),
+            dtor: drop_thin::<P>,
+            #[cfg(debug_assertions)]
+            type_name: std::any::type_name::<P>(),
+            #[cfg(debug_assertions)]
+            unique_addr: std::mem::size_of::<P::Thin>() != 0,
+            #[cfg(any(test, feature = "testing"))]
+            quarantine: Some(quarantine::<P>),
+            into_any: None,
+        }
+    }
+
     /// Create a new retired object from a Box<T>.
     /// 从 Box<T> 创建一个新的已退休对象。
     #[inline(always)]
-    fn new<T: 'static>(value: Box<T>) -> Self {
+    pub(crate) fn new<T: Send + 'static>(value: Box<T>) -> Self {
         let ptr = Box::into_raw(value) as *mut ();
         RetiredObject {
             ptr,
             dtor: drop_value::<T>,
+            #[cfg(debug_assertions)]
+            type_name: std::any::type_name::<T>(),
+            #[cfg(debug_assertions)]
+            unique_addr: std::mem::size_of::<T>() != 0,
+            #[cfg(any(test, feature = "testing"))]
+            quarantine: Some(quarantine::<T>),
+            into_any: Some(box_any::<T>),
         }
     }
+
+    /// Take the value out as a `Box<dyn Any>`, or give the object back if its type is unknown.
+    /// 将值作为 `Box<dyn Any>` 取出；如果其类型未知，则交还该对象。
+    #[inline]
+    fn into_any(mut self) -> Result<Box<dyn Any>, Self> {
+        let Some(into_any) = self.into_any else {
+            return Err(self);
+        };
+        let value = unsafe { into_any(self.ptr) };
+        self.ptr = std::ptr::null_mut();
+        Ok(value)
+    }
+}
+
+/// A handle that reports whether one specific retired object has been reclaimed.
+///
+/// Returned by `EpochPtr::store_tracked()`. The flag is set right after the object's
+/// destructor has run, so `is_reclaimed()` becoming `true` means no reader can observe the
+/// object anymore. Useful for deterministic tests of reclamation timing and for tracking
+/// resources whose release must be observed.
+///
+/// 一个报告某个特定已退休对象是否已被回收的句柄。
+///
+/// 由 `EpochPtr::store_tracked()` 返回。标志在对象的析构函数运行之后立即被设置，
+/// 因此 `is_reclaimed()` 变为 `true` 意味着没有读者能再观察到该对象。
+/// 适用于回收时机的确定性测试，以及跟踪必须观察到其释放的资源。
+#[derive(Debug, Clone)]
+pub struct RetireToken {
+    reclaimed: Arc<AtomicBool>,
+}
+
+impl RetireToken {
+    /// Check whether the retired object has been reclaimed.
+    /// 检查已退休对象是否已被回收。
+    #[inline]
+    pub fn is_reclaimed(&self) -> bool {
+        self.reclaimed.load(Ordering::Acquire)
+    }
+}
+
+/// Sets the token's flag when dropped.
+/// 在被 drop 时设置令牌的标志。
+struct ReclaimFlag(Arc<AtomicBool>);
+
+impl Drop for ReclaimFlag {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// A retired value paired with its token's flag.
+/// Fields drop in declaration order, so the flag is set only after the value is gone.
+///
+/// 与其令牌标志配对的已退休值。
+/// 字段按声明顺序 drop，因此只有在值被销毁之后才会设置标志。
+struct Tracked<T> {
+    _value: Box<T>,
+    _flag: ReclaimFlag,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl RetiredObject {
+    /// Run the destructor but keep the memory, poisoned, in quarantine.
+    /// 运行析构函数，但将被毒化的内存保留在隔离区中。
+    /// Objects without a known layout are simply dropped.
+    /// 布局未知的对象会被直接 drop。
+    fn into_quarantine(mut self) -> Option<Quarantined> {
+        let quarantine = self.quarantine?;
+        let quarantined = unsafe { quarantine(self.ptr) };
+        self.ptr = std::ptr::null_mut();
+        Some(quarantined)
+    }
 }
 
 impl Drop for RetiredObject {
@@ -82,10 +236,23 @@ pub(crate) struct GarbageSet {
     /// Queue of garbage bags, ordered by epoch.
     /// Each element is (epoch, bag_of_nodes).
     queue: VecDeque<(usize, Vec<RetiredNode>)>,
+    /// Old generation: bags promoted out of `queue` after surviving several collections.
+    /// Always older than every bag in `queue`, and ordered by epoch as well.
+    old: VecDeque<(usize, Vec<RetiredNode>)>,
     /// Pool of empty vectors to reduce allocation.
     pool: Vec<Vec<RetiredNode>>,
     /// Total number of retired nodes in the queue.
     count: usize,
+    /// Queue index at which the next bounded collection resumes.
+    cursor: usize,
+    /// Number of bags above which a collection merges adjacent bags, if enabled.
+    pub(crate) coalesce_threshold: Option<usize>,
+    /// Poisoned allocations of reclaimed objects, if quarantine mode is enabled.
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) quarantine: Option<Vec<Quarantined>>,
+    /// Addresses of the retired objects not reclaimed yet, to catch double retirement.
+    #[cfg(debug_assertions)]
+    pending_addrs: HashSet<usize>,
 }
 
 impl GarbageSet {
@@ -94,8 +261,15 @@ impl GarbageSet {
     pub(crate) fn new() -> Self {
         Self {
             queue: VecDeque::new(),
+            old: VecDeque::new(),
             pool: Vec::new(),
             count: 0,
+            cursor: 0,
+            coalesce_threshold: None,
+            #[cfg(any(test, feature = "testing"))]
+            quarantine: None,
+            #[cfg(debug_assertions)]
+            pending_addrs: HashSet::new(),
         }
     }
 
@@ -106,6 +280,20 @@ impl GarbageSet {
         self.count
     }
 
+    /// Get the number of distinct epochs that still hold garbage.
+    /// 获取仍持有垃圾的不同纪元的数量。
+    #[inline]
+    pub(crate) fn epoch_count(&self) -> usize {
+        self.queue.len() + self.old.len()
+    }
+
+    /// Get the number of retired objects in the old generation.
+    /// 获取老年代中已退休对象的数量。
+    #[inline]
+    pub(crate) fn old_len(&self) -> usize {
+        self.old.iter().map(|(_, bag)| bag.len()).sum()
+    }
+
     /// Add a retired node to the set for the current epoch.
     ///
     /// If the last bag belongs to the current epoch, the node is appended to it.
@@ -117,6 +305,17 @@ impl GarbageSet {
     /// 否则，创建一个新袋子（可能从池中复用）。
     #[inline]
     fn add(&mut self, node: RetiredNode, current_epoch: usize) {
+        #[cfg(debug_assertions)]
+        if node.unique_addr && !self.pending_addrs.insert(node.ptr as usize) {
+            let (addr, type_name) = (node.ptr, node.type_name);
+            // Dropping the duplicate would free the allocation a first time already
+            std::mem::forget(node);
+            panic!(
+                "{addr:p} ({type_name}) was retired twice: it is still waiting to be reclaimed, \
+                 so reclaiming both would free it twice"
+            );
+        }
+
         // Check if we can append to the last bag
         let append_to_last = if let Some((last_epoch, _)) = self.queue.back() {
             *last_epoch == current_epoch
@@ -137,6 +336,126 @@ impl GarbageSet {
         self.count += 1;
     }
 
+    /// Count retired nodes by type name.
+    /// 按类型名称统计已退休节点。
+    #[cfg(debug_assertions)]
+    pub(crate) fn type_counts(&self) -> HashMap<&'static str, usize> {
+        let mut counts = HashMap::new();
+        let bags = self.old.iter().chain(self.queue.iter());
+        for node in bags.flat_map(|(_, bag)| bag.iter()) {
+            *counts.entry(node.type_name).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Move all of `other`'s retired nodes into this set under `current_epoch`.
+    ///
+    /// 将 `other` 的所有已退休节点以 `current_epoch` 移入此集合。
+    pub(crate) fn absorb(&mut self, other: &mut GarbageSet, current_epoch: usize) {
+        other.merge_old();
+        while let Some((_, mut bag)) = other.queue.pop_front() {
+            for node in bag.drain(..) {
+                self.add(node, current_epoch);
+            }
+            other.pool.push(bag);
+        }
+        other.count = 0;
+        other.cursor = 0;
+        #[cfg(debug_assertions)]
+        other.pending_addrs.clear();
+    }
+
+    /// Move every bag retired at `since` or later to epoch `epoch`, unless it is newer already.
+    ///
+    /// Used when the global epoch was advanced from outside the writer: a reader that pinned
+    /// at one of those foreign epochs is not guaranteed to see the writes that preceded the
+    /// retirement, so those bags must wait until the writer's own next epoch is reached.
+    ///
+    /// 将每个在 `since` 或之后退休的袋子移到纪元 `epoch`，除非它已经更新。
+    /// 用于全局纪元被写入者之外推进的情况：钉住于这些外部纪元之一的读者不保证能看到退休之前的写入，
+    /// 因此这些袋子必须等到写入者自己的下一个纪元才能回收。
+    pub(crate) fn retag_since(&mut self, since: usize, epoch: usize) {
+        for (bag_epoch, _) in self.queue.iter_mut().rev() {
+            if *bag_epoch < since {
+                break;
+            }
+            *bag_epoch = (*bag_epoch).max(epoch);
+        }
+    }
+
+    /// Move the old generation back to the front of the queue, so that the next collection
+    /// scans every bag.
+    ///
+    /// 将老年代移回队列前部，使下一次回收扫描所有袋子。
+    fn merge_old(&mut self) {
+        let moved = self.old.len();
+        while let Some(entry) = self.old.pop_back() {
+            self.queue.push_front(entry);
+        }
+        self.cursor += moved;
+    }
+
+    /// Clear a bag (dropping all retired objects inside) and return it to the pool.
+    /// 清空一个袋子（drop 其中所有已退休对象）并将其归还到池中。
+    #[inline]
+    fn recycle_bag(&mut self, mut bag: Vec<RetiredNode>) {
+        #[cfg(debug_assertions)]
+        for node in &bag {
+            self.pending_addrs.remove(&(node.ptr as usize));
+        }
+
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(quarantine) = &mut self.quarantine {
+            quarantine.extend(bag.drain(..).filter_map(RetiredObject::into_quarantine));
+        }
+
+        bag.clear();
+        self.pool.push(bag);
+    }
+
+    /// Remove every reclaimable node and return them instead of dropping them.
+    /// 移除所有可回收的节点并返回它们，而不是 drop 它们。
+    pub(crate) fn drain(
+        &mut self,
+        min_active_epoch: usize,
+        current_epoch: usize,
+    ) -> Vec<RetiredNode> {
+        self.merge_old();
+        let reclaimable = self.reclaimable_bags(min_active_epoch, current_epoch);
+
+        let mut drained = Vec::new();
+        for _ in 0..reclaimable {
+            if let Some((_, mut bag)) = self.queue.pop_front() {
+                #[cfg(debug_assertions)]
+                for node in &bag {
+                    self.pending_addrs.remove(&(node.ptr as usize));
+                }
+                drained.append(&mut bag);
+                self.pool.push(bag);
+            }
+        }
+
+        self.cursor = 0;
+        self.count = self.queue.iter().map(|(_, bag)| bag.len()).sum();
+        drained
+    }
+
+    /// Number of bags at the front of the queue that are safe to reclaim.
+    /// 队列前部可以安全回收的袋子数量。
+    fn reclaimable_bags(&self, min_active_epoch: usize, current_epoch: usize) -> usize {
+        if min_active_epoch == current_epoch {
+            self.queue.len()
+        } else if min_active_epoch > 0 {
+            let safe_to_reclaim_epoch = min_active_epoch - 1;
+            self.queue
+                .iter()
+                .take_while(|(epoch, _)| *epoch <= safe_to_reclaim_epoch)
+                .count()
+        } else {
+            0
+        }
+    }
+
     /// Reclaim garbage that is safe to delete.
     ///
     /// Garbage from epochs older than `min_active_epoch` (or `min_active_epoch - 1` depending on logic)
@@ -147,34 +466,387 @@ impl GarbageSet {
     /// 来自比 `min_active_epoch`（或 `min_active_epoch - 1`，取决于逻辑）更旧的纪元的垃圾
     /// 被清除，向量被归还到池中。
     pub(crate) fn collect(&mut self, min_active_epoch: usize, current_epoch: usize) {
-        // Helper closure to recycle a bag
-        fn recycle_bag(mut bag: Vec<RetiredNode>, pool: &mut Vec<Vec<RetiredNode>>) {
-            bag.clear(); // Drops all retired objects inside
-            pool.push(bag);
+        self.merge_old();
+        self.collect_young(min_active_epoch, current_epoch, None);
+    }
+
+    /// Reclaim safe garbage from the young generation only, then promote bags that have
+    /// survived at least `promote_after` collections to the old generation.
+    ///
+    /// The global epoch advances by one per collection, so the number of collections a bag
+    /// has survived is at most the distance between its epoch and the current epoch (fewer
+    /// if the epoch was also advanced with `EpochGcDomain::advance_epoch_only()`).
+    ///
+    /// 仅回收年轻代中安全的垃圾，然后将至少经历了 `promote_after` 次回收的袋子提升到老年代。
+    /// 每次回收全局纪元推进一，因此一个袋子经历的回收次数至多是其纪元与当前纪元之间的距离
+    /// （如果纪元还通过 `EpochGcDomain::advance_epoch_only()` 推进过，则会更少）。
+    pub(crate) fn collect_young(
+        &mut self,
+        min_active_epoch: usize,
+        current_epoch: usize,
+        promote_after: Option<usize>,
+    ) {
+        let reclaimable = self.reclaimable_bags(min_active_epoch, current_epoch);
+
+        for _ in 0..reclaimable {
+            // Pop and recycle
+            if let Some((_, bag)) = self.queue.pop_front() {
+                self.recycle_bag(bag);
+            }
         }
 
-        if min_active_epoch == current_epoch {
-            // Reclaim everything
-            for (_, bag) in self.queue.drain(..) {
-                recycle_bag(bag, &mut self.pool);
+        if let Some(cycles) = promote_after {
+            while let Some((epoch, _)) = self.queue.front()
+                && current_epoch - epoch >= cycles
+            {
+                let entry = self.queue.pop_front().unwrap();
+                self.old.push_back(entry);
             }
-        } else if min_active_epoch > 0 {
-            let safe_to_reclaim_epoch = min_active_epoch - 1;
-            while let Some((epoch, _)) = self.queue.front() {
-                if *epoch > safe_to_reclaim_epoch {
-                    break;
-                }
-                // Pop and recycle
-                if let Some((_, bag)) = self.queue.pop_front() {
-                    recycle_bag(bag, &mut self.pool);
+        }
+
+        self.coalesce();
+        self.cursor = 0;
+        self.count = self.old_len() + self.queue.iter().map(|(_, bag)| bag.len()).sum::<usize>();
+    }
+
+    /// Merge adjacent bags until the queue holds at most `coalesce_threshold` bags.
+    ///
+    /// Each step merges the two adjacent bags whose epochs are closest, preferring the newest
+    /// pair on ties, and the merged bag takes the newer epoch. A bag is reclaimed once its
+    /// epoch is safe, so objects from the older bag may be held back a little longer, but never
+    /// freed earlier. Picking the closest pair keeps that delay small and leaves old garbage
+    /// in place instead of dragging it forward on every collection. Epochs stay in ascending
+    /// order along the queue, since the merged bag sits between the bags that surrounded the
+    /// pair.
+    ///
+    /// 合并相邻的袋子，直到队列最多持有 `coalesce_threshold` 个袋子。
+    /// 每一步合并纪元最接近的两个相邻袋子（相同时优先最新的一对），合并后的袋子采用较新的纪元。
+    /// 袋子在其纪元安全后才会被回收，因此较旧袋子中的对象可能会被多保留一段时间，但绝不会被提前释放。
+    /// 选择最接近的一对可以使这一延迟保持较小，并使旧垃圾留在原处，而不是在每次回收时都被向前拖动。
+    /// 队列中的纪元保持升序，因为合并后的袋子位于原先包围这一对袋子的袋子之间。
+    fn coalesce(&mut self) {
+        let Some(threshold) = self.coalesce_threshold else {
+            return;
+        };
+
+        while self.queue.len() > threshold {
+            let mut closest = 0;
+            for i in 1..self.queue.len() - 1 {
+                let gap = self.queue[i + 1].0 - self.queue[i].0;
+                if gap <= self.queue[closest + 1].0 - self.queue[closest].0 {
+                    closest = i;
                 }
             }
+
+            let (_, mut older) = self.queue.remove(closest).unwrap();
+            let newer = &mut self.queue[closest].1;
+            // Move the smaller bag into the larger one
+            if older.len() > newer.len() {
+                std::mem::swap(&mut older, newer);
+            }
+            newer.append(&mut older);
+            self.pool.push(older);
         }
+    }
 
-        self.count = self.queue.iter().map(|(_, bag)| bag.len()).sum();
+    /// Reclaim safe garbage one bag at a time until `deadline` passes.
+    ///
+    /// Reclamation resumes at the bag following the one reclaimed last, wrapping around the
+    /// reclaimable part of the queue, so successive bounded calls make progress across all
+    /// bags instead of repeatedly starting from the front. At least one bag is reclaimed per
+    /// call if any is reclaimable. Returns `true` if no reclaimable garbage is left.
+    ///
+    /// 逐个袋子回收安全的垃圾，直到超过 `deadline`。
+    ///
+    /// 回收从上次回收的袋子之后继续，并在队列的可回收部分内循环，
+    /// 因此连续的有界调用会在所有袋子上取得进展，而不是每次都从队首开始。
+    /// 如果存在可回收的袋子，每次调用至少回收一个。如果没有剩余的可回收垃圾，返回 `true`。
+    pub(crate) fn collect_until(
+        &mut self,
+        min_active_epoch: usize,
+        current_epoch: usize,
+        deadline: Instant,
+    ) -> bool {
+        self.merge_old();
+        let mut reclaimable = self.reclaimable_bags(min_active_epoch, current_epoch);
+
+        while reclaimable > 0 {
+            if self.cursor >= reclaimable {
+                self.cursor = 0;
+            }
+
+            if let Some((_, bag)) = self.queue.remove(self.cursor) {
+                self.count -= bag.len();
+                self.recycle_bag(bag);
+            }
+            reclaimable -= 1;
+
+            if reclaimable > 0 && Instant::now() >= deadline {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Reclaim at most `limit` safe objects.
+    ///
+    /// Resumes at the same cursor as `collect_until`, and may leave a bag partially reclaimed.
+    /// Returns `true` if no reclaimable garbage is left.
+    ///
+    /// 最多回收 `limit` 个安全的对象。
+    /// 与 `collect_until` 使用相同的游标继续，可能只回收一个袋子的一部分。如果没有剩余的可回收垃圾，返回 `true`。
+    pub(crate) fn collect_bounded(
+        &mut self,
+        min_active_epoch: usize,
+        current_epoch: usize,
+        limit: usize,
+    ) -> bool {
+        self.merge_old();
+        let mut reclaimable = self.reclaimable_bags(min_active_epoch, current_epoch);
+        let mut remaining = limit;
+
+        while reclaimable > 0 && remaining > 0 {
+            if self.cursor >= reclaimable {
+                self.cursor = 0;
+            }
+
+            let bag = &mut self.queue[self.cursor].1;
+            if bag.len() <= remaining {
+                let (_, bag) = self.queue.remove(self.cursor).unwrap();
+                remaining -= bag.len();
+                self.count -= bag.len();
+                self.recycle_bag(bag);
+                reclaimable -= 1;
+            } else {
+                let mut part = self.pool.pop().unwrap_or_default();
+                part.extend(bag.drain(..remaining));
+                self.count -= remaining;
+                remaining = 0;
+                self.recycle_bag(part);
+            }
+        }
+
+        reclaimable == 0
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for GcHandle {
+    /// Warn if garbage was retired but no collection ever ran, and report readers that are
+    /// still pinned if `panic_on_reader_leak` is enabled.
+    ///
+    /// A handle that is kept around but never collects, with automatic reclamation disabled
+    /// and no explicit `collect()` calls, lets garbage pile up for its whole lifetime and only
+    /// frees it here. Debug builds print a warning in that case. With automatic reclamation
+    /// enabled, the pending garbage is bounded by the threshold, so a short-lived handle that
+    /// never reached it is not reported.
+    ///
+    /// 如果退休了垃圾但从未运行过回收则发出警告，并在启用了 `panic_on_reader_leak` 时报告仍被钉住的读者。
+    /// 一个被保留但从不回收的句柄（禁用了自动回收且从未显式调用 `collect()`）会让垃圾在其整个生命周期内不断堆积，
+    /// 直到此处才被释放。调试构建会在这种情况下打印一条警告。启用自动回收时，待回收垃圾受阈值限制，
+    /// 因此从未达到阈值的短生命周期句柄不会被报告。
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+
+        // `last_epoch` is only set once this handle has advanced the epoch, i.e. collected
+        let pending = self.total_garbage_count();
+        if pending > 0 && self.last_epoch == 0 && self.auto_reclaim_threshold.is_none() {
+            let domain = self.shared.describe();
+            warn(format_args!(
+                "GcHandle{domain} dropped with {pending} retired object(s) and no collection \
+                 ever run. Call collect() periodically or keep automatic reclamation enabled, \
+                 otherwise garbage is only freed when the handle is dropped."
+            ));
+        }
+
+        if !self.panic_on_reader_leak {
+            return;
+        }
+
+        let pinned = self
+            .shared
+            .lock_readers()
+            .iter()
+            .filter(|slot| slot.active_epoch.load(Ordering::Acquire) != INACTIVE_EPOCH)
+            .count();
+        assert!(
+            pinned == 0,
+            "GcHandle{} dropped while {pinned} reader(s) are still pinned. A PinGuard has \
+             probably been leaked, which prevents any garbage from being reclaimed.",
+            self.shared.describe()
+        );
+    }
+}
+
+#[cfg(all(debug_assertions, test))]
+thread_local! {
+    /// Warnings emitted on this thread, captured instead of printed so tests can inspect them.
+    /// 在此线程上发出的警告，被捕获而不是打印，以便测试检查它们。
+    pub(crate) static WARNINGS: std::cell::RefCell<Vec<String>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Print a diagnostic warning to stderr. Unit tests capture it in `WARNINGS` instead.
+/// 将诊断警告打印到 stderr。单元测试则将其捕获到 `WARNINGS` 中。
+#[cfg(debug_assertions)]
+fn warn(message: std::fmt::Arguments<'_>) {
+    #[cfg(test)]
+    WARNINGS.with_borrow_mut(|warnings| warnings.push(message.to_string()));
+    #[cfg(not(test))]
+    eprintln!("swmr-epoch: warning: {message}");
+}
+
+/// Number of buckets in `GcHandle::collect_latency_histogram()`.
+///
+/// Bucket `i` counts collections that took less than `4^i` microseconds (and at least
+/// `4^(i-1)`), so the buckets cover 1µs, 4µs, 16µs, 64µs, 256µs, ~1ms and ~4ms; the last
+/// bucket counts everything slower.
+///
+/// `GcHandle::collect_latency_histogram()` 中的桶数量。
+/// 桶 `i` 统计耗时少于 `4^i` 微秒（且至少 `4^(i-1)` 微秒）的回收，因此各桶覆盖 1µs、4µs、16µs、64µs、256µs、约 1ms 和约 4ms；
+/// 最后一个桶统计所有更慢的回收。
+#[cfg(feature = "metrics")]
+pub const COLLECT_LATENCY_BUCKETS: usize = 8;
+
+/// Index of the latency histogram bucket for a collection that took `elapsed`.
+/// 耗时为 `elapsed` 的回收所属的延迟直方图桶的索引。
+#[cfg(feature = "metrics")]
+fn latency_bucket(elapsed: std::time::Duration) -> usize {
+    let micros = elapsed.as_micros();
+    (0..COLLECT_LATENCY_BUCKETS - 1)
+        .find(|&i| micros < 4u128.pow(i as u32))
+        .unwrap_or(COLLECT_LATENCY_BUCKETS - 1)
+}
+
+/// How far pinned readers lag behind the global epoch, returned by `GcHandle::reader_lag_stats()`.
+///
+/// A reader's lag is the global epoch minus the epoch it is pinned at. Garbage retired since
+/// the most lagging reader pinned cannot be reclaimed, so a high `p99` usually means memory is
+/// being held back by a few slow readers. Percentiles use the nearest-rank method. Unpinned
+/// readers are not included, and all fields are `0` when no reader is pinned.
+///
+/// 被钉住的读者落后于全局纪元的程度，由 `GcHandle::reader_lag_stats()` 返回。
+/// 读者的滞后量是全局纪元减去其被钉住的纪元。自最滞后的读者钉住以来退休的垃圾都无法被回收，
+/// 因此较高的 `p99` 通常意味着少数慢读者正在占住内存。百分位数使用最近秩方法计算。
+/// 未被钉住的读者不计入在内，没有读者被钉住时所有字段均为 `0`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LagStats {
+    /// Number of readers pinned at the time of the scan.
+    /// 扫描时被钉住的读者数量。
+    pub pinned: usize,
+    /// Lag of the most up-to-date pinned reader.
+    /// 最新的被钉住读者的滞后量。
+    pub min: usize,
+    /// Median lag.
+    /// 滞后量的中位数。
+    pub median: usize,
+    /// 99th percentile lag.
+    /// 滞后量的第 99 百分位数。
+    pub p99: usize,
+}
+
+impl LagStats {
+    /// Compute the statistics from the pinned epochs, sorted in ascending order.
+    /// 根据按升序排列的被钉住纪元计算统计数据。
+    pub(crate) fn new(global_epoch: usize, pinned_epochs: &[usize]) -> Self {
+        let pinned = pinned_epochs.len();
+        if pinned == 0 {
+            return Self::default();
+        }
+
+        // Ascending epochs are descending lags, so rank `r` from the low end of the lags is
+        // `pinned - r` from the low end of the epochs
+        let lag = |percent: usize| {
+            let rank = (percent * pinned).div_ceil(100).max(1);
+            global_epoch.saturating_sub(pinned_epochs[pinned - rank])
+        };
+
+        Self {
+            pinned,
+            min: lag(0),
+            median: lag(50),
+            p99: lag(99),
+        }
     }
 }
 
+/// Summary of one collection cycle, returned by `GcHandle::collect_detailed()`.
+///
+/// 一个回收周期的摘要，由 `GcHandle::collect_detailed()` 返回。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CollectReport {
+    /// Number of retired objects reclaimed.
+    /// 被回收的已退休对象数量。
+    pub reclaimed: usize,
+    /// Number of dead reader slots removed from the readers list.
+    /// 从读者列表中移除的死读者槽数量。
+    pub dead_readers_removed: usize,
+    /// The global epoch after this cycle.
+    /// 此周期之后的全局纪元。
+    pub epoch: usize,
+    /// The newest epoch whose garbage became reclaimable this cycle, or `None` if a reader is
+    /// still pinned at epoch 0. Everything retired at or before this epoch has been reclaimed.
+    /// 此周期中其垃圾变为可回收的最新纪元；如果仍有读者被钉住于纪元 0 则为 `None`。
+    /// 在此纪元或更早退休的所有对象都已被回收。
+    pub safe_epoch: Option<usize>,
+}
+
+impl CollectReport {
+    /// The epochs whose garbage is reclaimed once this cycle is done, i.e. `0..=safe_epoch`.
+    ///
+    /// Lets external systems key their own cleanup to the same boundary: anything they tagged
+    /// with an epoch in this range is no longer visible to any reader of this domain.
+    ///
+    /// 此周期完成后其垃圾已被回收的纪元，即 `0..=safe_epoch`。
+    /// 使外部系统可以将自己的清理与同一边界对齐：它们以此范围内的纪元标记的任何东西都不再对此域的任何读者可见。
+    #[inline]
+    pub fn reclaimable_epochs(&self) -> Option<RangeInclusive<usize>> {
+        self.safe_epoch.map(|epoch| 0..=epoch)
+    }
+}
+
+/// Outcome of `GcHandle::shutdown()`.
+///
+/// `GcHandle::shutdown()` 的结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ShutdownReport {
+    /// Number of retired objects reclaimed during shutdown.
+    /// 关闭期间被回收的已退休对象数量。
+    pub reclaimed: usize,
+    /// Number of retired objects that could not be reclaimed before the deadline and were
+    /// leaked instead of being freed under a reader.
+    /// 在截止时间之前无法回收、因此被泄漏而不是在读者仍可访问时被释放的已退休对象数量。
+    pub leaked: usize,
+    /// Number of readers still pinned when shutdown finished.
+    /// 关闭结束时仍被钉住的读者数量。
+    pub pinned_readers: usize,
+}
+
+impl ShutdownReport {
+    /// Whether all garbage was reclaimed and no reader was left pinned.
+    /// 是否所有垃圾都已被回收，且没有读者仍被钉住。
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.leaked == 0 && self.pinned_readers == 0
+    }
+}
+
+/// A generation of retired garbage, for `GcHandle::collect_generation()`.
+///
+/// 已退休垃圾的代，用于 `GcHandle::collect_generation()`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Generation {
+    /// Recently retired garbage, scanned on every young collection.
+    /// 最近退休的垃圾，每次年轻代回收时都会被扫描。
+    Young,
+    /// Garbage promoted after surviving several collections; collecting it scans everything.
+    /// 经历多次回收后被提升的垃圾；回收它会扫描所有垃圾。
+    Old,
+}
+
 /// The unique garbage collector handle for an epoch GC domain.
 ///
 /// There should be exactly one `GcHandle` per `EpochGcDomain`, owned by the writer thread.
@@ -184,6 +856,16 @@ impl GarbageSet {
 /// - Scanning active readers and reclaiming garbage from old epochs.
 ///
 /// **Thread Safety**: `GcHandle` is not thread-safe and must be owned by a single thread.
+/// It may be moved to the writer thread, but it cannot be shared between threads:
+///
+/// ```compile_fail
+/// use swmr_epoch::EpochGcDomain;
+///
+/// let (gc, _domain) = EpochGcDomain::new();
+/// std::thread::scope(|s| {
+///     s.spawn(|| gc.pending_epochs()); // error: `GcHandle` is not `Sync`
+/// });
+/// ```
 ///
 /// 一个 epoch GC 域的唯一垃圾回收器句柄。
 /// 每个 `EpochGcDomain` 应该恰好有一个 `GcHandle`，由写入者线程持有。
@@ -191,13 +873,39 @@ impl GarbageSet {
 /// - 在回收周期中推进全局纪元。
 /// - 从 `EpochPtr::store()` 接收已退休对象。
 /// - 扫描活跃读者并回收旧纪元的垃圾。
+///
 /// **线程安全性**：`GcHandle` 不是线程安全的，必须由单个线程持有。
 pub struct GcHandle {
     pub(crate) shared: Arc<SharedState>,
     pub(crate) garbage: GarbageSet,
     pub(crate) auto_reclaim_threshold: Option<usize>,
+    pub(crate) collect_batch_size: Option<usize>,
+    pub(crate) max_pending_garbage: Option<usize>,
+    pub(crate) promote_after: Option<usize>,
     pub(crate) collection_counter: usize,
     pub(crate) cleanup_interval: usize,
+    pub(crate) scan_chunk: usize,
+    /// Index of a reader slot that was pinned at the published minimum active epoch when it
+    /// was last computed, so the next scan can check that slot first.
+    /// 上次计算最小活跃纪元时被钉住于该纪元的某个读者槽的索引，以便下一次扫描先检查该槽。
+    pub(crate) min_holder: Option<usize>,
+    /// The epoch the writer itself last advanced to, to detect advances made from the domain.
+    /// 写入者自己上次推进到的纪元，用于检测从域中进行的推进。
+    pub(crate) last_epoch: usize,
+    /// Number of collections triggered by the auto-reclaim threshold, wrapping on overflow.
+    /// 由自动回收阈值触发的回收次数，溢出时回绕。
+    pub(crate) auto_collections: usize,
+    /// Helper thread requesting collections while the writer is idle, if configured.
+    /// 在写入者空闲时请求回收的辅助线程（如果已配置）。
+    pub(crate) idle: Option<IdleCollector>,
+    /// Histogram of `collect()` durations, see `COLLECT_LATENCY_BUCKETS`.
+    /// `collect()` 耗时的直方图，参见 `COLLECT_LATENCY_BUCKETS`。
+    #[cfg(feature = "metrics")]
+    pub(crate) collect_latency: [u64; COLLECT_LATENCY_BUCKETS],
+    /// Whether dropping the handle panics if a reader is still pinned.
+    /// drop 此句柄时如果仍有读者被钉住是否 panic。
+    #[cfg(debug_assertions)]
+    pub(crate) panic_on_reader_leak: bool,
 }
 
 impl GcHandle {
@@ -206,6 +914,31 @@ impl GcHandle {
         self.garbage.len()
     }
 
+    /// Get the number of distinct epochs that currently hold unreclaimed garbage.
+    ///
+    /// A count that keeps growing across collection cycles usually means a reader is stuck
+    /// pinned to an old epoch and is preventing reclamation.
+    ///
+    /// 获取当前持有未回收垃圾的不同纪元的数量。
+    ///
+    /// 如果该数量在多个回收周期中持续增长，通常意味着某个读者被卡在旧纪元上，阻止了回收。
+    #[inline]
+    pub fn pending_epochs(&self) -> usize {
+        self.garbage.epoch_count()
+    }
+
+    /// Compute how far the pinned readers lag behind the global epoch.
+    ///
+    /// Scans the reader slots once, like a collection does, without advancing the epoch or
+    /// reclaiming anything. See `LagStats` for how the statistics are defined.
+    ///
+    /// 计算被钉住的读者落后于全局纪元的程度。
+    /// 像回收一样扫描一次读者槽，但不推进纪元，也不回收任何东西。统计数据的定义见 `LagStats`。
+    pub fn reader_lag_stats(&self) -> LagStats {
+        let global_epoch = self.shared.global_epoch.load(Ordering::Acquire);
+        LagStats::new(global_epoch, &self.shared.pinned_epochs_sorted())
+    }
+
     /// Retire (defer deletion) of a value.
     ///
     /// The value is stored in a garbage bin associated with the current epoch.
@@ -230,18 +963,418 @@ impl GcHandle {
     /// 默认阈值是 `AUTO_RECLAIM_THRESHOLD`（64）。
     /// 要禁用自动回收，请向 `new_with_threshold()` 传递 `None`。
     #[inline]
-    pub(crate) fn retire<T: 'static>(&mut self, data: Box<T>) {
-        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
+    pub(crate) fn retire<T: Send + 'static>(&mut self, data: Box<T>) {
+        self.retire_node(RetiredObject::new(data));
+    }
 
+    /// Retire a value, or drop it right away if no reader is registered.
+    ///
+    /// With no registered reader nobody can be reading `data`, so it skips the garbage queue
+    /// entirely, which keeps writer-only workloads free of reclamation work. A reader counts as
+    /// registered from `register_reader()` until its slot is cleaned up. Quarantine mode
+    /// always retires, so that reclaimed objects still end up poisoned.
+    ///
+    /// 退休一个值，如果没有注册任何读者则立即 drop 它。
+    /// 没有注册的读者时，没有人能读取 `data`，因此它完全跳过垃圾队列，使只有写入者的工作负载没有任何回收开销。
+    /// 读者从 `register_reader()` 起直到其槽被清理为止都被视为已注册。隔离模式总是退休，使被回收的对象仍然被毒化。
+    #[inline]
+    pub(crate) fn retire_or_free<T: Send + 'static>(&mut self, data: Box<T>) {
+        #[cfg(any(test, feature = "testing"))]
+        if self.garbage.quarantine.is_some() {
+            self.retire(data);
+            return;
+        }
+
+        // Order the pointer swap before reading the reader count; pairs with `add_reader`.
+        fence(Ordering::SeqCst);
+        if self.shared.reader_count.load(Ordering::SeqCst) == 0 {
+            drop(data);
+        } else {
+            self.retire(data);
+        }
+    }
+
+    /// Retire a value without checking the auto-reclaim threshold.
+    /// 退休一个值，但不检查自动回收阈值。
+    #[inline]
+    pub(crate) fn retire_no_autocollect<T: Send + 'static>(&mut self, data: Box<T>) {
+        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
         self.garbage.add(RetiredObject::new(data), current_epoch);
+        if let Some(idle) = &self.idle {
+            idle.note_retire();
+        }
+    }
+
+    /// Retire memory that is not a Rust `Box`, with a caller-supplied destructor.
+    ///
+    /// For allocations owned through FFI or a custom allocator: `dtor(ptr)` is called once no
+    /// reader can still observe `ptr`, exactly like for values retired by `EpochPtr::store()`.
+    /// Such objects are dropped normally even in quarantine mode, since their layout is
+    /// unknown. Debug builds panic if `ptr` is retired again while it is still waiting to be
+    /// reclaimed, before the double free can happen.
+    ///
+    /// # Safety
+    /// - Ownership of `ptr` is transferred: the caller must not use or free it afterwards,
+    ///   and it must no longer be reachable by readers that pin from now on.
+    /// - `dtor` must correctly release `ptr`, may be called on any thread that owns this
+    ///   handle, and must not panic.
+    ///
+    /// 退休一块不是 Rust `Box` 的内存，使用调用者提供的析构函数。
+    /// 适用于通过 FFI 或自定义分配器拥有的分配：一旦没有读者能再观察到 `ptr`，就会调用 `dtor(ptr)`，
+    /// 与通过 `EpochPtr::store()` 退休的值完全相同。由于布局未知，即使在隔离模式下这类对象也会被正常 drop。
+    /// 如果 `ptr` 在仍等待回收时被再次退休，调试构建会在双重释放发生之前 panic。
+    ///
+    /// # Safety
+    /// - `ptr` 的所有权被转移：调用者之后不得再使用或释放它，且从现在起钉住的读者不得再能访问到它。
+    /// - `dtor` 必须正确释放 `ptr`，可能在任何持有此句柄的线程上被调用，且不得 panic。
+    pub unsafe fn retire_raw(&mut self, ptr: *mut (), dtor: unsafe fn(*mut ())) {
+        self.retire_node(RetiredObject {
+            ptr,
+            dtor,
+            #[cfg(debug_assertions)]
+            type_name: "<raw>",
+            #[cfg(debug_assertions)]
+            unique_addr: !ptr.is_null(),
+            #[cfg(any(test, feature = "testing"))]
+            quarantine: None,
+            into_any: None,
+        });
+    }
+
+    /// Retire a value and return a token reporting when it has been reclaimed.
+    /// 退休一个值，并返回一个报告其何时被回收的令牌。
+    #[inline]
+    pub(crate) fn retire_tracked<T: Send + 'static>(&mut self, data: Box<T>) -> RetireToken {
+        let reclaimed = Arc::new(AtomicBool::new(false));
+        self.retire(Box::new(Tracked {
+            _value: data,
+            _flag: ReclaimFlag(reclaimed.clone()),
+        }));
+        RetireToken { reclaimed }
+    }
+
+    /// Retire a value stored behind a thin pointer (see `Storable`).
+    ///
+    /// # Safety
+    /// `ptr` must come from `P::into_thin` and must no longer be reachable by new readers.
+    ///
+    /// 退休一个存储在瘦指针后面的值（见 `Storable`）。
+    #[inline]
+    pub(crate) unsafe fn retire_thin<P: ?Sized + Storable + Send>(&mut self, ptr: *mut P::Thin) {
+        self.retire_node(RetiredObject::from_thin::<P>(ptr));
+    }
+
+    /// Enqueue a retired node for the current epoch and auto-collect if over the threshold.
+    /// 将已退休节点加入当前纪元，如果超过阈值则自动回收。
+    #[inline]
+    fn retire_node(&mut self, node: RetiredNode) {
+        // Backpressure: wait until readers advance far enough to make room
+        while !self.reserve_garbage_slot() {
+            yield_now();
+        }
 
-        if let Some(threshold) = self.auto_reclaim_threshold {
-            if self.total_garbage_count() > threshold {
-                self.collect();
+        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
+
+        self.garbage.add(node, current_epoch);
+        if let Some(idle) = &self.idle {
+            idle.note_retire();
+        }
+
+        if let Some(threshold) = self.auto_reclaim_threshold
+            && self.total_garbage_count() > threshold
+        {
+            self.auto_collect();
+        }
+    }
+
+    /// Enqueue all `nodes` into the bag of the current epoch, then auto-collect if over the
+    /// threshold. Used by `WriteBatch` so a multi-pointer update reclaims together.
+    ///
+    /// 将所有 `nodes` 加入当前纪元的袋子，然后如果超过阈值则自动回收。
+    /// 供 `WriteBatch` 使用，使多指针更新一起被回收。
+    pub(crate) fn retire_batch(&mut self, nodes: &mut Vec<RetiredNode>) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        while !self.reserve_garbage_slot() {
+            yield_now();
+        }
+
+        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
+        for node in nodes.drain(..) {
+            self.garbage.add(node, current_epoch);
+        }
+        if let Some(idle) = &self.idle {
+            idle.note_retire();
+        }
+
+        if let Some(threshold) = self.auto_reclaim_threshold
+            && self.total_garbage_count() > threshold
+        {
+            self.auto_collect();
+        }
+    }
+
+    /// Collection triggered by the auto-reclaim threshold, bounded by `collect_batch_size`.
+    /// 由自动回收阈值触发的回收，受 `collect_batch_size` 限制。
+    fn auto_collect(&mut self) {
+        self.auto_collections = self.auto_collections.wrapping_add(1);
+        match self.collect_batch_size {
+            Some(limit) => {
+                let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+                let before = self.garbage.len();
+                self.garbage
+                    .collect_bounded(min_active_epoch, new_epoch, limit);
+                self.report_collect(before - self.garbage.len(), new_epoch);
+            }
+            None => self.collect(),
+        }
+    }
+
+    /// Register a reader for the writer thread itself.
+    ///
+    /// For writers that also read their own pointers. Because `collect()` needs `&mut self`,
+    /// no scan can run while this handle is borrowed to register, so the reader is added
+    /// directly to the readers list without going through the registration machinery used for
+    /// other threads. The returned `LocalEpoch` is intended to stay on the writer thread;
+    /// other threads should use `EpochGcDomain::register_reader()`.
+    ///
+    /// 为写入者线程自身注册一个读者。
+    /// 适用于同时读取自己指针的写入者。由于 `collect()` 需要 `&mut self`，在借用此句柄进行注册期间不可能有扫描在运行，
+    /// 因此读者会被直接加入读者列表，而不经过其他线程使用的注册机制。
+    /// 返回的 `LocalEpoch` 应该留在写入者线程上；其他线程应该使用 `EpochGcDomain::register_reader()`。
+    #[inline]
+    pub fn local_reader(&self) -> LocalEpoch {
+        LocalEpoch::new_writer_local(self.shared.clone())
+    }
+
+    /// Commit a value proposed by a reader, if the pointer still holds what the reader saw.
+    ///
+    /// Formalizes the reader-proposes / writer-commits pattern: a reader captures the current
+    /// pointer identity with `EpochPtr::as_ptr()` (or from `load()`) while pinned, computes a
+    /// candidate `new` value, and hands both to the writer. The writer replaces the value only
+    /// if `ptr` still points at `expected_ptr` and `f` accepts the current value; otherwise
+    /// `new` is returned as the error. The replacement is a compare-and-swap from the pointer
+    /// that was checked, so a helper's `EpochPtr::compare_and_set_with_backoff()` landing
+    /// after the check makes this fail instead of being overwritten.
+    ///
+    /// **Happens-before requirements**:
+    /// - The handoff from reader to writer must synchronize (e.g. a channel or a mutex), so that
+    ///   the writer sees `new` fully constructed.
+    /// - `expected_ptr` is only compared, never dereferenced, so it may be stale. But an
+    ///   address can be reused once its value is reclaimed: the reader should stay pinned until
+    ///   the writer has decided, which keeps the observed value alive and rules out ABA. Use
+    ///   `EpochPtr::cas_tagged()` when that is not possible.
+    /// - `f` sees the current value fully constructed without further synchronization, even
+    ///   if a helper published it with `EpochPtr::compare_and_set_with_backoff()`: the writer
+    ///   reads it with an acquire load of the pointer itself, as `EpochPtr::fetch_update()`
+    ///   does.
+    ///
+    /// 如果指针仍持有读者所看到的值，则提交读者提议的值。
+    ///
+    /// 规范化"读者提议 / 写入者提交"模式：读者在被钉住时通过 `EpochPtr::as_ptr()`（或 `load()`）捕获当前指针标识，
+    /// 计算候选值 `new`，并将两者交给写入者。只有当 `ptr` 仍指向 `expected_ptr` 且 `f` 接受当前值时，
+    /// 写入者才会替换值；否则 `new` 作为错误返回。替换是从被检查的指针出发的比较并交换，
+    /// 因此在检查之后落地的辅助线程 `EpochPtr::compare_and_set_with_backoff()` 会使此方法失败，而不是被覆盖。
+    ///
+    /// **happens-before 要求**：
+    /// - 从读者到写入者的交接必须是同步的（例如通道或互斥锁），以便写入者看到完整构造的 `new`。
+    /// - `expected_ptr` 只被比较，从不被解引用，因此它可以是过时的。但地址在其值被回收后可能被复用：
+    ///   读者应该保持被钉住直到写入者做出决定，这会保持所观察到的值存活并排除 ABA。
+    ///   如果无法做到，请使用 `EpochPtr::cas_tagged()`。
+    /// - 即使当前值是辅助线程通过 `EpochPtr::compare_and_set_with_backoff()` 发布的，`f` 也无需额外同步即可看到完整构造的值：
+    ///   写入者与 `EpochPtr::fetch_update()` 一样，通过对指针本身的 acquire 加载读取它。
+    pub fn commit_if<T: Send + 'static, F>(
+        &mut self,
+        ptr: &EpochPtr<T>,
+        expected_ptr: *const T,
+        new: T,
+        f: F,
+    ) -> Result<(), T>
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        let Some((observed, current)) = ptr.writer_current() else {
+            return Err(new);
+        };
+        if !std::ptr::eq(current, expected_ptr) || !f(current) {
+            return Err(new);
+        }
+
+        // A helper may have replaced the value since the check: publish only from `observed`
+        let new_ptr = Box::into_raw(Box::new(new));
+        match ptr.replace_if(observed, new_ptr) {
+            Ok(old_ptr) => {
+                unsafe {
+                    self.retire_or_free(Box::from_raw(old_ptr));
+                }
+                Ok(())
             }
+            Err(_) => Err(*unsafe { Box::from_raw(new_ptr) }),
         }
     }
 
+    /// Make room for one more retired object under the `max_pending_garbage` cap.
+    ///
+    /// If the queue is full, forces a `collect()`. Returns `false` if it is still full
+    /// afterwards. Always returns `true` when no cap is configured.
+    ///
+    /// 在 `max_pending_garbage` 上限下为再退休一个对象腾出空间。
+    /// 如果队列已满，则强制执行一次 `collect()`。如果之后仍然已满，返回 `false`。未配置上限时总是返回 `true`。
+    pub(crate) fn reserve_garbage_slot(&mut self) -> bool {
+        let Some(cap) = self.max_pending_garbage else {
+            return true;
+        };
+        if self.total_garbage_count() < cap {
+            return true;
+        }
+        self.collect();
+        self.total_garbage_count() < cap
+    }
+
+    /// Change the automatic reclamation threshold at runtime.
+    ///
+    /// Same meaning as `EpochGcDomainBuilder::auto_reclaim_threshold`: pass `None` to disable
+    /// automatic reclamation. Takes effect from the next retirement.
+    ///
+    /// 在运行时更改自动回收阈值。
+    /// 与 `EpochGcDomainBuilder::auto_reclaim_threshold` 含义相同：传递 `None` 可禁用自动回收。
+    /// 从下一次退休开始生效。
+    #[inline]
+    pub fn set_auto_reclaim_threshold(&mut self, threshold: impl Into<Option<usize>>) {
+        self.auto_reclaim_threshold = threshold.into();
+    }
+
+    /// Change the dead reader slot cleanup interval at runtime.
+    ///
+    /// Same meaning as `EpochGcDomainBuilder::cleanup_interval`: set to `0` to disable
+    /// periodic cleanup.
+    ///
+    /// 在运行时更改死读者槽的清理间隔。
+    /// 与 `EpochGcDomainBuilder::cleanup_interval` 含义相同：设置为 `0` 可禁用定期清理。
+    #[inline]
+    pub fn set_cleanup_interval(&mut self, interval: usize) {
+        self.cleanup_interval = interval;
+    }
+
+    /// Count the garbage still waiting for reclamation, grouped by type name.
+    ///
+    /// Only available in debug builds. Useful when hunting a memory leak: it shows exactly
+    /// which types are accumulating in the garbage queue.
+    ///
+    /// 按类型名称分组统计仍在等待回收的垃圾。
+    /// 仅在调试构建中可用。用于排查内存泄漏：它能准确显示哪些类型正在垃圾队列中堆积。
+    #[cfg(debug_assertions)]
+    pub fn retired_type_counts(&self) -> HashMap<&'static str, usize> {
+        self.garbage.type_counts()
+    }
+
+    /// Enable quarantine mode to turn reclamation-too-early bugs into loud failures.
+    ///
+    /// Only available in tests and with the `testing` feature. Instead of freeing reclaimed
+    /// objects, their destructors are run and their memory is kept in a quarantine, filled
+    /// with `POISON_BYTE`. A reader that still holds a reference to a reclaimed object then
+    /// reads an obvious poison pattern rather than silently reading freed memory.
+    ///
+    /// Quarantined memory is released by `clear_quarantine()` or when the handle is dropped.
+    ///
+    /// 启用隔离模式，将过早回收的错误变成明显的失败。
+    ///
+    /// 仅在测试中以及启用 `testing` feature 时可用。已回收对象不会被释放，而是运行其析构函数，
+    /// 并将其内存保留在隔离区中，用 `POISON_BYTE` 填充。仍持有已回收对象引用的读者
+    /// 将读到明显的毒化模式，而不是静默地读取已释放的内存。
+    ///
+    /// 被隔离的内存会在 `clear_quarantine()` 或句柄被 drop 时释放。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn assert_safe_reclaim(&mut self) {
+        self.garbage.quarantine.get_or_insert_with(Vec::new);
+    }
+
+    /// Number of reclaimed objects currently held in quarantine.
+    /// 当前保留在隔离区中的已回收对象数量。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn quarantined_count(&self) -> usize {
+        self.garbage.quarantine.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Check that every quarantined allocation is still fully poisoned.
+    ///
+    /// Returns `false` if anything wrote to reclaimed memory after it was quarantined.
+    ///
+    /// 检查每个被隔离的分配是否仍被完全毒化。
+    /// 如果在隔离后有任何东西写入了已回收的内存，则返回 `false`。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn quarantine_intact(&self) -> bool {
+        self.garbage
+            .quarantine
+            .iter()
+            .flatten()
+            .all(Quarantined::is_poisoned)
+    }
+
+    /// Check whether the allocation at `addr` has been reclaimed into quarantine.
+    /// 检查位于 `addr` 的分配是否已被回收到隔离区中。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn is_quarantined(&self, addr: *const ()) -> bool {
+        self.garbage
+            .quarantine
+            .iter()
+            .flatten()
+            .any(|quarantined| quarantined.addr() == addr as usize)
+    }
+
+    /// Free all quarantined memory, keeping quarantine mode enabled.
+    /// 释放所有被隔离的内存，同时保持隔离模式启用。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn clear_quarantine(&mut self) {
+        if let Some(quarantine) = &mut self.garbage.quarantine {
+            quarantine.clear();
+        }
+    }
+
+    /// Check whether any registered reader is currently pinned.
+    /// 检查当前是否有任何已注册的读者被钉住。
+    pub(crate) fn has_active_readers(&self) -> bool {
+        self.shared
+            .lock_readers()
+            .iter()
+            .any(|slot| slot.active_epoch.load(Ordering::Acquire) != INACTIVE_EPOCH)
+    }
+
+    /// Take over all pending garbage from another handle.
+    ///
+    /// Used when structures migrate from one domain to another (e.g. resharding): the
+    /// garbage they left in `other` now has to be reclaimed by this handle. Epoch numbers of
+    /// different domains are unrelated, so every absorbed object is re-retired into this
+    /// handle's **current** epoch and is only reclaimed once this domain's readers have moved
+    /// past it. Cleanups deferred with `PinGuard::defer()` by readers of `other`'s domain and
+    /// not yet picked up by `other` are absorbed too, so they run on this handle's thread;
+    /// closures deferred after this call stay in `other`'s domain and run when it is dropped.
+    ///
+    /// # Safety
+    /// This domain's readers are the only ones that protect the absorbed garbage from now on.
+    /// Every reader that might still hold a reference to it must be registered with this
+    /// domain (for example, both domains share the same readers), or no reader of `other`'s
+    /// domain may be pinned anymore.
+    ///
+    /// 接管另一个句柄的所有待回收垃圾。
+    ///
+    /// 用于结构从一个域迁移到另一个域时（例如重新分片）：它们留在 `other` 中的垃圾
+    /// 现在需要由此句柄回收。不同域的纪元编号互不相关，因此每个被接管的对象都会被重新退休到
+    /// 此句柄的**当前**纪元，并且只有在此域的读者都越过该纪元后才会被回收。
+    /// `other` 所在域的读者通过 `PinGuard::defer()` 延迟、且尚未被 `other` 取走的清理闭包也会被一并接管，
+    /// 因此它们会在此句柄的线程上运行；此调用之后延迟的闭包留在 `other` 的域中，并在该域被 drop 时运行。
+    ///
+    /// # Safety
+    /// 从此以后只有此域的读者保护被接管的垃圾。所有可能仍持有其引用的读者都必须注册在此域中
+    /// （例如两个域共享相同的读者），否则 `other` 所在域中不得再有被钉住的读者。
+    pub unsafe fn absorb(&mut self, mut other: GcHandle) {
+        other.retire_deferred();
+        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
+        self.garbage.absorb(&mut other.garbage, current_epoch);
+    }
+
     /// Perform a garbage collection cycle.
     ///
     /// This method:
@@ -271,38 +1404,622 @@ impl GcHandle {
     /// 可以定期调用或在重大更新后调用。
     /// 即使没有垃圾要回收也可以安全调用。
     pub fn collect(&mut self) {
-        let new_epoch = self.shared.global_epoch.fetch_add(1, Ordering::AcqRel) + 1;
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+
+        let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+        self.reclaim(min_active_epoch, new_epoch);
+
+        #[cfg(feature = "metrics")]
+        {
+            self.collect_latency[latency_bucket(started.elapsed())] += 1;
+        }
+    }
+
+    /// Run a collection cycle that hands the reclaimable objects back instead of dropping them.
+    ///
+    /// Advances the epoch and scans the readers like `collect()`, then yields every object that
+    /// is safe to reclaim as a `Box<dyn Any>`, so the caller can downcast it and reuse the
+    /// value or its allocation. Objects without a known sized type, such as those retired with
+    /// `retire_raw()` or stored as slices, are dropped normally as the iterator reaches them,
+    /// as are any left when the iterator is dropped. Yielded objects the caller does not
+    /// recognize should simply be dropped, which reclaims them exactly as `collect()` would.
+    ///
+    /// 运行一个回收周期，将可回收的对象交还，而不是 drop 它们。
+    /// 与 `collect()` 一样推进纪元并扫描读者，然后将每个可以安全回收的对象作为 `Box<dyn Any>` 产出，
+    /// 以便调用者向下转型并复用该值或其分配。没有已知有大小类型的对象（例如通过 `retire_raw()` 退休或以切片形式存储的对象）
+    /// 会在迭代器到达它们时被正常 drop，迭代器被 drop 时剩余的对象也是如此。
+    /// 调用者不认识的产出对象应直接 drop，这与 `collect()` 对它们的回收完全相同。
+    pub fn drain_safe(&mut self) -> impl Iterator<Item = Box<dyn Any>> + use<> {
+        let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+        let drained = self.garbage.drain(min_active_epoch, new_epoch);
+        self.report_collect(drained.len(), new_epoch);
+        drained.into_iter().filter_map(|node| node.into_any().ok())
+    }
+
+    /// Collect if the idle helper asked for it, returning whether a collection ran.
+    ///
+    /// With `auto_collect_on_idle` configured, the helper thread raises a request and calls
+    /// the configured callback once the writer has been idle while holding garbage. The writer
+    /// thread serves the request by calling this method, typically when the callback has woken
+    /// its event loop. Cheap when no request is pending, so it can also simply be polled.
+    /// Always returns `false` without `auto_collect_on_idle`.
+    ///
+    /// 如果空闲辅助线程请求了回收则进行回收，返回是否执行了回收。
+    /// 配置了 `auto_collect_on_idle` 时，一旦写入者在持有垃圾的情况下空闲，辅助线程就会发出请求并调用所配置的回调。
+    /// 写入者线程通过调用此方法来处理请求，通常是在回调唤醒了其事件循环之后。没有待处理的请求时开销很小，因此也可以直接轮询。
+    /// 未配置 `auto_collect_on_idle` 时总是返回 `false`。
+    pub fn collect_if_requested(&mut self) -> bool {
+        if !self.idle.as_ref().is_some_and(IdleCollector::take_request) {
+            return false;
+        }
+        self.collect();
+        if let Some(idle) = &self.idle {
+            idle.note_collected(self.garbage.len() > 0);
+        }
+        true
+    }
+
+    /// Get how many `collect()` calls fell into each latency bucket.
+    ///
+    /// Only available with the `metrics` feature. See `COLLECT_LATENCY_BUCKETS` for the bucket
+    /// boundaries. Collections triggered automatically by stores are included, which makes
+    /// this the place to look for tail latency spikes when tuning the auto-reclaim threshold.
+    ///
+    /// 获取落入每个延迟桶的 `collect()` 调用次数。
+    /// 仅在启用 `metrics` feature 时可用。桶的边界参见 `COLLECT_LATENCY_BUCKETS`。
+    /// 由 store 自动触发的回收也包含在内，因此在调整自动回收阈值时可以在这里查找尾部延迟尖峰。
+    #[cfg(feature = "metrics")]
+    pub fn collect_latency_histogram(&self) -> [u64; COLLECT_LATENCY_BUCKETS] {
+        self.collect_latency
+    }
+
+    /// Perform a garbage collection cycle and report what it did.
+    ///
+    /// Same as `collect()`, but returns how many objects were reclaimed, how many dead reader
+    /// slots were cleaned up (this only happens every `cleanup_interval` cycles), the new
+    /// epoch and the range of epochs that became reclaimable. Useful to confirm that leaked
+    /// reader slots are being cleaned up.
+    ///
+    /// 执行一个垃圾回收周期并报告其结果。
+    /// 与 `collect()` 相同，但返回被回收的对象数量、被清理的死读者槽数量（仅每 `cleanup_interval` 个周期发生一次）、
+    /// 新的纪元以及变为可回收的纪元范围。可用于确认泄漏的读者槽正在被清理。
+    pub fn collect_detailed(&mut self) -> CollectReport {
+        let (min_active_epoch, new_epoch, dead_readers_removed) = self.advance_and_scan();
+        let reclaimed = self.reclaim(min_active_epoch, new_epoch);
+
+        CollectReport {
+            reclaimed,
+            dead_readers_removed,
+            epoch: new_epoch,
+            // Garbage is tagged with the epoch it was retired in, which is always below
+            // `new_epoch`, so this also covers the case without pinned readers
+            safe_epoch: min_active_epoch.checked_sub(1),
+        }
+    }
+
+    /// Tear down the writer side of the domain.
+    ///
+    /// Collects repeatedly until all garbage is reclaimed or `deadline` passes, then consumes
+    /// the handle. Readers that are still pinned at that point indicate a leaked `PinGuard`:
+    /// they are counted in the report, and any garbage they may still observe is leaked rather
+    /// than freed, so shutting down never causes a use-after-free. This also holds with
+    /// `panic_on_reader_leak` enabled, which only applies to dropping the handle directly.
+    ///
+    /// 拆除域的写入者侧。
+    /// 反复回收直到所有垃圾都被回收或超过 `deadline`，然后消耗此句柄。此时仍被钉住的读者表明存在泄漏的 `PinGuard`：
+    /// 它们会被计入报告，而它们可能仍能观察到的垃圾会被泄漏而不是被释放，因此关闭永远不会导致释放后使用。
+    /// 启用 `panic_on_reader_leak` 时也是如此，该选项只适用于直接 drop 句柄的情况。
+    pub fn shutdown(mut self, deadline: Instant) -> ShutdownReport {
+        let mut reclaimed = 0;
+        loop {
+            reclaimed += self.collect_detailed().reclaimed;
+            if self.garbage.len() == 0 || Instant::now() >= deadline {
+                break;
+            }
+            yield_now();
+        }
+
+        let pinned_readers = self
+            .shared
+            .lock_readers()
+            .iter()
+            .filter(|slot| slot.active_epoch.load(Ordering::Acquire) != INACTIVE_EPOCH)
+            .count();
+
+        let leaked = self.garbage.len();
+        if leaked > 0 {
+            std::mem::forget(std::mem::replace(&mut self.garbage, GarbageSet::new()));
+        }
+        // The pinned readers are reported instead, so the drop of `self` must not panic on them
+        #[cfg(debug_assertions)]
+        {
+            self.panic_on_reader_leak = false;
+        }
+
+        ShutdownReport {
+            reclaimed,
+            leaked,
+            pinned_readers,
+        }
+    }
+
+    /// Perform a garbage collection cycle restricted to one generation.
+    ///
+    /// When `EpochGcDomainBuilder::promote_after` is configured, garbage that is still
+    /// unreclaimable after that many collections is promoted to the old generation.
+    /// - `Generation::Young` advances the epoch and reclaims only young garbage, then promotes
+    ///   survivors. Call it frequently: it never rescans garbage held back by a slow reader.
+    /// - `Generation::Old` is a full collection, equivalent to `collect()`. Call it less often
+    ///   to eventually reclaim promoted garbage.
+    ///
+    /// 执行限定于一个代的垃圾回收周期。
+    ///
+    /// 配置了 `EpochGcDomainBuilder::promote_after` 时，经过该次数回收后仍不可回收的垃圾会被提升到老年代。
+    /// - `Generation::Young` 推进纪元并只回收年轻代垃圾，然后提升幸存者。应频繁调用：它不会重复扫描被慢读者阻挡的垃圾。
+    /// - `Generation::Old` 是一次完整回收，等价于 `collect()`。应较少调用，以最终回收被提升的垃圾。
+    pub fn collect_generation(&mut self, generation: Generation) {
+        match generation {
+            Generation::Young => {
+                let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+                let before = self.garbage.len();
+                self.garbage
+                    .collect_young(min_active_epoch, new_epoch, self.promote_after);
+                self.report_collect(before - self.garbage.len(), new_epoch);
+            }
+            Generation::Old => self.collect(),
+        }
+    }
+
+    /// Reclaim safe garbage until `deadline` passes.
+    ///
+    /// Like `collect()`, this advances the epoch and scans the readers, but reclamation stops
+    /// once the deadline is reached. Each call resumes where the previous one stopped, so
+    /// repeated deadline-bounded calls eventually reclaim every safe bag instead of favouring
+    /// the front of the queue. Returns `true` if all currently safe garbage was reclaimed.
+    ///
+    /// 回收安全的垃圾，直到超过 `deadline`。
+    ///
+    /// 与 `collect()` 一样，此方法会推进纪元并扫描读者，但到达截止时间后停止回收。
+    /// 每次调用都会从上一次停止的位置继续，因此重复的有截止时间的调用最终会回收所有安全的袋子，
+    /// 而不会偏向队首。如果当前所有安全垃圾都已回收，返回 `true`。
+    pub fn collect_until(&mut self, deadline: Instant) -> bool {
+        let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+        let before = self.garbage.len();
+        let done = self
+            .garbage
+            .collect_until(min_active_epoch, new_epoch, deadline);
+        self.report_collect(before - self.garbage.len(), new_epoch);
+        done
+    }
+
+    /// Reclaim safe garbage until `cancel` is set, returning how many objects were reclaimed.
+    ///
+    /// Like `collect()`, this advances the epoch and scans the readers, but the flag is checked
+    /// every few dozen reclaimed objects and reclamation stops as soon as it is seen set, so a
+    /// large backlog does not hold up a shutdown. Garbage left behind stays queued, and the
+    /// next call resumes where this one stopped, as with `collect_until()`. If `cancel` is
+    /// already set, nothing is reclaimed.
+    ///
+    /// 回收安全的垃圾，直到 `cancel` 被设置，返回被回收的对象数量。
+    ///
+    /// 与 `collect()` 一样，此方法会推进纪元并扫描读者，但每回收几十个对象就检查一次标志，一旦发现其被设置就停止回收，
+    /// 因此大量积压的垃圾不会拖慢关闭过程。留下的垃圾仍在队列中，下一次调用会像 `collect_until()` 一样从此次停止的位置继续。
+    /// 如果 `cancel` 已被设置，则不回收任何内容。
+    pub fn collect_cancellable(&mut self, cancel: &std::sync::atomic::AtomicBool) -> usize {
+        let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+        let before = self.garbage.len();
+        while !cancel.load(Ordering::Relaxed) {
+            if self
+                .garbage
+                .collect_bounded(min_active_epoch, new_epoch, CANCEL_CHECK_INTERVAL)
+            {
+                break;
+            }
+        }
+        let reclaimed = before - self.garbage.len();
+        self.report_collect(reclaimed, new_epoch);
+        reclaimed
+    }
+
+    /// Perform a collection cycle with a caller-supplied minimum active epoch.
+    ///
+    /// Advances the epoch like `collect()`, but skips the reader scan and reclaims as if
+    /// `min_active_epoch` were the oldest epoch any reader is pinned at: every bag retired
+    /// at an epoch older than `min_active_epoch` is freed, or every bag if it equals the new epoch.
+    /// Intended for tests of reclamation boundaries and for coordinating with an external
+    /// source of epochs.
+    ///
+    /// # Safety
+    /// **This bypasses the only thing that keeps readers safe.** `min_active_epoch` must not
+    /// exceed the minimum that `collect()` would compute, i.e. it must be no newer than the
+    /// epoch of every reader that is pinned now or pins before this call returns. Passing a
+    /// larger value frees objects that readers can still access, which is a use-after-free.
+    ///
+    /// # Panics
+    /// Panics if `min_active_epoch` is newer than the epoch this cycle advances to.
+    ///
+    /// 使用调用者提供的最小活跃纪元执行一个回收周期。
+    ///
+    /// 与 `collect()` 一样推进纪元，但跳过读者扫描，并按照 `min_active_epoch` 是所有读者被钉住的最旧纪元来回收：
+    /// 在早于 `min_active_epoch` 的纪元中退休的每个袋子都会被释放，如果它等于新纪元则释放所有袋子。
+    /// 适用于回收边界的测试，以及与外部纪元来源进行协调。
+    ///
+    /// # Safety
+    /// **这会绕过保证读者安全的唯一机制。** `min_active_epoch` 不得超过 `collect()` 会计算出的最小值，
+    /// 即它不得晚于当前被钉住或在此调用返回之前钉住的每个读者的纪元。传入更大的值会释放读者仍可访问的对象，导致释放后使用。
+    ///
+    /// # Panics
+    /// 如果 `min_active_epoch` 晚于此周期推进到的纪元，则 panic。
+    pub unsafe fn collect_with_min(&mut self, min_active_epoch: usize) {
+        self.retire_deferred();
+        let new_epoch = self.advance_epoch();
+        assert!(
+            min_active_epoch <= new_epoch,
+            "min_active_epoch {min_active_epoch} is newer than the new epoch {new_epoch}"
+        );
+
+        self.reclaim(min_active_epoch, new_epoch);
+    }
+
+    /// Reclaim every bag that is safe at `min_active_epoch`, report the cycle to the
+    /// `GcMetrics` hook, and return how many objects were reclaimed.
+    ///
+    /// 回收在 `min_active_epoch` 下安全的每个袋子，将该周期报告给 `GcMetrics` 钩子，并返回被回收的对象数量。
+    fn reclaim(&mut self, min_active_epoch: usize, new_epoch: usize) -> usize {
+        let before = self.garbage.len();
+        self.garbage.collect(min_active_epoch, new_epoch);
+        let reclaimed = before - self.garbage.len();
+        self.report_collect(reclaimed, new_epoch);
+        reclaimed
+    }
+
+    /// Report a finished collection cycle to the `GcMetrics` hook, if one was injected.
+    /// 将一个已完成的回收周期报告给 `GcMetrics` 钩子（如果注入了的话）。
+    #[inline]
+    fn report_collect(&self, reclaimed: usize, epoch: usize) {
+        if let Some(metrics) = &self.shared.metrics {
+            metrics.on_collect(reclaimed, epoch);
+        }
+    }
+
+    /// Move the closures deferred by readers into the current epoch's bag.
+    ///
+    /// A closure is deferred while its reader is pinned at an epoch no newer than the current
+    /// one, so retiring it at the current epoch keeps it alive for at least as long as any
+    /// reader that could still observe what it cleans up.
+    ///
+    /// 将读者延迟的闭包移入当前纪元的袋子。
+    /// 闭包是在其读者被钉住于不晚于当前纪元的纪元时被延迟的，因此在当前纪元退休它，
+    /// 可以保证它至少存活到任何仍可能观察到它所清理内容的读者结束为止。
+    fn retire_deferred(&mut self) {
+        let deferred = std::mem::take(&mut *self.shared.deferred.lock());
+        if deferred.is_empty() {
+            return;
+        }
+
+        let current_epoch = self.shared.global_epoch.load(Ordering::Relaxed);
+        for f in deferred {
+            self.garbage
+                .add(RetiredObject::new(Box::new(f)), current_epoch);
+        }
+    }
+
+    /// Collect until no reader can still hold a pointer loaded before this call.
+    ///
+    /// Blocks for as long as any reader stays pinned at the current epoch or earlier.
+    ///
+    /// 持续回收，直到没有读者仍可能持有在此调用之前加载的指针。
+    /// 只要有读者保持被钉住于当前或更早的纪元，就会一直阻塞。
+    pub(crate) fn quiesce(&mut self) {
+        // Only the writer's own advance is ordered after the swaps made before this call. An
+        // epoch bumped by `EpochGcDomain::advance_epoch_only()` in between may not be, so a
+        // reader pinned at it can still see a swapped-out value; wait for the first epoch
+        // this call advanced to instead of the one loaded up front.
+        let mut target = None;
+        loop {
+            let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+            self.reclaim(min_active_epoch, new_epoch);
+            if min_active_epoch >= *target.get_or_insert(new_epoch) {
+                break;
+            }
+            yield_now();
+        }
+    }
+
+    /// Reset the domain to its freshly created state, so it can be reused without reallocating.
+    ///
+    /// On success, the global and minimum active epochs go back to `0`, the slots of dropped
+    /// readers are removed, and the handle's collection counter restarts, so the domain and
+    /// every clone of its `EpochGcDomain` behave exactly like a new one. Returns `false` and
+    /// changes nothing unless all of these preconditions hold:
+    /// - no `LocalEpoch` of this domain is alive, including ones from `local_reader()`;
+    /// - no garbage is pending, including cleanups deferred by readers (call `collect()` first);
+    /// - no reader registers while the reset is in progress.
+    ///
+    /// Rewinding the epoch is only sound because nothing can remember an old epoch: there is
+    /// no reader to be pinned at one and no garbage tagged with one.
+    ///
+    /// 将域重置为刚创建时的状态，使其无需重新分配即可复用。
+    ///
+    /// 成功时，全局纪元和最小活跃纪元回到 `0`，已 drop 读者的槽被移除，句柄的回收计数器重新开始，
+    /// 因此该域及其 `EpochGcDomain` 的所有克隆的行为与新创建的完全相同。除非以下前提条件全部成立，否则返回 `false` 且不做任何改变：
+    /// - 此域没有任何存活的 `LocalEpoch`，包括来自 `local_reader()` 的读者；
+    /// - 没有待回收的垃圾，包括读者延迟的清理（请先调用 `collect()`）；
+    /// - 重置期间没有读者进行注册。
+    ///
+    /// 回退纪元之所以是安全的，只是因为没有任何东西能记住旧纪元：没有读者被钉住于某个旧纪元，也没有垃圾带有旧纪元的标记。
+    pub fn reset(&mut self) -> bool {
+        self.retire_deferred();
+        if self.garbage.len() > 0 {
+            return false;
+        }
+
+        let mut readers = self.shared.lock_readers();
+        if readers.iter().any(|slot| Arc::strong_count(slot) > 1) {
+            return false;
+        }
+        Self::remove_dead_slots(&self.shared, &mut readers);
+
+        let epoch = self.shared.global_epoch.load(Ordering::Acquire);
+        let min_active_epoch = self.shared.min_active_epoch.load(Ordering::Acquire);
+        self.shared.global_epoch.store(0, Ordering::SeqCst);
+        self.shared.min_active_epoch.store(0, Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+
+        // With the lock-free backend, a reader may have registered without the lock and
+        // pinned at the old epoch. Restore it, so epochs never go backwards for that reader.
+        // The pending stack is not enough to detect it, since the reader pushes its slot only
+        // after its fence and may already have loaded the old epoch by then. The count is
+        // bumped before that fence instead: either this load sees it, or the reader's fence
+        // comes after the one above and its first pin loads the rewound epoch.
+        if self.shared.reader_count.load(Ordering::SeqCst) != 0 {
+            self.shared.global_epoch.store(epoch, Ordering::SeqCst);
+            self.shared
+                .min_active_epoch
+                .store(min_active_epoch, Ordering::SeqCst);
+            return false;
+        }
+
+        self.collection_counter = 0;
+        self.min_holder = None;
+        self.last_epoch = 0;
+        true
+    }
+
+    /// Wait until every reader of `group` has unpinned at least once since this call.
+    ///
+    /// Like `quiesce()`, this repeatedly collects, but it only looks at the slots of readers
+    /// registered with `EpochGcDomain::register_reader_in_group()` for this group: a member
+    /// that is unpinned, or pinned again after the call started, no longer holds it up, while
+    /// readers outside the group are ignored entirely. Blocks for as long as a member stays
+    /// pinned.
+    ///
+    /// 等待直到 `group` 的每个读者自此调用以来都至少解除钉住过一次。
+    /// 与 `quiesce()` 一样，此方法会反复回收，但只查看通过 `EpochGcDomain::register_reader_in_group()` 注册到此群组的读者的槽：
+    /// 未被钉住或在调用开始后重新钉住的成员不再阻挡它，而群组之外的读者则被完全忽略。只要有成员保持被钉住就会一直阻塞。
+    pub fn quiesce_group(&mut self, group: &ReaderGroup) {
+        // As in `quiesce()`, only readers pinned at or after the writer's own first advance
+        // are ordered after the swaps made before this call.
+        let mut target = None;
+        loop {
+            let (min_active_epoch, new_epoch, _) = self.advance_and_scan();
+            self.reclaim(min_active_epoch, new_epoch);
+            let target = *target.get_or_insert(new_epoch);
+
+            let waiting = self.shared.lock_readers().iter().any(|slot| {
+                let active = slot.active_epoch.load(Ordering::Acquire);
+                slot.group.load(Ordering::Relaxed) == group.id
+                    && active != INACTIVE_EPOCH
+                    && active < target
+            });
+            if !waiting {
+                break;
+            }
+            yield_now();
+        }
+    }
+
+    /// Remove the slots of dropped readers now, regardless of `cleanup_interval`.
+    ///
+    /// Useful right after a known wave of reader threads has exited. Returns the number of
+    /// removed slots. With slot recycling enabled, the slots are kept for reuse as usual.
+    ///
+    /// 立即移除已 drop 读者的槽，不受 `cleanup_interval` 的影响。
+    /// 适用于已知的一批读者线程退出之后。返回被移除的槽数量。启用槽复用时，这些槽照常被保留以供复用。
+    pub fn cleanup_dead_readers(&mut self) -> usize {
+        let mut readers = self.shared.lock_readers();
+        Self::remove_dead_slots(&self.shared, &mut readers)
+    }
+
+    /// Remove slots whose `LocalEpoch` was dropped, recycling them if enabled.
+    /// Returns the number of removed slots.
+    ///
+    /// 移除其 `LocalEpoch` 已被 drop 的槽，如果启用则复用它们。返回被移除的槽数量。
+    fn remove_dead_slots(shared: &SharedState, readers: &mut ReaderList) -> usize {
+        let before = readers.len();
+        // Keep only slots that have external references (strong_count > 1), moving dead slots
+        // to the free list instead of dropping them if recycling is enabled
+        let mut recycled = shared
+            .recycled_slots
+            .as_ref()
+            .map(|recycled| recycled.lock());
+        readers.remove_if(
+            |arc_slot| Arc::strong_count(arc_slot) == 1,
+            |arc_slot| {
+                if let Some(recycled) = &mut recycled {
+                    recycled.push(arc_slot);
+                }
+            },
+        );
+        let removed = before - readers.len();
+        shared.reader_count.fetch_sub(removed, Ordering::Release);
+        removed
+    }
+
+    /// Advance the global epoch by one and return the new epoch.
+    ///
+    /// If the epoch was also advanced with `EpochGcDomain::advance_epoch_only()` since the
+    /// writer's previous advance, the garbage retired in between is retagged to the epoch just
+    /// before the new one. Only this advance is ordered after those retirements, so only
+    /// readers pinned at the new epoch are sure to no longer see the retired objects.
+    ///
+    /// 将全局纪元推进一并返回新纪元。
+    /// 如果自写入者上次推进以来纪元还通过 `EpochGcDomain::advance_epoch_only()` 被推进过，
+    /// 则期间退休的垃圾会被重新标记为新纪元之前的那个纪元。只有这次推进排在这些退休之后，
+    /// 因此只有钉住于新纪元的读者才确定不会再看到已退休的对象。
+    fn advance_epoch(&mut self) -> usize {
+        let epoch = self.shared.global_epoch.fetch_add(1, Ordering::AcqRel);
+        if epoch != self.last_epoch {
+            self.garbage.retag_since(self.last_epoch, epoch);
+        }
+        self.last_epoch = epoch + 1;
+        self.last_epoch
+    }
+
+    /// Advance the global epoch, scan the readers and publish the new minimum active epoch.
+    ///
+    /// Returns `(min_active_epoch, new_epoch, dead_readers_removed)`.
+    ///
+    /// 推进全局纪元，扫描读者并发布新的最小活跃纪元。
+    /// 返回 `(min_active_epoch, new_epoch, dead_readers_removed)`。
+    fn advance_and_scan(&mut self) -> (usize, usize, usize) {
+        self.retire_deferred();
+
+        let new_epoch = self.advance_epoch();
+        // Order the pointer swaps and the advance before the reader scan; pairs with the
+        // fence in `LocalEpoch::try_enter`.
+        fence(Ordering::SeqCst);
 
         let mut min_active_epoch = new_epoch;
         self.collection_counter += 1;
 
-        let should_cleanup =
-            self.cleanup_interval > 0 && self.collection_counter % self.cleanup_interval == 0;
+        // Fast path: with no registered reader there is nothing to scan or clean up. A reader
+        // registering concurrently is safe for the same reason as one missed by a chunked
+        // scan: its pin fence pairs with the fence above.
+        if self.shared.reader_count.load(Ordering::Acquire) == 0 {
+            self.min_holder = None;
+            self.shared
+                .min_active_epoch
+                .store(min_active_epoch, Ordering::Release);
+            return (min_active_epoch, new_epoch, 0);
+        }
 
-        let mut shared_readers = self.shared.readers.lock();
+        let should_cleanup = self.cleanup_interval > 0
+            && self
+                .collection_counter
+                .is_multiple_of(self.cleanup_interval);
+
+        // Incremental path: if the slot that held the minimum last time still does, reuse the
+        // previous minimum without rescanning the stable readers. Any reader at that epoch will
+        // do, so it does not matter whether the index still holds the same slot. This does not
+        // mean every pinned reader is at or above that minimum: a reader a scan missed may have
+        // published a stale epoch below it, since the minimum it checked in `pin()` may be
+        // stale as well. The shortcut is safe only because it never raises the minimum above
+        // its previous value, so it makes nothing reclaimable that the previous scan did not.
+        // Cleanups and pin timeout checks still need a full scan.
+        #[cfg(debug_assertions)]
+        let needs_full_scan = should_cleanup || self.shared.pin_timeout.is_some();
+        #[cfg(not(debug_assertions))]
+        let needs_full_scan = should_cleanup;
+        if !needs_full_scan && let Some(index) = self.min_holder {
+            let previous_min = self.shared.min_active_epoch.load(Ordering::Acquire);
+            let still_held = self
+                .shared
+                .lock_readers()
+                .get(index)
+                .is_some_and(|slot| slot.active_epoch.load(Ordering::Acquire) == previous_min);
+            if still_held {
+                return (previous_min, new_epoch, 0);
+            }
+        }
 
         let mut dead_count = 0;
+        let mut removed = 0;
+
+        #[cfg(debug_assertions)]
+        let mut longest_pin = std::time::Duration::ZERO;
+        #[cfg(debug_assertions)]
+        let now = self.shared.created_at.elapsed().as_nanos() as u64;
 
-        for arc_slot in shared_readers.iter() {
-            let epoch = arc_slot.active_epoch.load(Ordering::Acquire);
-            if epoch != INACTIVE_EPOCH {
-                min_active_epoch = min_active_epoch.min(epoch);
-            } else if should_cleanup && Arc::strong_count(arc_slot) == 1 {
-                // Only this Vec holds a reference, the LocalEpoch was dropped
-                dead_count += 1;
+        // Scan in chunks, releasing the lock in between so that registrations are not starved.
+        // Registrations only append, so indices stay valid across chunks. A reader that pins
+        // meanwhile may be missed, and may publish an epoch loaded before the advance, but its
+        // fence in `LocalEpoch::try_enter` pairs with the one above: a reader the scan missed
+        // sees every pointer swap made before that fence, and only garbage from those swaps
+        // can be reclaimed with the minimum computed here. An eager reader removing its own
+        // slot from the `Vec` storage may move an unscanned slot to a scanned index, so the scan
+        // restarts whenever that happened in between chunks. The slab storage never moves
+        // slots, and a vacant index is only refilled by a new registration.
+        let chunk = if self.scan_chunk == 0 {
+            usize::MAX
+        } else {
+            self.scan_chunk
+        };
+        let mut start = 0usize;
+        let mut min_holder = None;
+        let mut removals = self.shared.slot_removals.load(Ordering::Acquire);
+
+        loop {
+            let mut shared_readers = self.shared.lock_readers();
+            let current_removals = self.shared.slot_removals.load(Ordering::Acquire);
+            if current_removals != removals {
+                removals = current_removals;
+                start = 0;
+                min_holder = None;
+            }
+            let end = start.saturating_add(chunk).min(shared_readers.span());
+
+            for (index, arc_slot) in shared_readers.range(start, end) {
+                let epoch = arc_slot.active_epoch.load(Ordering::Acquire);
+                if epoch != INACTIVE_EPOCH {
+                    if epoch < min_active_epoch {
+                        min_active_epoch = epoch;
+                        min_holder = Some(index);
+                    }
+
+                    #[cfg(debug_assertions)]
+                    if self.shared.pin_timeout.is_some() {
+                        let started = arc_slot.pin_started_at.load(Ordering::Relaxed);
+                        let pinned_for =
+                            std::time::Duration::from_nanos(now.saturating_sub(started));
+                        longest_pin = longest_pin.max(pinned_for);
+                    }
+                } else if should_cleanup && Arc::strong_count(arc_slot) == 1 {
+                    // Only the readers list holds a reference, the LocalEpoch was dropped
+                    dead_count += 1;
+                }
+            }
+
+            if end < shared_readers.span() {
+                drop(shared_readers);
+                start = end;
+                continue;
             }
-        }
 
-        if should_cleanup && dead_count > 0 {
-            // Keep only slots that have external references (strong_count > 1)
-            shared_readers.retain(|arc_slot| Arc::strong_count(arc_slot) > 1);
+            if should_cleanup && dead_count > 0 {
+                removed = Self::remove_dead_slots(&self.shared, &mut shared_readers);
+                // The cleanup may have moved the holder to another index
+                min_holder = None;
+            }
+            break;
         }
 
-        drop(shared_readers);
+        #[cfg(debug_assertions)]
+        if let Some(timeout) = self.shared.pin_timeout {
+            assert!(
+                longest_pin <= timeout,
+                "a reader{} has been pinned for {longest_pin:?}, exceeding the pin timeout of \
+                 {timeout:?}. A PinGuard is probably being held longer than intended.",
+                self.shared.describe()
+            );
+        }
 
         self.shared
             .min_active_epoch
             .store(min_active_epoch, Ordering::Release);
-        self.garbage.collect(min_active_epoch, new_epoch);
+        self.min_holder = min_holder;
+
+        (min_active_epoch, new_epoch, removed)
     }
 }
@@ -0,0 +1,130 @@
+use std::boxed::Box;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Callback run on the helper thread to ask the writer to collect.
+/// 在辅助线程上运行、请求写入者进行回收的回调。
+pub(crate) type IdleNotify = Box<dyn Fn() + Send + 'static>;
+
+/// State shared between the writer and the idle helper thread.
+///
+/// The helper only ever reads and writes these flags; it never touches the garbage itself.
+///
+/// 写入者与空闲辅助线程之间共享的状态。
+/// 辅助线程只会读写这些标志，从不触及垃圾本身。
+#[derive(Debug, Default)]
+struct IdleState {
+    /// Incremented by the writer on every retirement.
+    /// 写入者每次退休时递增。
+    activity: AtomicUsize,
+    /// Whether the writer may still hold garbage.
+    /// 写入者是否可能仍持有垃圾。
+    pending: AtomicBool,
+    /// Whether a collection was requested and not yet served by the writer.
+    /// 是否已请求回收且写入者尚未处理。
+    requested: AtomicBool,
+    /// Set when the `GcHandle` is dropped, to stop the helper thread.
+    /// 在 `GcHandle` 被 drop 时设置，用于停止辅助线程。
+    stop: AtomicBool,
+}
+
+/// The writer's side of `auto_collect_on_idle`: owns the helper thread and stops it on drop.
+///
+/// `auto_collect_on_idle` 的写入者侧：持有辅助线程并在 drop 时停止它。
+#[derive(Debug)]
+pub(crate) struct IdleCollector {
+    state: Arc<IdleState>,
+    helper: Option<JoinHandle<()>>,
+}
+
+impl IdleCollector {
+    /// Spawn the helper thread, which calls `notify` whenever the writer has been idle for
+    /// `idle` while holding garbage.
+    ///
+    /// 启动辅助线程，每当写入者在持有垃圾的情况下空闲了 `idle` 时间，它就会调用 `notify`。
+    pub(crate) fn spawn(idle: Duration, notify: IdleNotify) -> Self {
+        let state = Arc::new(IdleState::default());
+        let helper = {
+            let state = Arc::clone(&state);
+            std::thread::Builder::new()
+                .name("swmr-epoch-idle".into())
+                .spawn(move || run_helper(&state, idle, notify))
+                .expect("failed to spawn the idle collection helper thread")
+        };
+
+        Self {
+            state,
+            helper: Some(helper),
+        }
+    }
+
+    /// Record that the writer retired garbage.
+    /// 记录写入者退休了垃圾。
+    #[inline]
+    pub(crate) fn note_retire(&self) {
+        self.state.activity.fetch_add(1, Ordering::Relaxed);
+        self.state.pending.store(true, Ordering::Relaxed);
+    }
+
+    /// Take the pending request, if any.
+    /// 取出待处理的请求（如果有）。
+    #[inline]
+    pub(crate) fn take_request(&self) -> bool {
+        self.state.requested.swap(false, Ordering::AcqRel)
+    }
+
+    /// Record whether garbage is left after serving a request.
+    /// 记录处理请求后是否还剩余垃圾。
+    #[inline]
+    pub(crate) fn note_collected(&self, garbage_left: bool) {
+        self.state.pending.store(garbage_left, Ordering::Relaxed);
+    }
+}
+
+impl Drop for IdleCollector {
+    fn drop(&mut self) {
+        self.state.stop.store(true, Ordering::Release);
+        if let Some(helper) = self.helper.take() {
+            helper.thread().unpark();
+            let _ = helper.join();
+        }
+    }
+}
+
+/// Body of the helper thread.
+///
+/// Every `idle` interval it checks whether the writer retired anything since the previous
+/// check. If not, and garbage may be pending, it raises a request and calls `notify`, once
+/// per request: it does not notify again until the writer has served the previous one.
+///
+/// 辅助线程的主体。
+/// 每隔 `idle` 时间检查写入者自上次检查以来是否退休过任何东西。如果没有，且可能有待回收的垃圾，
+/// 它会发出请求并调用 `notify`，每个请求只调用一次：在写入者处理之前的请求之前不会再次通知。
+fn run_helper(state: &IdleState, idle: Duration, notify: IdleNotify) {
+    let mut last_activity = state.activity.load(Ordering::Relaxed);
+    loop {
+        // `park_timeout` may wake up spuriously, so wait for the full interval
+        let deadline = Instant::now() + idle;
+        loop {
+            if state.stop.load(Ordering::Acquire) {
+                return;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            std::thread::park_timeout(deadline - now);
+        }
+
+        let activity = state.activity.load(Ordering::Relaxed);
+        if activity == last_activity
+            && state.pending.load(Ordering::Relaxed)
+            && !state.requested.swap(true, Ordering::AcqRel)
+        {
+            notify();
+        }
+        last_activity = activity;
+    }
+}
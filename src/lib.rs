@@ -44,17 +44,54 @@
 //! gc.collect();  // Reclaim garbage from old epochs
 //! ```
 
+pub(crate) mod arc_swap;
+pub(crate) mod batch;
+pub(crate) mod cell;
+pub(crate) mod collect_guard;
 pub(crate) mod domain;
+pub(crate) mod epoch_cell;
+pub(crate) mod ext;
 pub(crate) mod garbage;
+pub(crate) mod idle;
+pub(crate) mod list;
+pub(crate) mod metrics;
+#[cfg(any(test, feature = "model"))]
+pub mod model;
+#[cfg(feature = "numa")]
+pub(crate) mod numa;
+pub mod prelude;
 pub(crate) mod ptr;
+#[cfg(any(test, feature = "testing"))]
+pub(crate) mod quarantine;
 pub(crate) mod reader;
+pub(crate) mod recycling;
+pub(crate) mod registry;
+pub(crate) mod slice;
 pub(crate) mod state;
 mod sync;
 
 #[cfg(test)]
 mod tests;
 
+pub use arc_swap::EpochArcSwap;
+pub use batch::WriteBatch;
+pub use cell::{AtomicEpochCell, InlineValue};
+pub use collect_guard::CollectGuard;
 pub use domain::{EpochGcDomain, EpochGcDomainBuilder};
-pub use garbage::GcHandle;
-pub use ptr::EpochPtr;
-pub use reader::{LocalEpoch, PinGuard};
+pub use epoch_cell::{EpochCell, EpochCellReader};
+pub use ext::{LoadFull, OptionPtr};
+#[cfg(feature = "metrics")]
+pub use garbage::COLLECT_LATENCY_BUCKETS;
+pub use garbage::{CollectReport, GcHandle, Generation, LagStats, RetireToken, ShutdownReport};
+pub use list::{EpochList, EpochListIter};
+pub use metrics::GcMetrics;
+#[cfg(debug_assertions)]
+pub use ptr::LoadError;
+pub use ptr::{EpochPtr, Ref, Storable, TagAligned};
+#[cfg(any(test, feature = "testing"))]
+pub use quarantine::POISON_BYTE;
+#[cfg(not(feature = "loom"))]
+pub use reader::ReaderToken;
+pub use reader::{LocalEpoch, PinGuard, ReaderGroup, ReaderSet, UnpinnedToken};
+pub use recycling::RecyclingEpochPtr;
+pub use registry::{ReaderStorage, ReadersBackend};
@@ -0,0 +1,214 @@
+use crate::garbage::GcHandle;
+use crate::ptr::{EpochPtr, untag};
+use crate::reader::PinGuard;
+use crate::sync::{AtomicPtr, Ordering};
+use std::marker::PhantomData;
+
+/// A node of an `EpochList`, owning the rest of the list through `next`.
+/// `EpochList` 的一个节点，通过 `next` 拥有链表的其余部分。
+struct ListNode<T: 'static> {
+    value: T,
+    next: EpochPtr<ListNode<T>>,
+}
+
+/// Wrap an untagged node pointer, taking ownership of it.
+/// 包装一个未带标签的节点指针，并取得其所有权。
+#[inline]
+fn owning_ptr<T: 'static>(node: *mut ListNode<T>) -> EpochPtr<ListNode<T>> {
+    EpochPtr {
+        ptr: AtomicPtr::new(node),
+        _marker: PhantomData,
+    }
+}
+
+/// Destructor for a popped node: drops its value but not the nodes it still points to,
+/// which now belong to the list again.
+///
+/// 被弹出节点的析构函数：drop 其值，但不 drop 它仍然指向的节点，这些节点现在重新属于链表。
+unsafe fn drop_popped<T: 'static>(ptr: *mut ()) {
+    let node = unsafe { Box::from_raw(ptr as *mut ListNode<T>) };
+    node.next.ptr.store(std::ptr::null_mut(), Ordering::Relaxed);
+    drop(node);
+}
+
+/// An epoch-protected singly-linked list with a single writer.
+///
+/// Each link is an `EpochPtr`, so readers traverse the list with nothing but a `PinGuard`,
+/// while the writer pushes and pops at the front. A popped node is retired through the
+/// `GcHandle` and reclaimed only once every reader that might still be standing on it has
+/// unpinned; the nodes after it are never touched, so a reader keeps seeing a consistent
+/// suffix of the list.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::{EpochGcDomain, EpochList};
+///
+/// let (mut gc, domain) = EpochGcDomain::new();
+/// let list: EpochList<i32> = (1..=3).collect();
+/// list.push_front(0, &mut gc);
+///
+/// let local_epoch = domain.register_reader();
+/// let guard = local_epoch.pin();
+/// assert_eq!(list.iter(&guard).copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+/// ```
+///
+/// 一个只有单个写入者的受 epoch 保护的单链表。
+///
+/// 每个链接都是一个 `EpochPtr`，因此读者只需一个 `PinGuard` 即可遍历链表，而写入者在表头进行压入和弹出。
+/// 被弹出的节点通过 `GcHandle` 退休，只有在所有可能仍停留在它上面的读者都解除钉住之后才会被回收；
+/// 它之后的节点永远不会被修改，因此读者始终看到链表的一个一致的后缀。
+pub struct EpochList<T: 'static> {
+    head: EpochPtr<ListNode<T>>,
+}
+
+impl<T: 'static> EpochList<T> {
+    /// Create an empty list.
+    /// 创建一个空链表。
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            head: EpochPtr::null(),
+        }
+    }
+
+    /// Check whether the list is currently empty.
+    /// 检查链表当前是否为空。
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.is_null()
+    }
+
+    /// Get the first element, if any.
+    /// 获取第一个元素（如果有）。
+    #[inline]
+    pub fn front<'guard>(&self, guard: &'guard PinGuard) -> Option<&'guard T> {
+        self.iter(guard).next()
+    }
+
+    /// Iterate over the elements from front to back.
+    ///
+    /// The iterator borrows from the guard, so every element it yields stays valid while the
+    /// guard is held, even if the writer pops it in the meantime.
+    ///
+    /// 从前到后遍历元素。
+    /// 迭代器借用守卫，因此即使写入者在此期间弹出了元素，它产生的每个元素在守卫被持有期间都保持有效。
+    #[inline]
+    pub fn iter<'guard>(&self, _guard: &'guard PinGuard) -> EpochListIter<'guard, T> {
+        EpochListIter {
+            next: untag(self.head.ptr.load(Ordering::Acquire)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writer: insert `value` at the front of the list.
+    ///
+    /// The new node links to the current head, so no node is retired.
+    ///
+    /// 写入者：在链表头部插入 `value`。
+    /// 新节点链接到当前的表头，因此不会退休任何节点。
+    #[inline]
+    pub fn push_front(&self, value: T, _gc: &mut GcHandle) {
+        let head = untag(self.head.ptr.load(Ordering::Relaxed));
+        let node = Box::into_raw(Box::new(ListNode {
+            value,
+            next: owning_ptr(head),
+        }));
+        self.head.replace(node, Ordering::Release);
+    }
+
+    /// Writer: remove the first element, returning `false` if the list was empty.
+    ///
+    /// The element is not returned, because readers may still be reading it: its node is
+    /// retired and dropped once they have unpinned, like a value replaced by `store()`.
+    ///
+    /// 写入者：移除第一个元素，如果链表为空则返回 `false`。
+    /// 元素不会被返回，因为读者可能仍在读取它：其节点被退休，并在读者解除钉住之后被 drop，
+    /// 与被 `store()` 替换的值一样。
+    ///
+    /// The popped value is dropped by whichever thread calls `collect()`, so `T` must be `Send`:
+    /// 被弹出的值由调用 `collect()` 的线程 drop，因此 `T` 必须是 `Send`：
+    ///
+    /// ```compile_fail
+    /// use std::rc::Rc;
+    /// use swmr_epoch::{EpochGcDomain, EpochList};
+    ///
+    /// let (mut gc, _domain) = EpochGcDomain::new();
+    /// let list: EpochList<Rc<i32>> = [Rc::new(1)].into_iter().collect();
+    /// list.pop_front(&mut gc); // error: `Rc<i32>` is not `Send`
+    /// std::thread::spawn(move || gc.collect()).join().unwrap();
+    /// ```
+    #[inline]
+    pub fn pop_front(&self, gc: &mut GcHandle) -> bool
+    where
+        T: Send,
+    {
+        let head = untag(self.head.ptr.load(Ordering::Relaxed));
+        if head.is_null() {
+            return false;
+        }
+
+        let next = untag(unsafe { &*head }.next.ptr.load(Ordering::Relaxed));
+        let old = self.head.replace(next, Ordering::Release);
+        unsafe {
+            gc.retire_raw(old as *mut (), drop_popped::<T>);
+        }
+        true
+    }
+}
+
+impl<T: 'static> Default for EpochList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> FromIterator<T> for EpochList<T> {
+    /// Build a list holding the items in iteration order, without going through a `GcHandle`.
+    /// 构建一个按迭代顺序持有各元素的链表，无需经过 `GcHandle`。
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let items: Vec<T> = iter.into_iter().collect();
+        let mut head = std::ptr::null_mut();
+        for value in items.into_iter().rev() {
+            head = Box::into_raw(Box::new(ListNode {
+                value,
+                next: owning_ptr(head),
+            }));
+        }
+        Self {
+            head: owning_ptr(head),
+        }
+    }
+}
+
+impl<T: 'static> Drop for EpochList<T> {
+    /// Free the nodes one by one, so long lists do not overflow the stack.
+    /// 逐个释放节点，使长链表不会导致栈溢出。
+    fn drop(&mut self) {
+        let mut node = untag(self.head.ptr.swap(std::ptr::null_mut(), Ordering::Relaxed));
+        while !node.is_null() {
+            let boxed = unsafe { Box::from_raw(node) };
+            node = untag(boxed.next.ptr.swap(std::ptr::null_mut(), Ordering::Relaxed));
+        }
+    }
+}
+
+/// Iterator over the elements of an `EpochList`, returned by `EpochList::iter()`.
+/// `EpochList` 元素的迭代器，由 `EpochList::iter()` 返回。
+pub struct EpochListIter<'guard, T: 'static> {
+    next: *mut ListNode<T>,
+    _marker: PhantomData<&'guard T>,
+}
+
+impl<'guard, T: 'static> Iterator for EpochListIter<'guard, T> {
+    type Item = &'guard T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'guard T> {
+        if self.next.is_null() {
+            return None;
+        }
+        let node = unsafe { &*self.next };
+        self.next = untag(node.next.ptr.load(Ordering::Acquire));
+        Some(&node.value)
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// Observability hooks called by a domain as it runs.
+///
+/// Inject an implementation with `EpochGcDomainBuilder::with_metrics()` to feed collection and
+/// reader activity into your own counters, histograms or tracing. Every method has an empty
+/// default, so an implementation only overrides the events it cares about.
+///
+/// The hooks run inline on the thread that triggered the event: `on_collect` on the writer,
+/// `on_pin` and `on_register` on the reader. `on_pin` sits on the read path, so it should be
+/// cheap, typically a relaxed atomic increment.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use swmr_epoch::{EpochGcDomain, GcMetrics};
+///
+/// #[derive(Default)]
+/// struct Pins(AtomicUsize);
+///
+/// impl GcMetrics for Pins {
+///     fn on_pin(&self) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let pins = Arc::new(Pins::default());
+/// let (_gc, domain) = EpochGcDomain::builder().with_metrics(pins.clone()).build();
+/// let reader = domain.register_reader();
+/// drop(reader.pin());
+/// assert_eq!(pins.0.load(Ordering::Relaxed), 1);
+/// ```
+///
+/// 域在运行时调用的可观测性钩子。
+/// 通过 `EpochGcDomainBuilder::with_metrics()` 注入一个实现，即可将回收和读者活动接入你自己的计数器、直方图或追踪系统。
+/// 每个方法都有空的默认实现，因此实现只需重写它关心的事件。
+///
+/// 钩子在触发事件的线程上内联运行：`on_collect` 在写入者上，`on_pin` 和 `on_register` 在读者上。
+/// `on_pin` 位于读路径上，因此应当足够廉价，通常是一次 relaxed 原子递增。
+pub trait GcMetrics: Send + Sync {
+    /// Called after each collection cycle with the number of objects it reclaimed and the
+    /// global epoch it advanced to.
+    ///
+    /// 在每个回收周期之后调用，参数为该周期回收的对象数量以及它推进到的全局纪元。
+    #[inline]
+    fn on_collect(&self, reclaimed: usize, epoch: usize) {
+        let _ = (reclaimed, epoch);
+    }
+
+    /// Called when a reader enters its outermost pin. Nested pins do not call it.
+    ///
+    /// 在读者进入最外层 pin 时调用。嵌套的 pin 不会调用它。
+    #[inline]
+    fn on_pin(&self) {}
+
+    /// Called when a reader is registered with the domain.
+    ///
+    /// 在向域注册读者时调用。
+    #[inline]
+    fn on_register(&self) {}
+}
+
+impl fmt::Debug for dyn GcMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GcMetrics")
+    }
+}
@@ -0,0 +1,211 @@
+//! Deterministic single-threaded simulation for checking reclamation safety.
+//!
+//! A test scripts a sequence of `Op`s against a `Model`, which drives a real domain, a real
+//! `EpochPtr` and real readers, and tracks every retired value with a `RetireToken`. After each
+//! operation the model checks the reclamation-safety invariant against a reference model:
+//!
+//! > A value is never reclaimed while a reader that pinned before the value was replaced is
+//! > still pinned.
+//!
+//! It also checks liveness: once no reader is pinned, two collections reclaim everything.
+//!
+//! ```
+//! use swmr_epoch::model::{Model, Op};
+//!
+//! Model::run(2, [Op::Pin(0), Op::Store, Op::Collect, Op::Unpin(0), Op::Collect]);
+//! ```
+//!
+//! 用于检查回收安全性的确定性单线程模拟。
+//!
+//! 测试针对 `Model` 编写一系列 `Op`，`Model` 会驱动真实的域、真实的 `EpochPtr` 和真实的读者，
+//! 并通过 `RetireToken` 跟踪每个已退休的值。每次操作之后，模型都会对照参考模型检查回收安全不变量：
+//! 当一个在值被替换之前钉住的读者仍被钉住时，该值永远不会被回收。
+//! 它还会检查活性：一旦没有读者被钉住，两次回收就会回收所有垃圾。
+
+use crate::domain::EpochGcDomain;
+use crate::garbage::{GcHandle, RetireToken};
+use crate::ptr::EpochPtr;
+use crate::reader::{LocalEpoch, PinGuard};
+use std::vec::Vec;
+
+/// One step of a simulated schedule.
+///
+/// 模拟调度中的一步。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Pin the given reader. Ignored if it is already pinned.
+    /// 钉住给定的读者。如果它已经被钉住则忽略。
+    Pin(usize),
+    /// Unpin the given reader. Ignored if it is not pinned.
+    /// 解除给定读者的钉住。如果它没有被钉住则忽略。
+    Unpin(usize),
+    /// Store a new value, retiring the old one (may trigger an automatic collection).
+    /// 存入一个新值并退休旧值（可能触发自动回收）。
+    Store,
+    /// Run a collection cycle.
+    /// 运行一个回收周期。
+    Collect,
+}
+
+/// A retired value and the step at which it was replaced.
+/// 一个已退休的值及其被替换时的步骤。
+struct Retired {
+    token: RetireToken,
+    retired_at: usize,
+}
+
+/// A simulated domain with a fixed set of readers and one pointer.
+///
+/// 一个具有固定读者集合和一个指针的模拟域。
+pub struct Model {
+    gc: GcHandle,
+    readers: Vec<LocalEpoch>,
+    /// Step at which each reader pinned, or `None` if it is not pinned.
+    /// 每个读者被钉住时的步骤，未被钉住时为 `None`。
+    pinned_at: Vec<Option<usize>>,
+    ptr: EpochPtr<usize>,
+    retired: Vec<Retired>,
+    step: usize,
+}
+
+impl Model {
+    /// Create a model with `readers` readers and a small auto-reclaim threshold, so that
+    /// automatic collections are exercised as well.
+    ///
+    /// 创建一个具有 `readers` 个读者和较小自动回收阈值的模型，以便自动回收也能被覆盖。
+    pub fn new(readers: usize) -> Self {
+        let (gc, domain) = EpochGcDomain::builder().auto_reclaim_threshold(4).build();
+        Self {
+            gc,
+            readers: (0..readers).map(|_| domain.register_reader()).collect(),
+            pinned_at: std::vec![None; readers],
+            ptr: EpochPtr::new(0),
+            retired: Vec::new(),
+            step: 0,
+        }
+    }
+
+    /// Run `ops` on a fresh model, then unpin everyone and check that all garbage is reclaimed.
+    ///
+    /// # Panics
+    /// Panics if the safety or liveness invariant is violated.
+    ///
+    /// 在一个新模型上运行 `ops`，然后解除所有读者的钉住并检查所有垃圾都被回收。
+    ///
+    /// # Panics
+    /// 如果违反了安全或活性不变量则 panic。
+    pub fn run(readers: usize, ops: impl IntoIterator<Item = Op>) {
+        let mut model = Self::new(readers);
+        for op in ops {
+            model.apply(op);
+        }
+        model.finish();
+    }
+
+    /// Apply one operation and check the safety invariant.
+    ///
+    /// # Panics
+    /// Panics if a reader index is out of range or the safety invariant is violated.
+    ///
+    /// 应用一个操作并检查安全不变量。
+    ///
+    /// # Panics
+    /// 如果读者索引越界或违反了安全不变量则 panic。
+    pub fn apply(&mut self, op: Op) {
+        self.step += 1;
+        match op {
+            Op::Pin(reader) => {
+                if self.pinned_at[reader].is_none() {
+                    let guard = self.readers[reader].pin();
+                    // Keep the pin alive until `Op::Unpin`
+                    std::mem::forget(guard);
+                    self.pinned_at[reader] = Some(self.step);
+                }
+            }
+            Op::Unpin(reader) => {
+                if self.pinned_at[reader].take().is_some() {
+                    drop(PinGuard {
+                        reader: &self.readers[reader],
+                    });
+                }
+            }
+            Op::Store => {
+                let token = self.ptr.store_tracked(self.step, &mut self.gc).unwrap();
+                self.retired.push(Retired {
+                    token,
+                    retired_at: self.step,
+                });
+            }
+            Op::Collect => self.gc.collect(),
+        }
+        self.check_safety();
+    }
+
+    /// Number of retired values that have been reclaimed so far.
+    /// 到目前为止已被回收的已退休值的数量。
+    pub fn reclaimed(&self) -> usize {
+        self.retired
+            .iter()
+            .filter(|retired| retired.token.is_reclaimed())
+            .count()
+    }
+
+    /// Number of values retired so far.
+    /// 到目前为止已退休的值的数量。
+    pub fn retired(&self) -> usize {
+        self.retired.len()
+    }
+
+    /// Unpin every reader, collect, and check that all garbage was reclaimed.
+    ///
+    /// # Panics
+    /// Panics if some garbage is left over.
+    ///
+    /// 解除所有读者的钉住并回收，检查所有垃圾都已被回收。
+    ///
+    /// # Panics
+    /// 如果有垃圾残留则 panic。
+    pub fn finish(&mut self) {
+        for reader in 0..self.readers.len() {
+            self.apply(Op::Unpin(reader));
+        }
+        self.apply(Op::Collect);
+        self.apply(Op::Collect);
+        assert_eq!(
+            self.reclaimed(),
+            self.retired(),
+            "garbage left over after all readers unpinned"
+        );
+    }
+
+    /// Reference model: a value replaced at step `s` may still be observed by any reader that
+    /// pinned before `s` and is still pinned.
+    /// 参考模型：在步骤 `s` 被替换的值，仍可能被任何在 `s` 之前钉住且仍被钉住的读者观察到。
+    fn check_safety(&self) {
+        let oldest_pin = self.pinned_at.iter().flatten().min().copied();
+        for retired in &self.retired {
+            if let Some(pinned_at) = oldest_pin {
+                assert!(
+                    !(retired.token.is_reclaimed() && pinned_at < retired.retired_at),
+                    "value retired at step {} was reclaimed while a reader pinned at step {} \
+                     is still pinned",
+                    retired.retired_at,
+                    pinned_at
+                );
+            }
+        }
+    }
+}
+
+impl Drop for Model {
+    fn drop(&mut self) {
+        // Release the forgotten pins so the readers drop cleanly
+        for reader in 0..self.readers.len() {
+            if self.pinned_at[reader].take().is_some() {
+                drop(PinGuard {
+                    reader: &self.readers[reader],
+                });
+            }
+        }
+    }
+}
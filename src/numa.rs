@@ -0,0 +1,42 @@
+/// `MPOL_MF_MOVE` from `<numaif.h>`, which `libc` does not export: move pages that are only
+/// mapped by this process.
+/// 来自 `<numaif.h>` 的 `MPOL_MF_MOVE`，`libc` 没有导出它：移动仅由本进程映射的页面。
+#[cfg(target_os = "linux")]
+const MPOL_MF_MOVE: libc::c_int = 1 << 1;
+
+/// Ask the kernel to migrate the page holding `value` to NUMA node `node`.
+///
+/// Best effort: the request is silently dropped if the kernel lacks NUMA support, the
+/// process may not migrate pages (e.g. inside some containers), or `node` does not exist.
+/// The whole page moves, including any neighbouring allocations on it. Does nothing on
+/// platforms other than Linux.
+///
+/// 请求内核将持有 `value` 的页面迁移到 NUMA 节点 `node`。
+/// 尽力而为：如果内核不支持 NUMA、进程不允许迁移页面（例如在某些容器中）或 `node` 不存在，请求会被静默丢弃。
+/// 整个页面都会被移动，包括其上相邻的任何分配。在 Linux 以外的平台上不做任何事。
+#[cfg(target_os = "linux")]
+pub(crate) fn move_to_node<T>(value: *const T, node: usize) {
+    let Ok(node) = libc::c_int::try_from(node) else {
+        return;
+    };
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let page = value.map_addr(|addr| addr & !(page_size - 1)) as *mut libc::c_void;
+    let mut status: libc::c_int = 0;
+
+    // Safety: `move_pages` only reads the three one-element arrays and writes `status`.
+    // Migrating a page changes where its memory lives, never its contents or address.
+    unsafe {
+        libc::syscall(
+            libc::SYS_move_pages,
+            0 as libc::pid_t,
+            1 as libc::c_ulong,
+            &page as *const *mut libc::c_void,
+            &node as *const libc::c_int,
+            &mut status as *mut libc::c_int,
+            MPOL_MF_MOVE,
+        );
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn move_to_node<T>(_value: *const T, _node: usize) {}
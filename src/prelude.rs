@@ -0,0 +1,24 @@
+//! Convenience re-exports of the commonly used types and extension traits.
+//!
+//! ```
+//! use swmr_epoch::prelude::*;
+//! use std::sync::Arc;
+//!
+//! let (mut gc, domain): (GcHandle, EpochGcDomain) = EpochGcDomain::new();
+//! let config = EpochPtr::new(Arc::new(String::from("v1")));
+//!
+//! let local_epoch: LocalEpoch = domain.register_reader();
+//! let snapshot = {
+//!     let guard: PinGuard = local_epoch.pin();
+//!     config.load_full(&guard)
+//! };
+//!
+//! config.store(Arc::new(String::from("v2")), &mut gc);
+//! gc.collect();
+//! assert_eq!(*snapshot, "v1");
+//! ```
+//!
+//! 常用类型和扩展 trait 的便捷重导出。
+
+pub use crate::ext::{LoadFull, OptionPtr};
+pub use crate::{EpochGcDomain, EpochPtr, GcHandle, LocalEpoch, PinGuard};
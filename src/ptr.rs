@@ -1,7 +1,233 @@
-use crate::garbage::GcHandle;
+use crate::garbage::{GcHandle, RetireToken};
 use crate::reader::PinGuard;
-use crate::sync::{AtomicPtr, Ordering};
+#[cfg(debug_assertions)]
+use crate::state::INACTIVE_EPOCH;
+#[cfg(not(feature = "backoff"))]
+use crate::sync::yield_now;
+use crate::sync::{AtomicPtr, Ordering, fence};
+use std::alloc::Layout;
 use std::boxed::Box;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+mod sealed {
+    pub trait Sealed {}
+    impl<T: 'static> Sealed for T {}
+    impl<T: 'static> Sealed for [T] {}
+}
+
+/// Types that can be stored behind an `EpochPtr`.
+///
+/// Implemented for every sized `T: 'static`, and for slices `[T]`. The trait is sealed and
+/// its items are implementation details: a value is kept behind a thin pointer to
+/// `Self::Thin` so that it can be swapped with a single atomic operation.
+///
+/// 可以存储在 `EpochPtr` 后面的类型。
+/// 为每个有大小的 `T: 'static` 以及切片 `[T]` 实现。此 trait 是密封的，其成员是实现细节：
+/// 值通过指向 `Self::Thin` 的瘦指针保存，以便可以用单个原子操作进行交换。
+pub trait Storable: sealed::Sealed + 'static {
+    #[doc(hidden)]
+    type Thin;
+
+    #[doc(hidden)]
+    fn into_thin(value: Box<Self>) -> *mut Self::Thin;
+
+    #[doc(hidden)]
+    unsafe fn deref_thin<'a>(ptr: *mut Self::Thin) -> &'a Self;
+
+    #[doc(hidden)]
+    unsafe fn drop_thin(ptr: *mut Self::Thin);
+
+    #[doc(hidden)]
+    unsafe fn drop_contents(ptr: *mut Self::Thin) -> Layout;
+}
+
+impl<T: 'static> Storable for T {
+    type Thin = T;
+
+    #[inline(always)]
+    fn into_thin(value: Box<Self>) -> *mut T {
+        Box::into_raw(value)
+    }
+
+    #[inline(always)]
+    unsafe fn deref_thin<'a>(ptr: *mut T) -> &'a T {
+        unsafe { &*ptr }
+    }
+
+    #[inline(always)]
+    unsafe fn drop_thin(ptr: *mut T) {
+        unsafe { drop(Box::from_raw(ptr)) }
+    }
+
+    #[inline(always)]
+    unsafe fn drop_contents(ptr: *mut T) -> Layout {
+        unsafe { std::ptr::drop_in_place(ptr) };
+        Layout::new::<T>()
+    }
+}
+
+/// Error returned by `EpochPtr::try_load()` when a load would not be safe.
+///
+/// `EpochPtr::try_load()` 在加载不安全时返回的错误。
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+    /// The guard's reader has a pin count of zero.
+    /// 守卫的读者的 pin 计数为零。
+    NotPinned,
+    /// The guard's reader slot is not pinned to any epoch.
+    /// 守卫的读者槽没有被钉住到任何纪元。
+    SlotInactive,
+    /// The pointer has not been initialized.
+    /// 指针尚未被初始化。
+    Null,
+}
+
+#[cfg(debug_assertions)]
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LoadError::NotPinned => "the guard's reader is not pinned",
+            LoadError::SlotInactive => "the guard's reader slot is not pinned to any epoch",
+            LoadError::Null => "the pointer has not been initialized",
+        })
+    }
+}
+
+#[cfg(debug_assertions)]
+impl std::error::Error for LoadError {}
+
+/// Mask of the low pointer bits that the alignment of `P` leaves free for a version tag.
+/// `P` 的对齐方式留给版本标签使用的低位指针比特掩码。
+#[inline(always)]
+const fn tag_mask<P>() -> usize {
+    std::mem::align_of::<P>() - 1
+}
+
+/// Strip the version tag from a pointer loaded from an `EpochPtr`.
+/// 从 `EpochPtr` 加载的指针中去除版本标签。
+#[inline(always)]
+pub(crate) fn untag<P>(ptr: *mut P) -> *mut P {
+    ptr.map_addr(|addr| addr & !tag_mask::<P>())
+}
+
+/// Dereference an untagged pointer loaded by a reader, panicking if it is null.
+///
+/// A pointer created with `EpochPtr::null()` has no value yet, so every safe load goes
+/// through this check instead of dereferencing null.
+///
+/// 解引用读者加载的未带标签指针，如果它为 null 则 panic。
+/// 通过 `EpochPtr::null()` 创建的指针尚无值，因此每个安全的 load 都会经过此检查，而不是解引用 null。
+#[inline(always)]
+#[track_caller]
+fn deref_loaded<'a, P>(ptr: *mut P) -> &'a P {
+    assert!(!ptr.is_null(), "load of a null EpochPtr");
+    unsafe { &*ptr }
+}
+
+/// Get the version tag packed into a pointer.
+/// 获取打包在指针中的版本标签。
+#[inline(always)]
+fn tag_of<P>(ptr: *mut P) -> usize {
+    ptr.addr() & tag_mask::<P>()
+}
+
+/// Pack a version tag into the free low bits of an untagged pointer, wrapping on overflow.
+/// 将版本标签打包到未带标签指针的空闲低位中，溢出时回绕。
+#[inline(always)]
+fn with_tag<P>(ptr: *mut P, tag: usize) -> *mut P {
+    debug_assert_eq!(tag_of(ptr), 0, "pointer is not aligned");
+    ptr.map_addr(|addr| addr | (tag & tag_mask::<P>()))
+}
+
+/// A value over-aligned to 8 bytes, so that pointers to it have 3 free low bits for a tag.
+///
+/// The version tag of `load_tagged()` and `cas_tagged()` lives in the low bits that the
+/// pointee's alignment leaves free, so types such as `u8` or `[u8; N]` have none. Wrapping
+/// them in `TagAligned` raises the alignment of every allocation, including the ones made by
+/// `store()`, and since the wrapper is part of the type, reclamation frees each allocation
+/// with the same layout it was made with. Create such pointers with `EpochPtr::new_tagged()`.
+///
+/// 一个被过度对齐到 8 字节的值，使指向它的指针有 3 个空闲低位可用作标签。
+/// `load_tagged()` 和 `cas_tagged()` 的版本标签存放在被指向类型的对齐方式留出的低位中，因此 `u8` 或 `[u8; N]`
+/// 等类型没有空闲低位。用 `TagAligned` 包装它们会提高每次分配（包括 `store()` 所做的分配）的对齐，
+/// 并且由于包装器是类型的一部分，回收时会以与分配时相同的布局释放每个分配。使用 `EpochPtr::new_tagged()` 创建此类指针。
+#[repr(align(8))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TagAligned<T>(pub T);
+
+impl<T> TagAligned<T> {
+    /// Unwrap the value.
+    /// 取出内部的值。
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for TagAligned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for TagAligned<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// A reference to a value loaded from an `EpochPtr`, bound to the guard it was loaded under.
+///
+/// Returned by `EpochPtr::load_ref()`. It dereferences to the value like the plain `&T` from
+/// `load()`, and also records the epoch the reader was pinned at when loading it.
+///
+/// 从 `EpochPtr` 加载的值的引用，绑定到加载时所用的守卫。
+/// 由 `EpochPtr::load_ref()` 返回。它像 `load()` 返回的普通 `&T` 一样解引用到值，并且还记录了加载时读者被钉住的纪元。
+#[derive(Debug)]
+pub struct Ref<'guard, T: ?Sized> {
+    value: &'guard T,
+    epoch: usize,
+}
+
+impl<'guard, T: ?Sized> Ref<'guard, T> {
+    /// The epoch the reader was pinned at when the value was loaded.
+    /// 加载该值时读者被钉住的纪元。
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// Get the plain reference, with the same lifetime.
+    /// 获取具有相同生命周期的普通引用。
+    #[inline]
+    pub fn into_ref(self) -> &'guard T {
+        self.value
+    }
+}
+
+impl<T: ?Sized> Clone for Ref<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Ref<'_, T> {}
+
+impl<T: ?Sized> std::ops::Deref for Ref<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
 
 /// An epoch-protected shared pointer for safe concurrent access.
 ///
@@ -46,10 +272,88 @@ use std::boxed::Box;
 /// - 写入者必须对所有可能被相同读者访问的指针使用相同的 `GcHandle`。
 ///   这确保了正确的垃圾回收。
 /// - 从 `load()` 返回的引用的生命周期被绑定到 `PinGuard`。
-pub struct EpochPtr<T> {
-    ptr: AtomicPtr<T>,
+///
+/// **Thread Safety**: Readers on other threads obtain `&T` from a shared `&EpochPtr<T>`, and
+/// the writer moves values in and retired values out through it. `EpochPtr<T>` is therefore
+/// `Send` only if `T: Send`, and `Sync` only if `T: Send + Sync`:
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use swmr_epoch::EpochPtr;
+///
+/// fn assert_sync<T: Sync>(_: &T) {}
+/// assert_sync(&EpochPtr::new(Cell::new(0))); // error: `Cell<i32>` is not `Sync`
+/// ```
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use swmr_epoch::EpochPtr;
+///
+/// fn assert_send<T: Send>(_: &T) {}
+/// assert_send(&EpochPtr::new(Rc::new(0))); // error: `Rc<i32>` is not `Send`
+/// ```
+///
+/// Retired values are dropped by whichever thread calls `collect()`, and a `GcHandle` can
+/// move between threads, so writer methods that retire values (`store()` and its variants)
+/// also require `T: Send`:
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use swmr_epoch::{EpochGcDomain, EpochPtr};
+///
+/// let (mut gc, _domain) = EpochGcDomain::new();
+/// let ptr = EpochPtr::new(Rc::new(1));
+/// ptr.store(Rc::new(2), &mut gc); // error: `Rc<i32>` is not `Send`
+/// std::thread::spawn(move || gc.collect()).join().unwrap();
+/// ```
+///
+/// **线程安全性**：其他线程上的读者通过共享的 `&EpochPtr<T>` 获得 `&T`，写入者也通过它移入新值并移出已退休的值。
+/// 因此 `EpochPtr<T>` 只有在 `T: Send` 时才是 `Send`，只有在 `T: Send + Sync` 时才是 `Sync`。
+/// 已退休的值由调用 `collect()` 的线程 drop，而 `GcHandle` 可以在线程间移动，
+/// 因此退休值的写入者方法（`store()` 及其变体）也要求 `T: Send`。
+///
+/// **Memory Ordering**: every operation that installs a value releases it, and every load that
+/// returns one acquires it, so a reader always sees the value fully initialized:
+/// - Reader loads use `Acquire`.
+/// - Writer swaps (`store()` and its variants, `WriteBatch::store()`) use `AcqRel`. The release
+///   half publishes the new value. The acquire half matters because the value swapped out may
+///   have been installed by another thread through `init_once()` or
+///   `compare_and_set_with_backoff()`, and the writer eventually drops it, which must happen
+///   after that thread built it.
+/// - `cas_tagged()` and `compare_and_set_with_backoff()` use `AcqRel` on success for the same
+///   two reasons, and `Acquire` on failure. `init_once()` only replaces null, so it uses
+///   `Release` on success and `Relaxed` on failure, where nothing is read through the pointer.
+///
+/// Reclamation safety does not rest on these orderings alone: a reader's pin and the writer's
+/// scan are separated by `SeqCst` fences, so that either the scan sees the pinned reader or
+/// the reader's loads see the writer's latest swaps.
+///
+/// **内存顺序**：每个安装值的操作都会 release 它，每个返回值的加载都会 acquire 它，因此读者总能看到完全初始化的值：
+/// - 读者加载使用 `Acquire`。
+/// - 写入者交换（`store()` 及其变体、`WriteBatch::store()`）使用 `AcqRel`。release 部分发布新值。
+///   acquire 部分同样必要，因为被换出的值可能是另一个线程通过 `init_once()` 或 `compare_and_set_with_backoff()` 安装的，
+///   而写入者最终会 drop 它，这必须发生在该线程构造它之后。
+/// - `cas_tagged()` 和 `compare_and_set_with_backoff()` 在成功时出于同样的两个原因使用 `AcqRel`，失败时使用 `Acquire`。
+///   `init_once()` 只替换空指针，因此成功时使用 `Release`，失败时使用 `Relaxed`，此时不会通过指针读取任何内容。
+///
+/// 回收的安全性并不只依赖这些顺序：读者的 pin 与写入者的扫描之间由 `SeqCst` 栅栏分隔，
+/// 使得要么扫描看到被钉住的读者，要么读者的加载看到写入者最新的交换。
+pub struct EpochPtr<T: ?Sized + Storable> {
+    pub(crate) ptr: AtomicPtr<T::Thin>,
+    /// Ties the auto traits to `T` instead of to the always-`Send + Sync` `AtomicPtr`.
+    /// 将自动 trait 绑定到 `T`，而不是始终 `Send + Sync` 的 `AtomicPtr`。
+    pub(crate) _marker: PhantomData<*const T>,
 }
 
+// Safety: Sending an `EpochPtr<T>` to another thread moves ownership of the current value
+// with it, which is exactly what sending a `Box<T>` does, so `T: Send` suffices.
+unsafe impl<T: ?Sized + Storable + Send> Send for EpochPtr<T> {}
+
+// Safety: Through `&EpochPtr<T>`, readers on several threads obtain `&T` concurrently
+// (requires `T: Sync`), and the writer moves values in on one thread while retired values may
+// be dropped on another (requires `T: Send`).
+unsafe impl<T: ?Sized + Storable + Send + Sync> Sync for EpochPtr<T> {}
+
 impl<T: 'static> EpochPtr<T> {
     /// Create a new epoch-protected pointer, initialized with the given value.
     /// 创建一个新的受 epoch 保护的指针，初始化为给定的值。
@@ -57,6 +361,34 @@ impl<T: 'static> EpochPtr<T> {
     pub fn new(data: T) -> Self {
         Self {
             ptr: AtomicPtr::new(Box::into_raw(Box::new(data))),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new epoch-protected pointer, initialized with the value returned by `f`.
+    ///
+    /// Same as `new(f())`, for symmetry with the other lazy APIs such as `store_with()` and
+    /// `get_or_insert_with()`.
+    ///
+    /// 创建一个新的受 epoch 保护的指针，初始化为 `f` 返回的值。
+    /// 等同于 `new(f())`，与 `store_with()` 和 `get_or_insert_with()` 等其他延迟 API 保持对称。
+    #[inline]
+    pub fn new_with(f: impl FnOnce() -> T) -> Self {
+        Self::new(f())
+    }
+
+    /// Create an empty (null) epoch-protected pointer.
+    ///
+    /// A null pointer must be initialized, e.g. with `store()` or `load_or_init()`, before it
+    /// is read with `load()`. Loading it earlier panics.
+    ///
+    /// 创建一个空的（null）受 epoch 保护的指针。
+    /// 空指针在通过 `load()` 读取之前必须先被初始化，例如通过 `store()` 或 `load_or_init()`。提前加载会 panic。
+    #[inline]
+    pub fn null() -> Self {
+        Self {
+            ptr: AtomicPtr::new(std::ptr::null_mut()),
+            _marker: PhantomData,
         }
     }
 
@@ -73,8 +405,8 @@ impl<T: 'static> EpochPtr<T> {
     /// - This creates a compile-time guarantee of memory safety without runtime overhead.
     ///
     /// # Panics
-    /// This method does not panic. The `guard` parameter is used only for type-level safety.
-    /// If you call this without a valid `PinGuard`, you are violating the API contract.
+    /// Panics if the pointer is null, i.e. created with `null()` and not initialized yet.
+    /// The same holds for every method built on `load()`.
     ///
     /// 读取者 load：安全地读取当前值。
     ///
@@ -86,10 +418,586 @@ impl<T: 'static> EpochPtr<T> {
     /// - 你不能在守卫被 drop 后使用该引用。
     /// - 当守卫（以及引用）活跃时，写入者不能回收数据。
     /// - 这在没有运行时开销的情况下创建了内存安全的编译时保证。
+    ///
+    /// # Panics
+    /// 如果指针为 null（即通过 `null()` 创建且尚未初始化）则 panic。所有基于 `load()` 的方法同样如此。
     #[inline]
+    #[track_caller]
     pub fn load<'guard>(&self, _guard: &'guard PinGuard) -> &'guard T {
+        deref_loaded(untag(self.ptr.load(Ordering::Acquire)))
+    }
+
+    /// Reader load of every pointer in `ptrs` under a single pin.
+    ///
+    /// Yields the current value of each pointer in order. Each item is read when the iterator
+    /// reaches it, with the same single acquire load as `load()`, so pinning once and
+    /// iterating costs one atomic load per pointer and nothing else.
+    ///
+    /// # Example
+    /// ```
+    /// use swmr_epoch::{EpochGcDomain, EpochPtr};
+    ///
+    /// let (_gc, domain) = EpochGcDomain::new();
+    /// let local_epoch = domain.register_reader();
+    /// let table = [EpochPtr::new(1), EpochPtr::new(2), EpochPtr::new(3)];
+    ///
+    /// let guard = local_epoch.pin();
+    /// let sum: i32 = EpochPtr::load_many(&table, &guard).sum();
+    /// assert_eq!(sum, 6);
+    /// ```
+    ///
+    /// 在单次 pin 下读取 `ptrs` 中的每个指针。
+    /// 按顺序产出每个指针的当前值。每一项在迭代器到达它时才被读取，使用与 `load()` 相同的单次 acquire 加载，
+    /// 因此 pin 一次并迭代的开销是每个指针一次原子加载，没有其他开销。
+    #[inline]
+    pub fn load_many<'guard>(
+        ptrs: &[EpochPtr<T>],
+        guard: &'guard PinGuard,
+    ) -> impl ExactSizeIterator<Item = &'guard T> + DoubleEndedIterator {
+        ptrs.iter().map(move |ptr| ptr.load(guard))
+    }
+
+    /// Reader load returning a `Ref`, which dereferences to the value and also carries the
+    /// epoch it was read at.
+    ///
+    /// Equivalent to `load()` apart from the wrapper, which leaves room for extra information
+    /// without changing call sites that simply dereference the result.
+    ///
+    /// 返回 `Ref` 的读取者 load，它解引用到值，并携带读取时的纪元。
+    /// 除了包装之外与 `load()` 相同，包装为额外信息留出了空间，而无需修改仅对结果解引用的调用点。
+    #[inline]
+    pub fn load_ref<'guard>(&self, guard: &'guard PinGuard) -> Ref<'guard, T> {
+        Ref {
+            value: self.load(guard),
+            epoch: guard.epoch(),
+        }
+    }
+
+    /// Reader load that returns an owned clone of the current value.
+    ///
+    /// The clone does not borrow from the guard, so the caller can drop the guard right away
+    /// instead of keeping the thread pinned while using the value.
+    ///
+    /// 读取当前值并返回其拥有所有权的克隆。
+    ///
+    /// 克隆不借用守卫，因此调用者可以立即 drop 守卫，而不必在使用值时保持线程被钉住。
+    #[inline]
+    pub fn load_cloned(&self, guard: &PinGuard) -> T
+    where
+        T: Clone,
+    {
+        self.load(guard).clone()
+    }
+
+    /// Load the current value, initializing a null pointer first.
+    ///
+    /// If the pointer is null, `init` is called and its result is stored; otherwise `init`
+    /// is not called. Storing requires the `GcHandle`, so only the writer can call this,
+    /// which also makes the check-then-store race-free. A helper using
+    /// `compare_and_set_with_backoff()` cannot replace a null pointer, but once it is
+    /// initialized a helper may replace the value before it is loaded, in which case the
+    /// helper's value is returned.
+    ///
+    /// 加载当前值，如果指针为空则先进行初始化。
+    ///
+    /// 如果指针为空，则调用 `init` 并存储其结果；否则不调用 `init`。
+    /// 存储需要 `GcHandle`，因此只有写入者可以调用此方法，这也使得先检查后存储不存在竞争。
+    /// 使用 `compare_and_set_with_backoff()` 的辅助线程无法替换 null 指针，但一旦完成初始化，
+    /// 辅助线程可能在加载之前替换该值，此时返回的是辅助线程的值。
+    #[inline]
+    pub fn load_or_init<'guard>(
+        &self,
+        guard: &'guard PinGuard,
+        init: impl FnOnce() -> T,
+        gc: &mut GcHandle,
+    ) -> &'guard T
+    where
+        T: Send,
+    {
+        if self.is_null() {
+            self.store(init(), gc);
+        }
+        self.load(guard)
+    }
+
+    /// Return the current value, or initialize a null pointer with `f()` and return that.
+    ///
+    /// Named after `Option::get_or_insert_with()`; otherwise the same as `load_or_init()`.
+    /// `f` is only called while the pointer is null, so once it has been initialized, later
+    /// calls return the stored value without constructing anything.
+    ///
+    /// 返回当前值，或者用 `f()` 初始化空指针并返回该值。
+    /// 以 `Option::get_or_insert_with()` 命名，其余与 `load_or_init()` 相同。
+    /// `f` 只会在指针为空时被调用，因此一旦完成初始化，之后的调用会直接返回已存储的值而不构造任何东西。
+    #[inline]
+    pub fn get_or_insert_with<'guard>(
+        &self,
+        guard: &'guard PinGuard,
+        f: impl FnOnce() -> T,
+        gc: &mut GcHandle,
+    ) -> &'guard T
+    where
+        T: Send,
+    {
+        self.load_or_init(guard, f, gc)
+    }
+
+    /// Initialize a null pointer with `value`, or give `value` back if it is already set.
+    ///
+    /// Supports two-phase construction: create the pointer with `null()` before the value is
+    /// ready, e.g. for circular structures, and set it once later. Since a null pointer has
+    /// no old value to retire, no `GcHandle` is needed, and the compare-and-swap makes the
+    /// first of several concurrent callers win. Returns `Err(value)` if the pointer was not
+    /// null.
+    ///
+    /// 用 `value` 初始化一个空指针，如果已经设置过则交还 `value`。
+    /// 支持两阶段构造：在值尚未就绪时用 `null()` 创建指针（例如用于循环结构），之后再设置一次。
+    /// 由于空指针没有需要退休的旧值，因此不需要 `GcHandle`，而比较并交换使多个并发调用者中的第一个获胜。
+    /// 如果指针不为空则返回 `Err(value)`。
+    pub fn init_once(&self, value: T) -> Result<(), T> {
+        let mut current = self.ptr.load(Ordering::Relaxed);
+        if !untag(current).is_null() {
+            return Err(value);
+        }
+
+        let new_ptr = Box::into_raw(Box::new(value));
+        loop {
+            let tagged = with_tag(new_ptr, tag_of(current).wrapping_add(1));
+            match self
+                .ptr
+                .compare_exchange(current, tagged, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return Ok(()),
+                // Only the tag of a null pointer changed, try again
+                Err(actual) if untag(actual).is_null() => current = actual,
+                Err(_) => return Err(*unsafe { Box::from_raw(new_ptr) }),
+            }
+        }
+    }
+
+    /// Format the current value for diagnostics.
+    ///
+    /// The `Debug` impl of `EpochPtr` only prints the raw address, because formatting the
+    /// value requires a guard. This returns a wrapper that prints `EpochPtr(<value>)` using the
+    /// value loaded when this method is called; a concurrent `store()` is not reflected, so
+    /// the output is a snapshot.
+    ///
+    /// 格式化当前值以用于诊断。
+    ///
+    /// `EpochPtr` 的 `Debug` 实现只打印原始地址，因为格式化值需要守卫。
+    /// 此方法返回一个包装器，使用调用时加载的值打印 `EpochPtr(<value>)`；
+    /// 并发的 `store()` 不会被反映，因此输出只是一个快照。
+    #[inline]
+    pub fn debug_value<'guard>(&self, guard: &'guard PinGuard) -> impl std::fmt::Debug + 'guard
+    where
+        T: std::fmt::Debug,
+    {
+        struct DebugValue<'a, T>(&'a T);
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for DebugValue<'_, T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple("EpochPtr").field(self.0).finish()
+            }
+        }
+
+        DebugValue(self.load(guard))
+    }
+
+    /// Reader comparison of the values of two pointers, both loaded under `guard`.
+    ///
+    /// Equivalent to `self.load(guard).cmp(other.load(guard))`, the comparison needed when
+    /// ordering the nodes of a tree or skiplist built on `EpochPtr`. Both loads happen under
+    /// the same pin, so neither value can be reclaimed during the comparison; the writer may
+    /// still replace either value right afterwards. Comparing a pointer with itself skips the
+    /// second load and returns `Equal`.
+    ///
+    /// 在 `guard` 下加载两个指针的值并进行比较的读取者方法。
+    /// 等价于 `self.load(guard).cmp(other.load(guard))`，即对基于 `EpochPtr` 构建的树或跳表的节点排序时所需的比较。
+    /// 两次加载在同一个 pin 下进行，因此比较期间两个值都不会被回收；写入者仍可能在之后立即替换任一值。
+    /// 将指针与自身比较时会跳过第二次加载并返回 `Equal`。
+    #[inline]
+    pub fn load_cmp(&self, other: &EpochPtr<T>, guard: &PinGuard) -> std::cmp::Ordering
+    where
+        T: Ord,
+    {
+        if std::ptr::eq(self, other) {
+            return std::cmp::Ordering::Equal;
+        }
+        self.load(guard).cmp(other.load(guard))
+    }
+
+    /// Reader transformation into a new, independent pointer.
+    ///
+    /// Loads the current value under `guard`, applies `f` to it, and returns a fresh
+    /// `EpochPtr<U>` owning the result. The new pointer shares nothing with `self`: later
+    /// stores to either one do not affect the other, and `self` is left untouched.
+    ///
+    /// 将当前值变换为一个新的、独立的指针的读取者方法。
+    /// 在 `guard` 下加载当前值，对其应用 `f`，并返回一个拥有结果的新 `EpochPtr<U>`。
+    /// 新指针与 `self` 不共享任何内容：之后对任一指针的 store 都不会影响另一个，`self` 也保持不变。
+    #[inline]
+    pub fn map<U: 'static, F: FnOnce(&T) -> U>(&self, f: F, guard: &PinGuard) -> EpochPtr<U> {
+        EpochPtr::new(f(self.load(guard)))
+    }
+
+    /// Check whether two pointers currently point to the same allocation.
+    ///
+    /// This is a momentary comparison of the raw pointer values: a writer may replace either
+    /// value immediately afterwards, so the result only describes the instant of the loads.
+    ///
+    /// 检查两个指针当前是否指向同一个分配。
+    ///
+    /// 这是对原始指针值的瞬时比较：写入者可能在之后立即替换任一值，
+    /// 因此结果只描述加载时的那一刻。
+    #[inline]
+    pub fn ptr_eq(a: &EpochPtr<T>, b: &EpochPtr<T>) -> bool {
+        std::ptr::eq(
+            untag(a.ptr.load(Ordering::Acquire)),
+            untag(b.ptr.load(Ordering::Acquire)),
+        )
+    }
+
+    /// Check whether the pointer is currently null, i.e. has not been initialized yet.
+    ///
+    /// This is a relaxed load that does not dereference the pointer, so no guard is needed.
+    /// The answer may be stale by the time it is used; it must not be taken as permission to
+    /// call `load()` without a guard.
+    ///
+    /// 检查指针当前是否为 null，即尚未被初始化。
+    /// 这是一次不解引用指针的 relaxed load，因此不需要守卫。
+    /// 结果在被使用时可能已经过时；不能将其作为在没有守卫的情况下调用 `load()` 的依据。
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        untag(self.ptr.load(Ordering::Relaxed)).is_null()
+    }
+
+    /// Return the raw address currently stored in the pointer, for identity checks and logging.
+    ///
+    /// **The returned pointer must never be dereferenced.** It is read with a relaxed load and
+    /// is not protected by any guard, so the value it points to may already have been retired
+    /// and reclaimed. Use `load()` with a `PinGuard` to access the value.
+    ///
+    /// 返回指针当前存储的原始地址，仅用于身份比较和日志记录。
+    ///
+    /// **绝不能解引用返回的指针。** 它通过 relaxed load 读取，且不受任何守卫保护，
+    /// 因此它指向的值可能已经被退休并回收。请使用 `load()` 配合 `PinGuard` 访问值。
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        untag(self.ptr.load(Ordering::Relaxed))
+    }
+
+    /// Best-effort observation of the current value without registering a reader.
+    ///
+    /// Intended for one-off diagnostic reads from threads that are not registered readers, such
+    /// as a monitoring thread. Unlike `as_ptr()`, the result is meant to be dereferenced, but
+    /// nothing protects it: the load is relaxed and no epoch is pinned, so the writer may
+    /// retire and reclaim the value at any moment. Prefer `load()` with a `PinGuard` whenever a
+    /// reader can be registered.
+    ///
+    /// # Safety
+    /// Before dereferencing the returned pointer, the caller must ensure that:
+    /// - the writer is quiescent: it does not replace or reclaim this value for as long as the
+    ///   pointer is used;
+    /// - the value's initialization is visible to this thread, because the load itself does
+    ///   not synchronize with the store that published it (for example, the writer handed
+    ///   control over through a channel, a lock or a joined thread after storing).
+    ///
+    /// The pointer may be null if the `EpochPtr` was never initialized.
+    ///
+    /// 在不注册读者的情况下尽力观察当前值。
+    /// 用于从未注册为读者的线程（例如监控线程）进行一次性的诊断读取。与 `as_ptr()` 不同，返回结果是供解引用的，
+    /// 但没有任何保护：load 是 relaxed 的且没有钉住任何纪元，因此写入者可能随时退休并回收该值。
+    /// 只要能注册读者，就应优先使用 `load()` 配合 `PinGuard`。
+    ///
+    /// # Safety
+    /// 在解引用返回的指针之前，调用者必须确保：
+    /// - 写入者处于静止状态：在指针被使用期间，它不会替换或回收该值；
+    /// - 该值的初始化对当前线程可见，因为 load 本身不会与发布它的 store 同步
+    ///   （例如写入者在 store 之后通过通道、锁或被 join 的线程交出了控制权）。
+    ///
+    /// 如果 `EpochPtr` 从未被初始化，指针可能为 null。
+    #[inline]
+    pub unsafe fn observe(&self) -> *const T {
+        untag(self.ptr.load(Ordering::Relaxed))
+    }
+
+    /// Reader load with an explicit memory ordering, for experts tuning specific architectures.
+    ///
+    /// `load()` uses `Acquire`, which is what makes the value's initialization visible to the
+    /// reader. Only `Acquire`, `Relaxed` and `SeqCst` are valid for a load; like
+    /// `AtomicPtr::load`, this panics on `Release` and `AcqRel`.
+    ///
+    /// # Safety
+    /// With `Relaxed`, the initialization of the value the pointer was last set to must
+    /// already be visible to the calling thread some other way, for example through a fence
+    /// or because this thread stored it. `Acquire` and `SeqCst` have no extra requirement.
+    ///
+    /// 使用显式内存顺序的读者 load，供针对特定架构进行调优的专家使用。
+    /// `load()` 使用 `Acquire`，这正是使值的初始化对读者可见的原因。
+    /// 只有 `Acquire`、`Relaxed` 和 `SeqCst` 对 load 有效；与 `AtomicPtr::load` 一样，对 `Release` 和 `AcqRel` 会 panic。
+    ///
+    /// # Safety
+    /// 使用 `Relaxed` 时，指针最后被设置的值的初始化必须已经通过其他方式对调用线程可见，
+    /// 例如通过 fence，或者该值就是由此线程存储的。`Acquire` 和 `SeqCst` 没有额外要求。
+    #[inline]
+    pub unsafe fn load_ordered<'guard>(
+        &self,
+        _guard: &'guard PinGuard,
+        order: Ordering,
+    ) -> &'guard T {
+        deref_loaded(untag(self.ptr.load(order)))
+    }
+
+    /// Reader load that validates the guard before dereferencing (debug builds only).
+    ///
+    /// The type system already requires a `PinGuard`, but a guard whose pin state was corrupted,
+    /// for example through unsafe code, would make `load()` undefined behavior. This checks that
+    /// the guard's reader is pinned and that the pointer has been initialized, returning a
+    /// `LoadError` instead. `EpochPtr` is not tied to a domain, so a guard from an unrelated
+    /// domain cannot be detected.
+    ///
+    /// 在解引用之前验证守卫的读者 load（仅调试构建）。
+    ///
+    /// 类型系统已经要求 `PinGuard`，但一个 pin 状态被破坏（例如通过不安全代码）的守卫会使 `load()` 成为未定义行为。
+    /// 此方法检查守卫的读者确实被钉住，并且指针已被初始化，否则返回 `LoadError`。
+    /// `EpochPtr` 并不绑定到某个域，因此无法检测来自无关域的守卫。
+    #[cfg(debug_assertions)]
+    pub fn try_load<'guard>(&self, guard: &'guard PinGuard) -> Result<&'guard T, LoadError> {
+        if guard.reader.pin_count.get() == 0 {
+            return Err(LoadError::NotPinned);
+        }
+        if guard.reader.slot.active_epoch.load(Ordering::Acquire) == INACTIVE_EPOCH {
+            return Err(LoadError::SlotInactive);
+        }
+
+        let ptr = untag(self.ptr.load(Ordering::Acquire));
+        if ptr.is_null() {
+            return Err(LoadError::Null);
+        }
+        Ok(unsafe { &*ptr })
+    }
+
+    /// Reader load that also returns the pointer's version tag.
+    ///
+    /// The tag lives in the low bits of the pointer that `T`'s alignment leaves free, and is
+    /// bumped by every writer update (`store()`, `cas_tagged()`, ...). Passing the returned
+    /// pair to `cas_tagged()` therefore detects ABA: the CAS fails if the pointer was replaced
+    /// in between, even if the same address was later stored again. The tag wraps around after
+    /// `align_of::<T>()` updates.
+    ///
+    /// Only available for types aligned to at least 2 bytes; using it with a 1-byte aligned
+    /// `T` is a compile error. Wrap such types in `TagAligned` (see `new_tagged()`) instead.
+    ///
+    /// ```compile_fail
+    /// use swmr_epoch::{EpochGcDomain, EpochPtr};
+    ///
+    /// let (_gc, domain) = EpochGcDomain::new();
+    /// let local_epoch = domain.register_reader();
+    /// let ptr = EpochPtr::new(0u8);
+    /// let guard = local_epoch.pin();
+    /// ptr.load_tagged(&guard); // error: `u8` has no free low bits
+    /// ```
+    ///
+    /// 同时返回指针版本标签的读取者 load。
+    ///
+    /// 标签存放在 `T` 的对齐方式留出的指针低位中，每次写入者更新（`store()`、`cas_tagged()` 等）都会递增它。
+    /// 因此将返回的二元组传给 `cas_tagged()` 可以检测 ABA：如果指针在此期间被替换过，即使之后又存入了相同的地址，CAS 也会失败。
+    /// 标签在 `align_of::<T>()` 次更新后回绕。
+    ///
+    /// 仅适用于对齐至少为 2 字节的类型；对 1 字节对齐的 `T` 使用它会导致编译错误。
+    /// 对于此类类型，请改为用 `TagAligned` 包装（参见 `new_tagged()`）。
+    #[inline]
+    pub fn load_tagged<'guard>(&self, _guard: &'guard PinGuard) -> (&'guard T, usize) {
+        const {
+            assert!(
+                tag_mask::<T>() > 0,
+                "T's alignment leaves no bits for a tag"
+            )
+        };
         let ptr = self.ptr.load(Ordering::Acquire);
-        unsafe { &*ptr }
+        (deref_loaded(untag(ptr)), tag_of(ptr))
+    }
+
+    /// Writer compare-and-store on a `(pointer, tag)` pair obtained from `load_tagged()`.
+    ///
+    /// Replaces the value only if both the pointer and the tag still match `expected`, in which
+    /// case the tag is incremented and the old value is retired. Otherwise the pointer is left
+    /// unchanged and `data` is returned as the error.
+    ///
+    /// 对从 `load_tagged()` 获得的 `(指针, 标签)` 二元组进行写入者比较并存储。
+    /// 仅当指针和标签都仍与 `expected` 匹配时才替换值，此时标签递增，旧值被退休。
+    /// 否则指针保持不变，`data` 作为错误返回。
+    #[inline]
+    pub fn cas_tagged(&self, expected: (&T, usize), data: T, gc: &mut GcHandle) -> Result<(), T>
+    where
+        T: Send,
+    {
+        const {
+            assert!(
+                tag_mask::<T>() > 0,
+                "T's alignment leaves no bits for a tag"
+            )
+        };
+        let (expected_value, expected_tag) = expected;
+        let current = with_tag(expected_value as *const T as *mut T, expected_tag);
+
+        let new_ptr = Box::into_raw(Box::new(data));
+        match self.ptr.compare_exchange(
+            current,
+            with_tag(new_ptr, expected_tag.wrapping_add(1)),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(old_ptr) => {
+                unsafe {
+                    gc.retire(Box::from_raw(untag(old_ptr)));
+                }
+                Ok(())
+            }
+            Err(_) => Err(*unsafe { Box::from_raw(new_ptr) }),
+        }
+    }
+
+    /// Helper compare-and-set from a non-writer thread, retrying with exponential backoff.
+    ///
+    /// Replaces the value with `new` only if it is still `current`, which must have been
+    /// loaded under `guard`. Returns `Ok(())` on success, and `Err(new)` as soon as another
+    /// thread has replaced `current`. Failures that leave `current` in place (a spurious
+    /// failure, or a concurrent tag change) are retried after spinning for an exponentially
+    /// growing number of iterations, then yielding, or with `crossbeam_utils::Backoff` if the
+    /// `backoff` feature is enabled. On success the old value is retired through
+    /// `PinGuard::defer()`, so it is reclaimed by the writer's next collection once every
+    /// reader that may hold it has unpinned.
+    ///
+    /// **This weakens the single-writer guarantee.** Memory safety is preserved: each replaced
+    /// value is handed out by exactly one successful swap, so it is retired exactly once, only
+    /// the writer reclaims, and writer methods that dereference the current value load it with
+    /// `Acquire`, so a value published by a helper is fully visible to them. But writer
+    /// methods that read the current value before replacing it, such as `fetch_update()`, may
+    /// overwrite a helper's update based on a stale value, and the version tag may not
+    /// increase on every update. Only use it on pointers whose updates are designed to
+    /// tolerate this.
+    ///
+    /// 来自非写入者线程的辅助比较并设置，以指数退避重试。
+    /// 仅当值仍为 `current` 时才将其替换为 `new`，`current` 必须是在 `guard` 下加载的。成功时返回 `Ok(())`，
+    /// 一旦另一个线程替换了 `current` 就返回 `Err(new)`。保留 `current` 不变的失败（伪失败或并发的标签变化）
+    /// 会在自旋指数增长的次数之后重试，然后改为让出线程（启用 `backoff` feature 时改用 `crossbeam_utils::Backoff`）。
+    /// 成功时旧值通过 `PinGuard::defer()` 退休，因此一旦可能持有它的每个读者都解除钉住，它就会由写入者的下一次回收来回收。
+    ///
+    /// **这会削弱单写入者保证。** 内存安全性得以保留：每个被替换的值恰好由一次成功的交换交出，因此恰好被退休一次；
+    /// 只有写入者进行回收；并且解引用当前值的写入者方法以 `Acquire` 加载它，因此辅助线程发布的值对它们完全可见。
+    /// 但在替换之前读取当前值的写入者方法（例如 `fetch_update()`）可能基于过时的值覆盖辅助线程的更新，
+    /// 并且版本标签不一定在每次更新时都递增。仅在其更新被设计为能容忍这些情况的指针上使用。
+    pub fn compare_and_set_with_backoff(
+        &self,
+        current: &T,
+        new: T,
+        guard: &PinGuard,
+    ) -> Result<(), T>
+    where
+        T: Send,
+    {
+        #[cfg(not(feature = "backoff"))]
+        const SPIN_LIMIT: u32 = 6;
+
+        let expected = current as *const T as *mut T;
+        let new_ptr = Box::into_raw(Box::new(new));
+        #[cfg(feature = "backoff")]
+        let backoff = crossbeam_utils::Backoff::new();
+        #[cfg(not(feature = "backoff"))]
+        let mut step = 0;
+        loop {
+            let raw = self.ptr.load(Ordering::Acquire);
+            if untag(raw) != expected {
+                return Err(*unsafe { Box::from_raw(new_ptr) });
+            }
+
+            if self
+                .ptr
+                .compare_exchange_weak(
+                    raw,
+                    with_tag(new_ptr, tag_of(raw).wrapping_add(1)),
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_ok()
+            {
+                // `current` was loaded under `guard`, so it is still allocated, and the swap
+                // above took it out of the pointer: nobody else can retire it
+                let old = unsafe { Box::from_raw(expected) };
+                guard.defer(move || drop(old));
+                return Ok(());
+            }
+
+            #[cfg(feature = "backoff")]
+            backoff.snooze();
+            #[cfg(not(feature = "backoff"))]
+            if step <= SPIN_LIMIT {
+                for _ in 0..1 << step {
+                    std::hint::spin_loop();
+                }
+                step += 1;
+            } else {
+                yield_now();
+            }
+        }
+    }
+
+    /// Swap in an untagged `new_ptr`, bumping the tag, and return the untagged old pointer.
+    ///
+    /// Only the writer replaces the pointer, so the tag read here cannot change before the swap,
+    /// unless a helper uses `compare_and_set_with_backoff()`. Even then the swap returns the
+    /// pointer it actually replaced; only the tag bump may be lost.
+    ///
+    /// 换入未带标签的 `new_ptr` 并递增标签，返回未带标签的旧指针。
+    /// 只有写入者会替换指针，因此这里读取的标签在交换之前不会改变，除非有辅助线程使用 `compare_and_set_with_backoff()`。
+    /// 即便如此，交换返回的也是它实际替换的指针；只有标签的递增可能丢失。
+    #[inline]
+    pub(crate) fn replace(&self, new_ptr: *mut T, order: Ordering) -> *mut T {
+        let tag = tag_of(self.ptr.load(Ordering::Relaxed));
+        untag(self.ptr.swap(with_tag(new_ptr, tag.wrapping_add(1)), order))
+    }
+
+    /// Load the current value for a writer method that reads it before replacing it.
+    ///
+    /// Returns the loaded pointer, still tagged so it can be passed to `replace_if()`, and the
+    /// value it points at, or `None` if the pointer is null. The load is `Acquire`, since a
+    /// helper may have published the value with `compare_and_set_with_backoff()`. Helpers may
+    /// also replace it concurrently, but only the writer reclaims, so the value stays alive
+    /// until the writer's next collection.
+    ///
+    /// 为在替换之前读取当前值的写入者方法加载当前值。
+    /// 返回加载到的指针（仍带标签，以便传给 `replace_if()`）及其指向的值；如果指针为 null 则返回 `None`。
+    /// 加载使用 `Acquire`，因为辅助线程可能通过 `compare_and_set_with_backoff()` 发布了该值。
+    /// 辅助线程也可能并发地替换它，但只有写入者进行回收，因此该值在写入者的下一次回收之前一直存活。
+    #[inline]
+    pub(crate) fn writer_current(&self) -> Option<(*mut T, &T)> {
+        let observed = self.ptr.load(Ordering::Acquire);
+        let current = untag(observed);
+        (!current.is_null()).then(|| (observed, unsafe { &*current }))
+    }
+
+    /// Swap in an untagged `new_ptr` only if the pointer is still exactly `observed`, bumping
+    /// the tag, and return the untagged old pointer.
+    ///
+    /// For writer paths that check the current value before replacing it: a helper using
+    /// `compare_and_set_with_backoff()` may have replaced it since, and then this fails with
+    /// the pointer that is there now instead of overwriting the helper's update.
+    ///
+    /// 仅当指针仍恰好为 `observed` 时才换入未带标签的 `new_ptr` 并递增标签，返回未带标签的旧指针。
+    /// 用于在替换之前检查当前值的写入者路径：辅助线程可能已经通过 `compare_and_set_with_backoff()` 替换了它，
+    /// 此时这里会失败并返回当前的指针，而不是覆盖辅助线程的更新。
+    #[inline]
+    pub(crate) fn replace_if(&self, observed: *mut T, new_ptr: *mut T) -> Result<*mut T, *mut T> {
+        self.ptr
+            .compare_exchange(
+                observed,
+                with_tag(new_ptr, tag_of(observed).wrapping_add(1)),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .map(untag)
+            .map_err(untag)
     }
 
     /// Writer store: safely update the value and retire the old one.
@@ -100,7 +1008,12 @@ impl<T: 'static> EpochPtr<T> {
     /// all readers have moved past the epoch in which it was retired).
     ///
     /// **Automatic Reclamation**: This operation may trigger automatic garbage collection
-    /// if the garbage threshold is exceeded.
+    /// if the garbage threshold is exceeded. If no reader is registered with the domain, the
+    /// old value cannot be observed by anyone and is dropped immediately instead.
+    ///
+    /// **Backpressure**: If `max_pending_garbage` is configured and the garbage queue is full,
+    /// this call blocks until readers advance far enough to free room. Use `try_store()` to
+    /// fail instead of blocking.
     ///
     /// 写入者 store：安全地更新值并退休旧值。
     /// 此方法原子地用新指针替换当前指针，
@@ -109,27 +1022,337 @@ impl<T: 'static> EpochPtr<T> {
     /// 退休该值的纪元之后）。
     ///
     /// **自动回收**：如果超过垃圾阈值，此操作可能会触发自动垃圾回收。
+    /// 如果域中没有注册任何读者，旧值不可能被任何人观察到，因此会被立即 drop。
+    ///
+    /// **背压**：如果配置了 `max_pending_garbage` 且垃圾队列已满，此调用会阻塞，
+    /// 直到读者前进到足以释放空间。使用 `try_store()` 可以失败而不是阻塞。
+    #[inline]
+    pub fn store(&self, data: T, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        self.store_boxed(Box::new(data), gc);
+    }
+
+    /// Writer store that reports whether it triggered an automatic collection.
+    ///
+    /// Same as `store()`, but returns `true` if retiring the old value pushed the garbage count
+    /// over the auto-reclaim threshold and a collection ran as part of this call. Useful to
+    /// find which store in a loop is responsible for a latency spike.
+    ///
+    /// 报告是否触发了自动回收的写入者 store。
+    /// 与 `store()` 相同，但如果退休旧值使垃圾计数超过自动回收阈值、并在此次调用中运行了一次回收，则返回 `true`。
+    /// 可用于找出循环中是哪一次 store 导致了延迟尖峰。
+    #[inline]
+    pub fn store_reporting(&self, data: T, gc: &mut GcHandle) -> bool
+    where
+        T: Send,
+    {
+        let before = gc.auto_collections;
+        self.store(data, gc);
+        gc.auto_collections != before
+    }
+
+    /// Publish an already allocated value and retire the old one, as `store()` does.
+    /// 发布一个已分配的值并退休旧值，与 `store()` 的行为相同。
     #[inline]
-    pub fn store(&self, data: T, gc: &mut GcHandle) {
+    fn store_boxed(&self, data: Box<T>, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        let new_ptr = Box::into_raw(data);
+        let old_ptr = self.replace(new_ptr, Ordering::AcqRel);
+
+        if !old_ptr.is_null() {
+            unsafe {
+                gc.retire_or_free(Box::from_raw(old_ptr));
+            }
+        }
+    }
+
+    /// Writer swap that waits until the old value is unreachable, then returns it.
+    ///
+    /// Stores `data`, then repeatedly advances the epoch and scans the readers until no reader
+    /// can still hold the old pointer, and finally hands the old value back by value instead of
+    /// retiring it. This gives writers willing to block a synchronous "swap and recover now".
+    ///
+    /// **Blocking**: this waits for every reader pinned at the time of the swap to unpin. If a
+    /// reader never unpins, it blocks forever.
+    ///
+    /// # Panics
+    /// Panics if the pointer is null.
+    ///
+    /// 等待旧值不可达后再将其返回的写入者 swap。
+    /// 存入 `data`，然后反复推进纪元并扫描读者，直到没有读者仍可能持有旧指针，最后按值交还旧值，而不是退休它。
+    /// 这为愿意阻塞的写入者提供了同步的"交换并立即取回"操作。
+    ///
+    /// **阻塞**：此方法会等待交换时被钉住的每个读者解除钉住。如果某个读者永不解除钉住，它将永远阻塞。
+    ///
+    /// # Panics
+    /// 如果指针为 null 则 panic。
+    pub fn swap_blocking(&self, data: T, gc: &mut GcHandle) -> T {
+        let new_ptr = Box::into_raw(Box::new(data));
+        let old_ptr = self.replace(new_ptr, Ordering::AcqRel);
+        assert!(
+            !old_ptr.is_null(),
+            "swap_blocking called on a null EpochPtr"
+        );
+
+        gc.quiesce();
+
+        *unsafe { Box::from_raw(old_ptr) }
+    }
+
+    /// Writer store that never triggers an automatic collection.
+    ///
+    /// Behaves like `store()`, but skips the auto-reclaim threshold check, so a tight loop of
+    /// stores followed by one explicit `collect()` never collects in the middle. Other stores
+    /// still honor the threshold, unlike disabling automatic reclamation for the whole handle.
+    /// The `max_pending_garbage` cap is not enforced either.
+    ///
+    /// 永远不会触发自动回收的写入者 store。
+    /// 行为与 `store()` 相同，但跳过自动回收阈值检查，因此一连串 store 之后跟随一次显式 `collect()` 的紧凑循环永远不会在中途回收。
+    /// 与为整个句柄禁用自动回收不同，其他 store 仍然遵循该阈值。`max_pending_garbage` 上限同样不会被强制执行。
+    #[inline]
+    pub fn store_no_autocollect(&self, data: T, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
         let new_ptr = Box::into_raw(Box::new(data));
-        let old_ptr = self.ptr.swap(new_ptr, Ordering::Release);
+        let old_ptr = self.replace(new_ptr, Ordering::AcqRel);
 
         if !old_ptr.is_null() {
             unsafe {
-                gc.retire(Box::from_raw(old_ptr));
+                gc.retire_no_autocollect(Box::from_raw(old_ptr));
+            }
+        }
+    }
+
+    /// Writer store that also returns a token tracking the reclamation of the old value.
+    ///
+    /// Behaves like `store()`. Returns `None` if the pointer was null, since there is no old
+    /// value to retire. Tracking costs one extra allocation per call, so prefer `store()`
+    /// when the token is not needed.
+    ///
+    /// 同时返回一个跟踪旧值回收情况的令牌的写入者 store。
+    /// 行为与 `store()` 相同。如果指针为 null，由于没有旧值需要退休，返回 `None`。
+    /// 跟踪在每次调用时需要一次额外的分配，因此不需要令牌时请优先使用 `store()`。
+    #[inline]
+    pub fn store_tracked(&self, data: T, gc: &mut GcHandle) -> Option<RetireToken>
+    where
+        T: Send,
+    {
+        let new_ptr = Box::into_raw(Box::new(data));
+        let old_ptr = self.replace(new_ptr, Ordering::AcqRel);
+
+        if old_ptr.is_null() {
+            None
+        } else {
+            Some(gc.retire_tracked(unsafe { Box::from_raw(old_ptr) }))
+        }
+    }
+
+    /// Writer store that fails instead of blocking when the garbage queue is full.
+    ///
+    /// Behaves like `store()`, except that if `max_pending_garbage` is configured and the
+    /// queue is still full after a forced `collect()`, the pointer is left unchanged and
+    /// `data` is returned as the error. Always succeeds when no cap is configured.
+    ///
+    /// 在垃圾队列已满时失败而不是阻塞的写入者 store。
+    /// 行为与 `store()` 相同，但如果配置了 `max_pending_garbage` 且在强制 `collect()` 之后队列仍然已满，
+    /// 指针保持不变，`data` 作为错误返回。未配置上限时总是成功。
+    #[inline]
+    pub fn try_store(&self, data: T, gc: &mut GcHandle) -> Result<(), T>
+    where
+        T: Send,
+    {
+        if !self.is_null() && !gc.reserve_garbage_slot() {
+            return Err(data);
+        }
+        self.store(data, gc);
+        Ok(())
+    }
+
+    /// Writer store of a lazily produced value.
+    ///
+    /// Same as `store(f(), gc)`. On its own it only defers construction, but it pairs with
+    /// the conditional variants such as `try_store_with()`, where `f` is never called if the
+    /// update is skipped, so an expensive replacement is not built (or allocated) for nothing.
+    ///
+    /// 存储一个延迟产生的值的写入者 store。
+    /// 等同于 `store(f(), gc)`。单独使用时只是推迟了构造，但它与 `try_store_with()` 等条件变体相配合：
+    /// 如果更新被跳过，`f` 永远不会被调用，因此不会白白构建（或分配）昂贵的替换值。
+    #[inline]
+    pub fn store_with(&self, f: impl FnOnce() -> T, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        self.store(f(), gc);
+    }
+
+    /// Writer store of a lazily produced value that fails instead of blocking when the garbage
+    /// queue is full.
+    ///
+    /// Like `try_store()`, but the room in the garbage queue is checked before `f` is called,
+    /// so when the store is skipped `f` is not called at all. Returns whether it stored.
+    ///
+    /// 在垃圾队列已满时失败而不是阻塞的、存储延迟产生值的写入者 store。
+    /// 与 `try_store()` 类似，但会在调用 `f` 之前检查垃圾队列中的空间，因此当跳过存储时 `f` 根本不会被调用。返回是否进行了存储。
+    #[inline]
+    pub fn try_store_with(&self, f: impl FnOnce() -> T, gc: &mut GcHandle) -> bool
+    where
+        T: Send,
+    {
+        if !self.is_null() && !gc.reserve_garbage_slot() {
+            return false;
+        }
+        self.store(f(), gc);
+        true
+    }
+
+    /// Writer store that builds the new value directly in its heap allocation.
+    ///
+    /// `store()` takes the value by value, so a large `T` is first built on the stack and
+    /// then copied into the box. Here the box is allocated first and `init` writes the value
+    /// into it, which avoids that copy for big structs and arrays. Otherwise behaves like
+    /// `store()`. If `init` panics, the allocation is freed and the pointer is left untouched.
+    ///
+    /// # Safety
+    /// `init` must fully initialize the value before returning.
+    ///
+    /// 直接在堆分配中构建新值的写入者 store。
+    /// `store()` 按值接收数据，因此大型 `T` 会先在栈上构建，然后被复制到 box 中。
+    /// 这里先分配 box，再由 `init` 将值写入其中，从而为大型结构体和数组避免这次复制。其余行为与 `store()` 相同。
+    /// 如果 `init` panic，分配会被释放，指针保持不变。
+    ///
+    /// # Safety
+    /// `init` 必须在返回之前完全初始化该值。
+    #[inline]
+    pub unsafe fn store_in_place(&self, init: impl FnOnce(&mut MaybeUninit<T>), gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        let mut data = Box::<T>::new_uninit();
+        init(&mut data);
+        self.store_boxed(unsafe { data.assume_init() }, gc);
+    }
+
+    /// Writer update: compute a new value from the current one and store it.
+    ///
+    /// Mirrors `AtomicUsize::fetch_update` to ease migration from atomics: `f` receives the
+    /// current value and returns `Some(new)` to store it (retiring the old value) or `None`
+    /// to leave the pointer untouched, in which case `Err(())` is returned.
+    ///
+    /// Unlike the atomic version there is no compare-and-swap retry loop: only the single
+    /// writer (the owner of the `GcHandle`) can replace the value, so it cannot change
+    /// between the load and the store. The exception is a helper using
+    /// `compare_and_set_with_backoff()`: an update it makes in that window is overwritten
+    /// based on a stale value.
+    ///
+    /// # Panics
+    /// Panics if the pointer is null.
+    ///
+    /// 写入者更新：根据当前值计算新值并存储。
+    ///
+    /// 模仿 `AtomicUsize::fetch_update` 以便于从原子类型迁移：`f` 接收当前值，
+    /// 返回 `Some(new)` 以存储它（并退休旧值），或返回 `None` 保持指针不变，此时返回 `Err(())`。
+    ///
+    /// 与原子版本不同，这里没有比较并交换的重试循环：只有唯一的写入者（`GcHandle` 的持有者）
+    /// 可以替换值，因此它在加载和存储之间不会改变。例外是使用 `compare_and_set_with_backoff()` 的辅助线程：
+    /// 它在这段窗口内所做的更新会基于过时的值被覆盖。
+    ///
+    /// # Panics
+    /// 如果指针为 null 则 panic。
+    #[allow(clippy::result_unit_err)]
+    #[inline]
+    pub fn fetch_update<F>(&self, mut f: F, gc: &mut GcHandle) -> Result<(), ()>
+    where
+        T: Send,
+        F: FnMut(&T) -> Option<T>,
+    {
+        let (_, current) = self
+            .writer_current()
+            .expect("fetch_update called on a null EpochPtr");
+
+        match f(current) {
+            Some(new) => {
+                self.store(new, gc);
+                Ok(())
             }
+            None => Err(()),
+        }
+    }
+
+    /// Writer store that hands the old allocation back when nobody can be reading it.
+    ///
+    /// Stores `data` like `store()`. If no reader is pinned at that moment, no reader can
+    /// observe the old value anymore, so it is returned to the caller (e.g. to reuse the
+    /// allocation) instead of being retired. Otherwise the old value is retired as usual and
+    /// `None` is returned.
+    ///
+    /// 写入者 store，在没有人能读取旧分配时将其交还。
+    ///
+    /// 与 `store()` 一样存储 `data`。如果此刻没有读者被钉住，则没有读者能再观察到旧值，
+    /// 因此它会被返回给调用者（例如用于复用分配），而不是被退休。
+    /// 否则旧值会像往常一样被退休，并返回 `None`。
+    #[inline]
+    pub fn store_recycling(&self, data: T, gc: &mut GcHandle) -> Option<Box<T>>
+    where
+        T: Send,
+    {
+        let new_ptr = Box::into_raw(Box::new(data));
+        let old_ptr = self.replace(new_ptr, Ordering::AcqRel);
+
+        if old_ptr.is_null() {
+            return None;
         }
+
+        // Order the swap before the reader scan.
+        fence(Ordering::SeqCst);
+
+        let old = unsafe { Box::from_raw(old_ptr) };
+        if gc.has_active_readers() {
+            gc.retire(old);
+            None
+        } else {
+            Some(old)
+        }
+    }
+}
+
+impl<T: 'static> EpochPtr<TagAligned<T>> {
+    /// Create a pointer whose allocations are aligned to at least 8 bytes, so that
+    /// `load_tagged()` and `cas_tagged()` work for any `T`, even 1-byte aligned ones.
+    ///
+    /// # Example
+    /// ```
+    /// use swmr_epoch::{EpochGcDomain, EpochPtr, TagAligned};
+    ///
+    /// let (mut gc, domain) = EpochGcDomain::new();
+    /// let ptr = EpochPtr::new_tagged(1u8);
+    ///
+    /// let local_epoch = domain.register_reader();
+    /// let guard = local_epoch.pin();
+    /// let (value, tag) = ptr.load_tagged(&guard);
+    /// assert_eq!((**value, tag), (1, 0));
+    /// assert!(ptr.cas_tagged((value, tag), TagAligned(2), &mut gc).is_ok());
+    /// ```
+    ///
+    /// 创建一个分配至少按 8 字节对齐的指针，使 `load_tagged()` 和 `cas_tagged()` 适用于任何 `T`，即使是 1 字节对齐的类型。
+    #[inline]
+    pub fn new_tagged(value: T) -> Self {
+        Self::new(TagAligned(value))
     }
 }
 
-impl<T> std::fmt::Debug for EpochPtr<T> {
+impl<T: ?Sized + Storable> std::fmt::Debug for EpochPtr<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let ptr = self.ptr.load(Ordering::Relaxed);
+        let ptr = untag(self.ptr.load(Ordering::Relaxed));
         f.debug_tuple("EpochPtr").field(&ptr).finish()
     }
 }
 
-impl<T> Drop for EpochPtr<T> {
+impl<T: ?Sized + Storable> Drop for EpochPtr<T> {
     /// When an `EpochPtr` is dropped, it safely drops the current value.
     ///
     /// At drop time, we assume no other threads are accessing the pointer,
@@ -140,10 +1363,10 @@ impl<T> Drop for EpochPtr<T> {
     /// 所以我们可以安全地拿回并 drop 最后的值。
     #[inline]
     fn drop(&mut self) {
-        let ptr = self.ptr.load(Ordering::Relaxed);
+        let ptr = untag(self.ptr.load(Ordering::Relaxed));
         if !ptr.is_null() {
             unsafe {
-                drop(Box::from_raw(ptr));
+                T::drop_thin(ptr);
             }
         }
     }
@@ -0,0 +1,66 @@
+use crate::ptr::Storable;
+use std::alloc::{self, Layout};
+
+/// Byte pattern written over the memory of quarantined objects.
+///
+/// A reader that still dereferences a reclaimed object sees this pattern instead of
+/// plausible-looking stale data.
+///
+/// 写入被隔离对象内存的字节模式。
+/// 仍在解引用已回收对象的读者会看到此模式，而不是看似合理的过期数据。
+pub const POISON_BYTE: u8 = 0xDE;
+
+/// The allocation of a reclaimed object, kept alive but poisoned.
+///
+/// The object's destructor has already run; only the memory is held until the quarantine
+/// is cleared, so a lingering reader reference hits poisoned memory instead of freed memory.
+///
+/// 已回收对象的分配，保持存活但被毒化。
+/// 对象的析构函数已经运行；只有内存被保留直到隔离区被清空，
+/// 因此残留的读者引用会命中被毒化的内存，而不是已释放的内存。
+pub(crate) struct Quarantined {
+    ptr: *mut u8,
+    layout: Layout,
+}
+
+// Safety: the allocation is exclusively owned and holds no live value
+unsafe impl Send for Quarantined {}
+
+impl Quarantined {
+    /// Address of the quarantined allocation.
+    /// 被隔离分配的地址。
+    #[inline]
+    pub(crate) fn addr(&self) -> usize {
+        self.ptr as usize
+    }
+
+    /// Check that every byte of the allocation still holds the poison pattern.
+    /// 检查分配的每个字节是否仍为毒化模式。
+    pub(crate) fn is_poisoned(&self) -> bool {
+        let bytes = unsafe { std::slice::from_raw_parts(self.ptr, self.layout.size()) };
+        bytes.iter().all(|&byte| byte == POISON_BYTE)
+    }
+}
+
+impl Drop for Quarantined {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            unsafe { alloc::dealloc(self.ptr, self.layout) };
+        }
+    }
+}
+
+/// Type-erased quarantine routine: drop the value in place, poison and keep its memory.
+///
+/// 类型擦除的隔离例程：原地 drop 值，毒化并保留其内存。
+pub(crate) unsafe fn quarantine<P: ?Sized + Storable>(ptr: *mut ()) -> Quarantined {
+    let ptr = ptr as *mut P::Thin;
+    unsafe {
+        let layout = P::drop_contents(ptr);
+        std::ptr::write_bytes(ptr as *mut u8, POISON_BYTE, layout.size());
+        Quarantined {
+            ptr: ptr as *mut u8,
+            layout,
+        }
+    }
+}
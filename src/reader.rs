@@ -1,5 +1,9 @@
-use crate::state::{INACTIVE_EPOCH, ReaderSlot, SharedState};
-use crate::sync::{Arc, AtomicUsize, Cell, Ordering};
+use crate::domain::EpochGcDomain;
+use crate::ptr::EpochPtr;
+use crate::state::{Deferred, INACTIVE_EPOCH, NO_GROUP, ReaderSlot, SharedState};
+#[cfg(not(feature = "loom"))]
+use crate::sync::Weak;
+use crate::sync::{Arc, Cell, Ordering, fence};
 
 /// A reader thread's local epoch state.
 ///
@@ -11,6 +15,17 @@ use crate::sync::{Arc, AtomicUsize, Cell, Ordering};
 /// - Obtain a `PinGuard` that protects access to `EpochPtr` values.
 ///
 /// **Thread Safety**: `LocalEpoch` is not `Sync` and must be used by only one thread.
+/// It can be moved to another thread (it is `Send`), but it cannot be shared between threads:
+///
+/// ```compile_fail
+/// use swmr_epoch::EpochGcDomain;
+///
+/// let (_gc, domain) = EpochGcDomain::new();
+/// let local_epoch = domain.register_reader();
+/// std::thread::scope(|s| {
+///     s.spawn(|| drop(local_epoch.pin())); // error: `LocalEpoch` is not `Sync`
+/// });
+/// ```
 ///
 /// 读者线程的本地纪元状态。
 /// 每个读者线程应该通过 `EpochGcDomain::register_reader()` 创建恰好一个 `LocalEpoch`。
@@ -18,26 +33,84 @@ use crate::sync::{Arc, AtomicUsize, Cell, Ordering};
 /// `LocalEpoch` 用于：
 /// - 通过 `pin()` 将线程钉住到当前纪元。
 /// - 获取保护对 `EpochPtr` 值的访问的 `PinGuard`。
+///
 /// **线程安全性**：`LocalEpoch` 不是 `Sync` 的，必须仅由一个线程使用。
+/// 它可以被移动到另一个线程（它是 `Send` 的），但不能在线程之间共享。
 pub struct LocalEpoch {
-    slot: Arc<ReaderSlot>,
-    shared: Arc<SharedState>,
-    pin_count: Cell<usize>,
+    pub(crate) slot: Arc<ReaderSlot>,
+    pub(crate) shared: Arc<SharedState>,
+    pub(crate) pin_count: Cell<usize>,
+    /// Epoch of the previous outermost pin, or `INACTIVE_EPOCH` if unknown.
+    /// 上一次最外层 pin 的纪元，未知时为 `INACTIVE_EPOCH`。
+    pub(crate) cached_epoch: Cell<usize>,
+    /// Whether dropping this reader removes its slot immediately.
+    /// drop 此读者时是否立即移除其槽。
+    pub(crate) eager: bool,
 }
 
 impl LocalEpoch {
     pub(crate) fn new(shared: Arc<SharedState>) -> Self {
-        let slot = Arc::new(ReaderSlot {
-            active_epoch: AtomicUsize::new(INACTIVE_EPOCH),
-        });
+        Self::new_in_group(shared, NO_GROUP)
+    }
+
+    /// Register a reader whose slot is tagged with `group`.
+    /// 注册一个槽被标记为 `group` 的读者。
+    pub(crate) fn new_in_group(shared: Arc<SharedState>, group: usize) -> Self {
+        // Reuse a dead slot if recycling is enabled, otherwise allocate a fresh one
+        let recycled = shared
+            .recycled_slots
+            .as_ref()
+            .and_then(|recycled| recycled.lock().pop());
+        let slot = recycled.unwrap_or_else(|| Arc::new(ReaderSlot::new()));
+        // A recycled slot still carries its previous owner's state, including an epoch left
+        // published if that owner forgot a guard before it was dropped
+        slot.active_epoch.store(INACTIVE_EPOCH, Ordering::Release);
+        #[cfg(feature = "metrics")]
+        slot.pin_total.store(0, Ordering::Relaxed);
+        #[cfg(debug_assertions)]
+        slot.pin_started_at.store(0, Ordering::Relaxed);
+        slot.group.store(group, Ordering::Relaxed);
+        slot.generation.fetch_add(1, Ordering::Release);
 
         // Register the reader immediately in the shared readers list
+        shared.add_reader(Arc::clone(&slot));
+        if let Some(metrics) = &shared.metrics {
+            metrics.on_register();
+        }
+
+        LocalEpoch {
+            slot,
+            shared,
+            pin_count: Cell::new(0),
+            cached_epoch: Cell::new(INACTIVE_EPOCH),
+            eager: false,
+        }
+    }
+
+    /// Create a reader for the writer thread itself.
+    ///
+    /// The caller must be the thread that owns the `GcHandle`. Since that thread cannot be
+    /// collecting at the same time, the slot is pushed straight onto the readers list,
+    /// skipping the pending-registration stack and the recycled-slot list.
+    ///
+    /// 为写入者线程自身创建一个读者。
+    /// 调用者必须是持有 `GcHandle` 的线程。由于该线程不可能同时在进行回收，
+    /// 槽会被直接推入读者列表，跳过待注册栈和复用槽列表。
+    pub(crate) fn new_writer_local(shared: Arc<SharedState>) -> Self {
+        let slot = Arc::new(ReaderSlot::new());
+        slot.generation.fetch_add(1, Ordering::Release);
+        shared.reader_count.fetch_add(1, Ordering::Release);
         shared.readers.lock().push(Arc::clone(&slot));
+        if let Some(metrics) = &shared.metrics {
+            metrics.on_register();
+        }
 
         LocalEpoch {
             slot,
             shared,
             pin_count: Cell::new(0),
+            cached_epoch: Cell::new(INACTIVE_EPOCH),
+            eager: false,
         }
     }
 
@@ -60,6 +133,11 @@ impl LocalEpoch {
     /// While pinned, the thread is considered "active" at a particular epoch,
     /// and the garbage collector will not reclaim data from that epoch.
     ///
+    /// **Retries**: if the writer advances past the epoch this call is publishing, the pin is
+    /// retried, with a single spin hint in between. With the `backoff` feature, retries wait
+    /// with `crossbeam_utils::Backoff::snooze()` instead, which spins for exponentially longer
+    /// and then yields, easing contention when many readers pin against a busy writer.
+    ///
     /// 将此线程钉住到当前纪元。
     ///
     /// 返回一个 `PinGuard`，在其生命周期内保持线程被钉住。
@@ -76,29 +154,245 @@ impl LocalEpoch {
     /// ```
     ///
     /// 当被钉住时，线程被认为在特定纪元"活跃"，垃圾回收器不会回收该纪元的数据。
+    ///
+    /// **重试**：如果写入者推进越过了此调用正在发布的纪元，pin 会重试，两次尝试之间只有一次自旋提示。
+    /// 启用 `backoff` feature 时，重试改为通过 `crossbeam_utils::Backoff::snooze()` 等待，它会自旋指数增长的时间然后让出线程，
+    /// 在许多读者与繁忙的写入者竞争 pin 时缓解争用。
     #[inline]
     pub fn pin(&self) -> PinGuard<'_> {
         let pin_count = self.pin_count.get();
 
         if pin_count == 0 {
-            loop {
-                let current_epoch = self.shared.global_epoch.load(Ordering::Acquire);
+            #[cfg(feature = "backoff")]
+            let backoff = crossbeam_utils::Backoff::new();
+            while !self.try_enter() {
+                #[cfg(feature = "metrics")]
+                self.shared.pin_spins.fetch_add(1, Ordering::Relaxed);
+                #[cfg(feature = "backoff")]
+                backoff.snooze();
+                #[cfg(not(feature = "backoff"))]
+                std::hint::spin_loop();
+            }
+            self.record_pin();
+        }
+
+        self.pin_count.set(pin_count + 1);
+
+        PinGuard { reader: self }
+    }
+
+    /// Try to pin this thread to the current epoch, without spinning.
+    ///
+    /// `pin()` retries until the epoch it publishes is not behind the writer's minimum active
+    /// epoch. `try_pin()` makes a single attempt instead and returns `None` if that check fails,
+    /// leaving the slot inactive, so the caller can retry or back off as it sees fit.
+    ///
+    /// `None` can only occur when this reader is not already pinned and the writer advanced the
+    /// epoch (and raised the minimum active epoch past the value just read) between this
+    /// reader loading the global epoch and publishing it. A nested call on an already pinned
+    /// reader always succeeds.
+    ///
+    /// 尝试将此线程钉住到当前纪元，但不自旋。
+    ///
+    /// `pin()` 会不断重试，直到其发布的纪元不落后于写入者的最小活跃纪元。
+    /// `try_pin()` 只尝试一次，如果检查失败则返回 `None` 并保持槽为非活跃状态，
+    /// 由调用者决定重试还是退避。
+    ///
+    /// 只有当此读者尚未被钉住，并且写入者在此读者读取全局纪元与发布该纪元之间推进了纪元
+    /// （并将最小活跃纪元提升到刚读取的值之上）时，才会返回 `None`。
+    /// 对已经被钉住的读者的嵌套调用总是成功。
+    #[inline]
+    pub fn try_pin(&self) -> Option<PinGuard<'_>> {
+        let pin_count = self.pin_count.get();
+
+        if pin_count == 0 {
+            if !self.try_enter() {
                 self.slot
                     .active_epoch
-                    .store(current_epoch, Ordering::Release);
-
-                let min_active = self.shared.min_active_epoch.load(Ordering::Acquire);
-                if current_epoch >= min_active {
-                    break;
-                }
-                std::hint::spin_loop();
+                    .store(INACTIVE_EPOCH, Ordering::Release);
+                return None;
             }
+            self.record_pin();
         }
 
         self.pin_count.set(pin_count + 1);
 
+        Some(PinGuard { reader: self })
+    }
+
+    /// Publish an epoch in this reader's slot and check it is not stale.
+    ///
+    /// Uses the cached epoch of the previous pin if `reader_epoch_cache` is enabled and it is
+    /// still valid, and the current global epoch otherwise.
+    ///
+    /// 在此读者的槽中发布一个纪元，并检查它是否已过时。
+    /// 如果启用了 `reader_epoch_cache` 且上一次 pin 的缓存纪元仍然有效，则使用它，否则使用当前全局纪元。
+    #[inline]
+    fn try_enter(&self) -> bool {
+        // Each store of the slot is followed by a SeqCst fence, pairing with the one in
+        // `GcHandle::advance_and_scan`: either the writer's scan sees this slot, or every load
+        // after the fence, including the loads of `EpochPtr`s under the guard, sees the pointer
+        // swaps the writer made before that scan. Without it, the store could still sit in
+        // a store buffer while the scan misses it and reclaims what the loads then return.
+        //
+        // A cached epoch was read from the global epoch earlier, so it passes the same check
+        // as a fresh one; it merely may be older than necessary.
+        #[cfg(debug_assertions)]
+        self.stamp_pin_start();
+        let cached_epoch = self.cached_epoch.get();
+        if self.shared.reader_epoch_cache && cached_epoch != INACTIVE_EPOCH {
+            self.slot
+                .active_epoch
+                .store(cached_epoch, Ordering::Release);
+            fence(Ordering::SeqCst);
+            let min_active = self.shared.min_active_epoch.load(Ordering::Acquire);
+            if cached_epoch >= min_active {
+                return true;
+            }
+        }
+
+        let current_epoch = self.shared.global_epoch.load(Ordering::Acquire);
+        self.slot
+            .active_epoch
+            .store(current_epoch, Ordering::Release);
+        fence(Ordering::SeqCst);
+
+        let min_active = self.shared.min_active_epoch.load(Ordering::Acquire);
+        if current_epoch < min_active {
+            return false;
+        }
+        self.cached_epoch.set(current_epoch);
+        true
+    }
+
+    /// Bookkeeping for an outermost pin that succeeded.
+    /// 对成功的最外层 pin 进行记录。
+    #[inline]
+    fn record_pin(&self) {
+        if let Some(metrics) = &self.shared.metrics {
+            metrics.on_pin();
+        }
+
+        #[cfg(feature = "metrics")]
+        self.slot.pin_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record when the pin about to be published started, for the pin timeout check.
+    ///
+    /// Must run before the slot's epoch is stored with `Release`: the writer's scan loads the
+    /// epoch with `Acquire` before the timestamp, so it never pairs a freshly published epoch
+    /// with the timestamp of an earlier pin, or with `0` on a new or recycled slot.
+    ///
+    /// 记录即将发布的 pin 的开始时间，用于 pin 超时检查。
+    /// 必须在以 `Release` 存储槽的纪元之前运行：写入者的扫描在读取时间戳之前以 `Acquire` 加载纪元，
+    /// 因此它永远不会把刚发布的纪元与更早一次 pin 的时间戳（或新槽、复用槽上的 `0`）配对。
+    #[cfg(debug_assertions)]
+    #[inline]
+    fn stamp_pin_start(&self) {
+        if self.shared.pin_timeout.is_some() {
+            let now = self.shared.created_at.elapsed().as_nanos() as u64;
+            self.slot.pin_started_at.store(now, Ordering::Relaxed);
+        }
+    }
+
+    /// Pin this thread to a specific, possibly older, epoch.
+    ///
+    /// Only available in tests and with the `testing` feature. Simulates a reader that lags
+    /// behind the global epoch, so tests can hold the minimum active epoch at a chosen value
+    /// and check reclamation boundaries deterministically.
+    ///
+    /// # Panics
+    /// Panics if `epoch` is newer than the current global epoch, or if this reader is
+    /// already pinned.
+    ///
+    /// 将此线程钉住到一个指定的、可能更旧的纪元。
+    ///
+    /// 仅在测试中以及启用 `testing` feature 时可用。模拟一个落后于全局纪元的读者，
+    /// 以便测试可以将最小活跃纪元保持在选定的值上，并确定性地检查回收边界。
+    ///
+    /// # Panics
+    /// 如果 `epoch` 比当前全局纪元更新，或者此读者已经被钉住，则 panic。
+    #[cfg(any(test, feature = "testing"))]
+    pub fn pin_at(&self, epoch: usize) -> PinGuard<'_> {
+        let global_epoch = self.shared.global_epoch.load(Ordering::Acquire);
+        assert!(
+            epoch <= global_epoch,
+            "cannot pin at epoch {epoch}, which is newer than the global epoch {global_epoch}"
+        );
+        assert_eq!(
+            self.pin_count.get(),
+            0,
+            "cannot pin at an explicit epoch while already pinned"
+        );
+
+        #[cfg(debug_assertions)]
+        self.stamp_pin_start();
+        self.slot.active_epoch.store(epoch, Ordering::Release);
+        fence(Ordering::SeqCst);
+        #[cfg(feature = "metrics")]
+        self.slot.pin_total.fetch_add(1, Ordering::Relaxed);
+        self.pin_count.set(1);
+
         PinGuard { reader: self }
     }
+
+    /// Unpin this reader regardless of how many pins it holds.
+    ///
+    /// The counterpart of `PinGuard::leak()` for manual pin management across FFI, where a
+    /// foreign caller pins and unpins through separate calls and cannot rely on `Drop`. Resets
+    /// the pin count to zero and marks the slot inactive, so the writer may reclaim anything
+    /// retired from now on.
+    ///
+    /// # Safety
+    /// - No `PinGuard` of this reader may still be alive: its `Drop` would find the pin count
+    ///   already at zero. Every guard must have been consumed with `leak()`.
+    /// - No reference loaded under any of those pins may be used afterwards, since the values
+    ///   it points to may be reclaimed at any time.
+    ///
+    /// 无论持有多少个 pin，都解除此读者的钉住。
+    ///
+    /// 这是 `PinGuard::leak()` 的对应操作，用于跨 FFI 的手动 pin 管理：外部调用者通过不同的调用进行 pin 和解除 pin，
+    /// 无法依赖 `Drop`。将 pin 计数重置为零并将槽标记为非活跃，因此写入者可以回收从现在起退休的任何东西。
+    ///
+    /// # Safety
+    /// - 此读者不得仍有存活的 `PinGuard`：其 `Drop` 会发现 pin 计数已经为零。每个守卫都必须已通过 `leak()` 消耗。
+    /// - 之后不得再使用在这些 pin 下加载的任何引用，因为它们指向的值随时可能被回收。
+    #[inline]
+    pub unsafe fn force_unpin(&self) {
+        self.slot
+            .active_epoch
+            .store(INACTIVE_EPOCH, Ordering::Release);
+        self.pin_count.set(0);
+    }
+
+    /// Get a token that observes this reader without keeping it registered.
+    ///
+    /// The token can be sent to another thread, e.g. a supervisor that monitors readers. See
+    /// `ReaderToken`.
+    ///
+    /// 获取一个观察此读者但不保持其注册状态的令牌。
+    /// 令牌可以发送到另一个线程，例如监控读者的监督线程。参见 `ReaderToken`。
+    #[cfg(not(feature = "loom"))]
+    #[inline]
+    pub fn token(&self) -> ReaderToken {
+        ReaderToken {
+            slot: Arc::downgrade(&self.slot),
+            generation: self.slot.generation.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for LocalEpoch {
+    /// Mark the reader as dead for its `ReaderToken`s, and remove the slot right away for
+    /// readers registered with `register_reader_eager()`.
+    /// 将读者对其 `ReaderToken` 标记为已死亡，并对于通过 `register_reader_eager()` 注册的读者立即移除其槽。
+    #[inline]
+    fn drop(&mut self) {
+        self.slot.generation.fetch_add(1, Ordering::Release);
+        if self.eager {
+            self.shared.remove_reader(&self.slot);
+        }
+    }
 }
 
 /// A guard that keeps the current thread pinned to an epoch.
@@ -117,6 +411,20 @@ impl LocalEpoch {
 /// `EpochPtr` values. Readers must always hold a valid `PinGuard` when accessing
 /// shared data through `EpochPtr::load()`.
 ///
+/// **Thread Confinement**: a guard (and every clone of it) only counts pins of the thread that
+/// owns its `LocalEpoch`, so it can neither be sent to nor shared with another thread:
+///
+/// ```compile_fail
+/// use swmr_epoch::EpochGcDomain;
+///
+/// let (_gc, domain) = EpochGcDomain::new();
+/// let local_epoch = domain.register_reader();
+/// let guard = local_epoch.pin();
+/// std::thread::scope(|s| {
+///     s.spawn(move || drop(guard.clone())); // error: `PinGuard` is not `Send`
+/// });
+/// ```
+///
 /// 一个保持当前线程被钉住到一个纪元的守卫。
 /// `PinGuard` 通过调用 `LocalEpoch::pin()` 获得。
 /// 它是 `!Send` 和 `!Sync` 的，因为它引用了一个 `!Sync` 的 `LocalEpoch`。
@@ -127,11 +435,152 @@ impl LocalEpoch {
 /// 线程保持被钉住直到所有克隆的守卫被 drop。
 /// **安全性**：`PinGuard` 是确保对 `EpochPtr` 值安全并发访问的机制。
 /// 读者在通过 `EpochPtr::load()` 访问共享数据时必须始终持有有效的 `PinGuard`。
+/// **线程限定**：守卫（及其所有克隆）只记录拥有其 `LocalEpoch` 的线程的 pin，
+/// 因此既不能被发送到也不能被共享给其他线程。
 #[must_use]
 pub struct PinGuard<'a> {
+    pub(crate) reader: &'a LocalEpoch,
+}
+
+impl<'a> PinGuard<'a> {
+    /// Get the epoch this guard's reader is pinned at.
+    ///
+    /// Nested guards of the same reader share the epoch of the outermost pin.
+    ///
+    /// 获取此守卫的读者被钉住的纪元。
+    /// 同一读者的嵌套守卫共享最外层 pin 的纪元。
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.reader.slot.active_epoch.load(Ordering::Relaxed)
+    }
+
+    /// Defer a cleanup closure until no reader can observe what it cleans up.
+    ///
+    /// Readers do not own a `GcHandle`, so the closure is queued in the domain and picked up by
+    /// the writer's next collection, which retires it like any other garbage. It runs on the
+    /// writer thread once every reader pinned at that point has unpinned, typically to free a
+    /// node this reader logically removed. Closures still queued when the domain is dropped
+    /// run at that time.
+    ///
+    /// 将一个清理闭包延迟到没有读者能观察到其所清理的内容为止。
+    /// 读者不持有 `GcHandle`，因此闭包会被放入域的队列中，由写入者的下一次回收取走，并像其他垃圾一样被退休。
+    /// 一旦当时被钉住的所有读者都解除钉住，它就会在写入者线程上运行，通常用于释放此读者在逻辑上移除的节点。
+    /// 域被 drop 时仍在队列中的闭包会在那时运行。
+    #[inline]
+    pub fn defer(&self, f: impl FnOnce() + Send + 'static) {
+        self.reader.shared.deferred.lock().push(Deferred::new(f));
+    }
+
+    /// Read a value once and release this pin before returning.
+    ///
+    /// Loads `ptr`, runs `f` on the value while still pinned, then drops this guard and returns
+    /// the closure's result. Since the guard is consumed, the value cannot escape the closure,
+    /// so downstream processing of `R` does not hold up garbage collection.
+    ///
+    /// If other guards from the same `LocalEpoch` are still alive, the thread stays pinned
+    /// until they are dropped as well.
+    ///
+    /// 读取一次值，并在返回前释放此 pin。
+    ///
+    /// 加载 `ptr`，在仍被钉住时对值运行 `f`，然后 drop 此守卫并返回闭包的结果。
+    /// 由于守卫被消耗，值无法逃逸出闭包，因此对 `R` 的后续处理不会阻碍垃圾回收。
+    ///
+    /// 如果同一 `LocalEpoch` 的其他守卫仍然存活，线程会保持被钉住直到它们也被 drop。
+    #[inline]
+    pub fn snapshot_then_release<T: 'static, R>(
+        self,
+        ptr: &EpochPtr<T>,
+        f: impl FnOnce(&T) -> R,
+    ) -> R {
+        let result = f(ptr.load(&self));
+        drop(self);
+        result
+    }
+
+    /// Consume this guard without unpinning.
+    ///
+    /// Like `std::mem::forget`, the pin count is not decremented, so the reader stays pinned
+    /// after the guard is gone. Intended for FFI, where a foreign caller cannot hold a Rust
+    /// guard across calls: pin and `leak()` in one call, then release the pin in a later one
+    /// with `LocalEpoch::force_unpin()`.
+    ///
+    /// # Safety
+    /// The pin must eventually be released with `LocalEpoch::force_unpin()`. Until then the
+    /// reader holds back reclamation for the whole domain, and any reference loaded under
+    /// this pin is only valid until that call.
+    ///
+    /// 消耗此守卫但不解除钉住。
+    ///
+    /// 与 `std::mem::forget` 一样，pin 计数不会被减少，因此守卫消失后读者仍保持被钉住。
+    /// 适用于 FFI：外部调用者无法跨调用持有 Rust 守卫，可以在一次调用中 pin 并 `leak()`，
+    /// 然后在之后的调用中通过 `LocalEpoch::force_unpin()` 释放该 pin。
+    ///
+    /// # Safety
+    /// 该 pin 最终必须通过 `LocalEpoch::force_unpin()` 释放。在此之前，读者会阻碍整个域的回收，
+    /// 并且在此 pin 下加载的任何引用只在该调用之前有效。
+    #[inline]
+    pub unsafe fn leak(self) {
+        std::mem::forget(self);
+    }
+
+    /// Release this pin for a stretch of work that does not read shared data.
+    ///
+    /// Consumes the guard and returns an `UnpinnedToken`, which cannot be used to `load()`, so no
+    /// reference obtained under this pin can be carried across the gap. While only the token is
+    /// held the reader gives up all GC protection and the writer may reclaim anything. Call
+    /// `UnpinnedToken::upgrade()` to pin again, at the then-current epoch, before the next read.
+    ///
+    /// If other guards from the same `LocalEpoch` are still alive, the thread stays pinned
+    /// until they are dropped as well.
+    ///
+    /// 为一段不读取共享数据的工作释放此 pin。
+    ///
+    /// 消耗守卫并返回一个 `UnpinnedToken`，它不能用于 `load()`，因此在此 pin 下获得的引用都无法跨越这段间隙。
+    /// 只持有令牌期间，读者放弃所有 GC 保护，写入者可以回收任何内容。在下一次读取之前调用
+    /// `UnpinnedToken::upgrade()` 以再次钉住（钉住到届时的当前纪元）。
+    ///
+    /// 如果同一 `LocalEpoch` 的其他守卫仍然存活，线程会保持被钉住直到它们也被 drop。
+    #[inline]
+    pub fn downgrade(self) -> UnpinnedToken<'a> {
+        let reader = self.reader;
+        drop(self);
+        UnpinnedToken { reader }
+    }
+}
+
+/// A reader that released its pin with `PinGuard::downgrade()` and can pin again.
+///
+/// Holds no pin and offers no access to shared data; `upgrade()` turns it back into a
+/// `PinGuard`. Like `PinGuard`, it is confined to the thread that owns its `LocalEpoch`.
+///
+/// ```compile_fail
+/// use swmr_epoch::{EpochGcDomain, EpochPtr};
+///
+/// let (_gc, domain) = EpochGcDomain::new();
+/// let local_epoch = domain.register_reader();
+/// let ptr = EpochPtr::new(1);
+/// let token = local_epoch.pin().downgrade();
+/// ptr.load(&token); // error: expected `&PinGuard`, found `&UnpinnedToken`
+/// ```
+///
+/// 一个通过 `PinGuard::downgrade()` 释放了 pin、并可以再次钉住的读者。
+/// 不持有 pin，也不提供对共享数据的访问；`upgrade()` 会将其重新转换为 `PinGuard`。
+/// 与 `PinGuard` 一样，它被限定在拥有其 `LocalEpoch` 的线程上。
+#[must_use]
+pub struct UnpinnedToken<'a> {
     reader: &'a LocalEpoch,
 }
 
+impl<'a> UnpinnedToken<'a> {
+    /// Pin the reader again, at the current global epoch.
+    ///
+    /// 再次钉住读者，钉住到当前全局纪元。
+    #[inline]
+    pub fn upgrade(self) -> PinGuard<'a> {
+        self.reader.pin()
+    }
+}
+
 impl<'a> Clone for PinGuard<'a> {
     /// Clone this guard to create a nested pin.
     ///
@@ -149,7 +598,9 @@ impl<'a> Clone for PinGuard<'a> {
         assert!(
             pin_count > 0,
             "BUG: Cloning a PinGuard in an unpinned state (pin_count = 0). \
-             This indicates incorrect API usage or a library bug."
+             This indicates incorrect API usage or a library bug. Note that a PinGuard and \
+             its clones are confined to the thread owning the LocalEpoch; the pin count is \
+             per-thread state and must never be manipulated from another thread."
         );
 
         self.reader.pin_count.set(pin_count + 1);
@@ -181,3 +632,132 @@ impl<'a> Drop for PinGuard<'a> {
         self.reader.pin_count.set(pin_count - 1);
     }
 }
+
+/// A non-protecting reference to a reader, for liveness checks from other threads.
+///
+/// Obtained with `LocalEpoch::token()`. It holds only a weak reference to the reader's slot,
+/// so it neither keeps the slot registered nor delays its cleanup, and it can never pin. It
+/// is `Send`, `Sync` and cheap to clone.
+///
+/// A token stays tied to the reader it came from: once that `LocalEpoch` is dropped the token
+/// reports the reader as dead, even if its slot is later recycled for a new reader.
+///
+/// 一个不提供保护的读者引用，用于从其他线程检查读者是否存活。
+/// 通过 `LocalEpoch::token()` 获取。它只持有读者槽的弱引用，因此既不会保持槽的注册状态，也不会延迟其清理，
+/// 并且永远无法钉住。它是 `Send`、`Sync` 的，克隆开销很小。
+/// 令牌始终绑定到其来源的读者：一旦该 `LocalEpoch` 被 drop，即使其槽之后被复用给新读者，令牌也会报告读者已死亡。
+#[cfg(not(feature = "loom"))]
+#[derive(Debug, Clone)]
+pub struct ReaderToken {
+    slot: Weak<ReaderSlot>,
+    generation: usize,
+}
+
+#[cfg(not(feature = "loom"))]
+impl ReaderToken {
+    /// Whether the `LocalEpoch` this token came from has not been dropped yet.
+    /// 此令牌来源的 `LocalEpoch` 是否尚未被 drop。
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.slot.upgrade().is_some_and(|slot| self.owns(&slot))
+    }
+
+    /// The epoch the reader is currently pinned at, or `None` if it is not pinned or dead.
+    ///
+    /// The value is a snapshot: the reader may pin or unpin right after it is read.
+    ///
+    /// 读者当前被钉住的纪元；如果未被钉住或已死亡，则返回 `None`。
+    /// 该值只是一个快照：读者可能在读取之后立即钉住或解除钉住。
+    #[inline]
+    pub fn pinned_epoch(&self) -> Option<usize> {
+        let slot = self.slot.upgrade()?;
+        let epoch = slot.active_epoch.load(Ordering::Acquire);
+        (epoch != INACTIVE_EPOCH && self.owns(&slot)).then_some(epoch)
+    }
+
+    #[inline]
+    fn owns(&self, slot: &ReaderSlot) -> bool {
+        slot.generation.load(Ordering::Acquire) == self.generation
+    }
+}
+
+/// A named cohort of readers that the writer can wait for as a whole.
+///
+/// Readers join a group when they are registered with
+/// `EpochGcDomain::register_reader_in_group()`. `GcHandle::quiesce_group()` then waits until
+/// every member has unpinned at least once since the call, ignoring all other readers, which
+/// makes a group a checkpoint barrier for phased algorithms. A group is a lightweight id: it
+/// can be cloned freely and used with any domain.
+///
+/// 一个写入者可以整体等待的具名读者群组。
+/// 读者在通过 `EpochGcDomain::register_reader_in_group()` 注册时加入群组。`GcHandle::quiesce_group()` 会等待直到
+/// 每个成员自调用以来都至少解除钉住过一次，并忽略所有其他读者，因此群组可以作为分阶段算法的检查点屏障。
+/// 群组只是一个轻量的 id：可以自由克隆并用于任何域。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReaderGroup {
+    pub(crate) id: usize,
+}
+
+impl ReaderGroup {
+    /// Create a new group, distinct from every other group.
+    /// 创建一个与其他所有群组都不同的新群组。
+    #[inline]
+    pub fn new() -> Self {
+        // Only needs to hand out unique ids, so it is a plain std atomic even under loom.
+        static NEXT_GROUP: std::sync::atomic::AtomicUsize =
+            std::sync::atomic::AtomicUsize::new(NO_GROUP + 1);
+        Self {
+            id: NEXT_GROUP.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for ReaderGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A group of readers that are pinned and unpinned together.
+///
+/// Useful for test harnesses, and for a thread that reads several structures guarded by
+/// separate `LocalEpoch`s and wants to pin all of them at once.
+///
+/// Like `LocalEpoch`, a `ReaderSet` must be used by only one thread.
+///
+/// 一组被一起钉住和释放的读者。
+/// 适用于测试工具，以及读取多个由不同 `LocalEpoch` 保护的结构并希望一次钉住所有读者的线程。
+/// 与 `LocalEpoch` 一样，`ReaderSet` 必须仅由一个线程使用。
+pub struct ReaderSet {
+    readers: Vec<LocalEpoch>,
+}
+
+impl ReaderSet {
+    /// Register `count` new readers in `domain`.
+    /// 在 `domain` 中注册 `count` 个新读者。
+    pub fn register(domain: &EpochGcDomain, count: usize) -> Self {
+        Self {
+            readers: (0..count).map(|_| domain.register_reader()).collect(),
+        }
+    }
+
+    /// Pin every reader in the set, returning one guard per reader in registration order.
+    /// 钉住集合中的每个读者，按注册顺序为每个读者返回一个守卫。
+    #[inline]
+    pub fn pin_all(&self) -> Vec<PinGuard<'_>> {
+        self.readers.iter().map(LocalEpoch::pin).collect()
+    }
+
+    /// The readers in this set, in registration order.
+    /// 此集合中的读者，按注册顺序排列。
+    #[inline]
+    pub fn readers(&self) -> &[LocalEpoch] {
+        &self.readers
+    }
+}
+
+impl From<Vec<LocalEpoch>> for ReaderSet {
+    fn from(readers: Vec<LocalEpoch>) -> Self {
+        Self { readers }
+    }
+}
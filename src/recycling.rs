@@ -0,0 +1,194 @@
+use crate::garbage::GcHandle;
+use crate::ptr::{EpochPtr, untag};
+use crate::reader::PinGuard;
+use crate::sync::{Arc, Mutex, Ordering};
+use std::boxed::Box;
+use std::mem::ManuallyDrop;
+use std::vec::Vec;
+
+/// Default number of reclaimed allocations kept for reuse by a `RecyclingEpochPtr`.
+/// `RecyclingEpochPtr` 默认保留以供复用的已回收分配数量。
+pub(crate) const DEFAULT_POOL_CAPACITY: usize = 8;
+
+/// A heap node holding one value of a `RecyclingEpochPtr`.
+///
+/// While the node is published or retired, `pool` is set and `value` is initialized. Once the
+/// node is reclaimed, the value is dropped in place and the node is parked in the pool with
+/// `pool` cleared, so parked nodes do not keep their pool alive.
+///
+/// 持有 `RecyclingEpochPtr` 的一个值的堆节点。
+/// 节点被发布或退休期间，`pool` 被设置且 `value` 已初始化。节点被回收后，值被原地 drop，
+/// 节点被放入池中并清除 `pool`，因此池中的节点不会使其池保持存活。
+struct Node<T> {
+    pool: Option<Arc<Pool<T>>>,
+    value: ManuallyDrop<T>,
+}
+
+/// A parked node whose value has already been dropped.
+/// 一个值已被 drop 的闲置节点。
+struct FreeNode<T>(*mut Node<T>);
+
+// Safety: A parked node holds no value and is owned exclusively by the pool
+unsafe impl<T> Send for FreeNode<T> {}
+
+/// Free list of reclaimed nodes.
+/// 已回收节点的空闲列表。
+struct Pool<T> {
+    free: Mutex<Vec<FreeNode<T>>>,
+    capacity: usize,
+}
+
+impl<T> Drop for Pool<T> {
+    fn drop(&mut self) {
+        for node in self.free.lock().drain(..) {
+            // The value was already dropped and `pool` is `None`
+            drop(unsafe { Box::from_raw(node.0) });
+        }
+    }
+}
+
+/// Destructor for retired nodes: drop the value, then park the allocation for reuse.
+/// 已退休节点的析构函数：drop 值，然后将分配放入池中以供复用。
+unsafe fn recycle_node<T>(ptr: *mut ()) {
+    let node = ptr as *mut Node<T>;
+    unsafe {
+        ManuallyDrop::drop(&mut (*node).value);
+        let pool = (*node).pool.take().expect("retired node without a pool");
+
+        let mut free = pool.free.lock();
+        if free.len() < pool.capacity {
+            free.push(FreeNode(node));
+        } else {
+            drop(free);
+            drop(Box::from_raw(node));
+        }
+    }
+}
+
+/// An epoch-protected pointer that reuses the allocations of reclaimed values.
+///
+/// `EpochPtr::store()` allocates a new box for every value and frees the old one when it is
+/// reclaimed. `RecyclingEpochPtr` instead parks reclaimed allocations in a small free list and
+/// reuses them for later stores, so a writer in steady state, storing and collecting
+/// regularly, stops allocating altogether. Readers use it exactly like an `EpochPtr`.
+///
+/// # Example
+/// ```
+/// use swmr_epoch::{EpochGcDomain, RecyclingEpochPtr};
+///
+/// let (mut gc, domain) = EpochGcDomain::new();
+/// let ptr = RecyclingEpochPtr::new([0u64; 16]);
+///
+/// for i in 0..100 {
+///     ptr.store([i; 16], &mut gc);
+///     gc.collect(); // reclaimed allocations are reused by the next store
+/// }
+///
+/// let local_epoch = domain.register_reader();
+/// assert_eq!(ptr.load(&local_epoch.pin())[0], 99);
+/// ```
+///
+/// 一个复用已回收值的分配的受 epoch 保护的指针。
+///
+/// `EpochPtr::store()` 为每个值分配一个新的 box，并在旧值被回收时释放它。
+/// `RecyclingEpochPtr` 则将已回收的分配放入一个小型空闲列表，并在之后的 store 中复用它们，
+/// 因此处于稳定状态、定期 store 并回收的写入者将完全停止分配。读者的使用方式与 `EpochPtr` 完全相同。
+pub struct RecyclingEpochPtr<T: 'static> {
+    ptr: EpochPtr<Node<T>>,
+    pool: Arc<Pool<T>>,
+}
+
+impl<T: 'static> RecyclingEpochPtr<T> {
+    /// Create a new pointer keeping up to a default number of allocations for reuse.
+    /// 创建一个新指针，保留默认数量的分配以供复用。
+    #[inline]
+    pub fn new(data: T) -> Self {
+        Self::with_pool_capacity(data, DEFAULT_POOL_CAPACITY)
+    }
+
+    /// Create a new pointer keeping up to `capacity` reclaimed allocations for reuse.
+    /// 创建一个新指针，保留最多 `capacity` 个已回收的分配以供复用。
+    pub fn with_pool_capacity(data: T, capacity: usize) -> Self {
+        let pool = Arc::new(Pool {
+            free: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+        });
+        Self {
+            ptr: EpochPtr::new(Node {
+                pool: Some(pool.clone()),
+                value: ManuallyDrop::new(data),
+            }),
+            pool,
+        }
+    }
+
+    /// Reader load: safely read the current value.
+    /// 读取者 load：安全地读取当前值。
+    #[inline]
+    pub fn load<'guard>(&self, guard: &'guard PinGuard) -> &'guard T {
+        &self.ptr.load(guard).value
+    }
+
+    /// Writer store: replace the value, reusing a reclaimed allocation if one is available.
+    /// 写入者 store：替换值，如果有可用的已回收分配则复用它。
+    ///
+    /// The old value is dropped by whichever thread calls `collect()`, so `T` must be `Send`:
+    /// 旧值由调用 `collect()` 的线程 drop，因此 `T` 必须是 `Send`：
+    ///
+    /// ```compile_fail
+    /// use std::rc::Rc;
+    /// use swmr_epoch::{EpochGcDomain, RecyclingEpochPtr};
+    ///
+    /// let (mut gc, _domain) = EpochGcDomain::new();
+    /// let ptr = RecyclingEpochPtr::new(Rc::new(1));
+    /// ptr.store(Rc::new(2), &mut gc); // error: `Rc<i32>` is not `Send`
+    /// std::thread::spawn(move || gc.collect()).join().unwrap();
+    /// ```
+    pub fn store(&self, data: T, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        let recycled = self.pool.free.lock().pop();
+        let node = match recycled {
+            Some(FreeNode(node)) => {
+                unsafe {
+                    node.write(Node {
+                        pool: Some(self.pool.clone()),
+                        value: ManuallyDrop::new(data),
+                    });
+                }
+                node
+            }
+            None => Box::into_raw(Box::new(Node {
+                pool: Some(self.pool.clone()),
+                value: ManuallyDrop::new(data),
+            })),
+        };
+
+        let old = self.ptr.replace(node, Ordering::Release);
+        unsafe {
+            gc.retire_raw(old as *mut (), recycle_node::<T>);
+        }
+    }
+
+    /// Number of reclaimed allocations currently available for reuse.
+    /// 当前可供复用的已回收分配数量。
+    #[inline]
+    pub fn pooled(&self) -> usize {
+        self.pool.free.lock().len()
+    }
+}
+
+impl<T: 'static> Drop for RecyclingEpochPtr<T> {
+    fn drop(&mut self) {
+        // Take the current node so that the inner `EpochPtr` does not free it without dropping
+        // the value first.
+        let node = untag(self.ptr.ptr.swap(std::ptr::null_mut(), Ordering::Relaxed));
+        if !node.is_null() {
+            unsafe {
+                ManuallyDrop::drop(&mut (*node).value);
+                drop(Box::from_raw(node));
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::state::ReaderSlot;
+use crate::sync::{Arc, AtomicPtr, Ordering};
+use std::boxed::Box;
+use std::vec::Vec;
+
+/// Storage layout of the readers list.
+///
+/// 读者列表的存储布局。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReaderStorage {
+    /// Slots are kept densely in a `Vec`.
+    ///
+    /// Scans touch no holes, but removing a slot moves another one to its index, and cleanup
+    /// rewrites the whole list.
+    ///
+    /// 槽紧凑地保存在 `Vec` 中。
+    /// 扫描不会遇到空洞，但移除一个槽会把另一个槽移到它的索引上，且清理会重写整个列表。
+    #[default]
+    Vec,
+    /// Slots are kept in a slab with a free list of vacant indices.
+    ///
+    /// Every slot keeps its index until it is removed, and each removal is O(1): an eager
+    /// reader finds its own entry by the key recorded in its slot, without searching the list.
+    /// Vacant indices are reused by later registrations.
+    ///
+    /// 槽保存在带有空闲索引列表的 slab 中。
+    /// 每个槽在被移除之前保持其索引不变，每次移除都是 O(1) 的：急切读者通过记录在其槽中的键找到自己的条目，
+    /// 无需搜索列表。空闲索引会被之后的注册复用。
+    Slab,
+}
+
+/// Implementation used to register new readers.
+///
+/// 用于注册新读者的实现。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadersBackend {
+    /// Readers are pushed directly into the mutex-protected readers list.
+    ///
+    /// Registration takes the same lock the writer holds while scanning, so it can wait for a
+    /// running collection, but `collect()` only has to scan the list.
+    ///
+    /// 读者被直接推入受 Mutex 保护的读者列表。
+    /// 注册会获取写入者扫描时持有的同一把锁，因此可能需要等待正在运行的回收，
+    /// 但 `collect()` 只需扫描该列表。
+    #[default]
+    Mutex,
+    /// Readers are pushed onto a lock-free stack, which the writer moves into the readers list
+    /// at the start of its next scan.
+    ///
+    /// Registration never blocks, at the cost of a little extra work in `collect()`.
+    ///
+    /// 读者被推入一个无锁栈，写入者在下一次扫描开始时将其移入读者列表。
+    /// 注册从不阻塞，代价是 `collect()` 中多一点额外工作。
+    LockFree,
+}
+
+/// A node of the lock-free registration stack.
+/// 无锁注册栈的节点。
+struct PendingNode {
+    slot: Arc<ReaderSlot>,
+    next: *mut PendingNode,
+}
+
+/// Lock-free stack of freshly registered reader slots not yet merged into the readers list.
+///
+/// 尚未合并到读者列表中的新注册读者槽的无锁栈。
+#[derive(Debug)]
+pub(crate) struct PendingSlots {
+    head: AtomicPtr<PendingNode>,
+}
+
+impl PendingSlots {
+    pub(crate) fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(std::ptr::null_mut()),
+        }
+    }
+
+    /// Push a newly registered slot without taking any lock.
+    /// 在不获取任何锁的情况下推入一个新注册的槽。
+    pub(crate) fn push(&self, slot: Arc<ReaderSlot>) {
+        let node = Box::into_raw(Box::new(PendingNode {
+            slot,
+            next: std::ptr::null_mut(),
+        }));
+
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Take every pending slot and add it to `readers`.
+    /// 取出所有待处理的槽并添加到 `readers` 中。
+    pub(crate) fn drain_into(&self, readers: &mut ReaderList) {
+        let mut node = self.head.swap(std::ptr::null_mut(), Ordering::AcqRel);
+        while !node.is_null() {
+            let boxed = unsafe { Box::from_raw(node) };
+            node = boxed.next;
+            readers.push(boxed.slot);
+        }
+    }
+}
+
+impl Drop for PendingSlots {
+    fn drop(&mut self) {
+        self.drain_into(&mut ReaderList::new(ReaderStorage::Vec));
+    }
+}
+
+/// The registered reader slots, stored as configured by `ReaderStorage`.
+///
+/// With `ReaderStorage::Vec` every entry is occupied. With `ReaderStorage::Slab` removed
+/// entries become holes whose indices are kept in `vacant` for reuse.
+///
+/// 已注册的读者槽，按 `ReaderStorage` 的配置存储。
+/// 使用 `ReaderStorage::Vec` 时每个条目都被占用。使用 `ReaderStorage::Slab` 时，被移除的条目成为空洞，
+/// 其索引保存在 `vacant` 中以供复用。
+#[derive(Debug)]
+pub(crate) struct ReaderList {
+    entries: Vec<Option<Arc<ReaderSlot>>>,
+    vacant: Vec<usize>,
+    len: usize,
+    storage: ReaderStorage,
+}
+
+impl ReaderList {
+    pub(crate) fn new(storage: ReaderStorage) -> Self {
+        Self {
+            entries: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
+            storage,
+        }
+    }
+
+    /// Number of registered slots.
+    /// 已注册槽的数量。
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of indices in use, including holes. Scans walk `0..span()`.
+    /// 使用中的索引数量，包括空洞。扫描遍历 `0..span()`。
+    #[inline]
+    pub(crate) fn span(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether removing a slot can move another slot to a different index.
+    /// 移除一个槽是否会把另一个槽移到不同的索引上。
+    #[inline]
+    pub(crate) fn moves_on_removal(&self) -> bool {
+        self.storage == ReaderStorage::Vec
+    }
+
+    /// Iterate over all registered slots.
+    /// 遍历所有已注册的槽。
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Arc<ReaderSlot>> {
+        self.entries.iter().flatten()
+    }
+
+    /// Iterate over the registered slots whose index is in `start..end`, with their index.
+    /// 遍历索引位于 `start..end` 中的已注册槽及其索引。
+    #[inline]
+    pub(crate) fn range(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl Iterator<Item = (usize, &Arc<ReaderSlot>)> {
+        self.entries[start..end]
+            .iter()
+            .enumerate()
+            .filter_map(move |(offset, entry)| Some((start + offset, entry.as_ref()?)))
+    }
+
+    /// Get the slot at `index`, if that index is occupied.
+    /// 获取位于 `index` 的槽（如果该索引被占用）。
+    #[inline]
+    pub(crate) fn get(&self, index: usize) -> Option<&Arc<ReaderSlot>> {
+        self.entries.get(index)?.as_ref()
+    }
+
+    /// Add a slot, reusing a vacant index with the slab storage.
+    /// 添加一个槽，使用 slab 存储时复用空闲索引。
+    pub(crate) fn push(&mut self, slot: Arc<ReaderSlot>) {
+        self.len += 1;
+        match self.vacant.pop() {
+            Some(key) => {
+                slot.key.store(key, Ordering::Relaxed);
+                self.entries[key] = Some(slot);
+            }
+            None => {
+                slot.key.store(self.entries.len(), Ordering::Relaxed);
+                self.entries.push(Some(slot));
+            }
+        }
+    }
+
+    /// Remove `slot` if it is registered, returning the list's reference to it.
+    ///
+    /// O(1) with the slab storage, which looks the entry up by the slot's key.
+    ///
+    /// 如果 `slot` 已注册则将其移除，并返回列表对它的引用。
+    /// 使用 slab 存储时为 O(1)，通过槽的键查找条目。
+    pub(crate) fn remove(&mut self, slot: &Arc<ReaderSlot>) -> Option<Arc<ReaderSlot>> {
+        let is_slot = |entry: &Option<Arc<ReaderSlot>>| {
+            entry.as_ref().is_some_and(|other| Arc::ptr_eq(other, slot))
+        };
+        let removed = match self.storage {
+            ReaderStorage::Vec => {
+                let index = self.entries.iter().position(is_slot)?;
+                self.entries.swap_remove(index)
+            }
+            ReaderStorage::Slab => {
+                let key = slot.key.load(Ordering::Relaxed);
+                if !self.entries.get(key).is_some_and(is_slot) {
+                    return None;
+                }
+                self.vacant.push(key);
+                self.entries[key].take()
+            }
+        };
+        self.len -= 1;
+        removed
+    }
+
+    /// Remove every slot for which `dead` returns true, passing each one to `on_removed`.
+    /// 移除所有使 `dead` 返回 true 的槽，并将每个槽传给 `on_removed`。
+    pub(crate) fn remove_if(
+        &mut self,
+        mut dead: impl FnMut(&Arc<ReaderSlot>) -> bool,
+        mut on_removed: impl FnMut(Arc<ReaderSlot>),
+    ) {
+        match self.storage {
+            ReaderStorage::Vec => {
+                let mut i = 0;
+                while i < self.entries.len() {
+                    if self.entries[i].as_ref().is_some_and(&mut dead) {
+                        if let Some(slot) = self.entries.swap_remove(i) {
+                            on_removed(slot);
+                        }
+                        self.len -= 1;
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            ReaderStorage::Slab => {
+                for (key, entry) in self.entries.iter_mut().enumerate() {
+                    if entry.as_ref().is_some_and(&mut dead)
+                        && let Some(slot) = entry.take()
+                    {
+                        self.vacant.push(key);
+                        self.len -= 1;
+                        on_removed(slot);
+                    }
+                }
+            }
+        }
+    }
+}
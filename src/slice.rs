@@ -0,0 +1,147 @@
+use crate::garbage::GcHandle;
+use crate::ptr::{EpochPtr, Storable};
+use crate::reader::PinGuard;
+use crate::sync::{AtomicPtr, Ordering};
+use std::alloc::{self, Layout};
+use std::boxed::Box;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+/// Header of a slice allocation stored behind an `EpochPtr<[T]>`.
+///
+/// The elements follow the header in the same allocation, so a reader reaches them with a
+/// single pointer load instead of going through a `Box<[T]>` or `Vec<T>` indirection.
+///
+/// 存储在 `EpochPtr<[T]>` 后面的切片分配的头部。
+/// 元素紧跟在头部之后位于同一分配中，因此读者只需一次指针加载即可访问它们，
+/// 而不需要经过 `Box<[T]>` 或 `Vec<T>` 的间接层。
+#[repr(C)]
+pub struct SliceHeader<T> {
+    len: usize,
+    elements: [T; 0],
+}
+
+impl<T> SliceHeader<T> {
+    /// Layout of a header followed by `len` elements, and the offset of the first element.
+    /// 头部后跟 `len` 个元素的布局，以及第一个元素的偏移量。
+    #[inline]
+    fn layout(len: usize) -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<SliceHeader<T>>()
+            .extend(Layout::array::<T>(len).expect("slice too large"))
+            .expect("slice too large");
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Pointer to the first element of the allocation starting at `header`.
+    /// 指向从 `header` 开始的分配中第一个元素的指针。
+    #[inline(always)]
+    unsafe fn elements(header: *mut Self) -> *mut T {
+        let len = unsafe { (*header).len };
+        let (_, offset) = Self::layout(len);
+        unsafe { (header as *mut u8).add(offset) as *mut T }
+    }
+}
+
+impl<T: 'static> Storable for [T] {
+    type Thin = SliceHeader<T>;
+
+    fn into_thin(value: Box<Self>) -> *mut SliceHeader<T> {
+        let len = value.len();
+        let (layout, offset) = SliceHeader::<T>::layout(len);
+
+        unsafe {
+            let header = alloc::alloc(layout) as *mut SliceHeader<T>;
+            if header.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+            ptr::addr_of_mut!((*header).len).write(len);
+
+            // Move the elements into the new allocation, then free the old one without
+            // running the elements' destructors.
+            let source = Box::into_raw(value) as *mut [ManuallyDrop<T>];
+            let dest = (header as *mut u8).add(offset) as *mut T;
+            ptr::copy_nonoverlapping(source as *const T, dest, len);
+            drop(Box::from_raw(source));
+
+            header
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn deref_thin<'a>(header: *mut SliceHeader<T>) -> &'a [T] {
+        unsafe {
+            let len = (*header).len;
+            std::slice::from_raw_parts(SliceHeader::elements(header), len)
+        }
+    }
+
+    unsafe fn drop_thin(header: *mut SliceHeader<T>) {
+        unsafe {
+            let layout = Self::drop_contents(header);
+            alloc::dealloc(header as *mut u8, layout);
+        }
+    }
+
+    unsafe fn drop_contents(header: *mut SliceHeader<T>) -> Layout {
+        unsafe {
+            let len = (*header).len;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                SliceHeader::elements(header),
+                len,
+            ));
+            SliceHeader::<T>::layout(len).0
+        }
+    }
+}
+
+impl<T: 'static> EpochPtr<[T]> {
+    /// Create a new epoch-protected slice pointer from a boxed slice.
+    ///
+    /// The elements are moved into a single allocation that also records the length,
+    /// so `load()` reaches them through one pointer.
+    ///
+    /// 从一个 boxed 切片创建新的受 epoch 保护的切片指针。
+    /// 元素被移动到同时记录长度的单个分配中，因此 `load()` 只需通过一个指针即可访问它们。
+    #[inline]
+    pub fn from_boxed_slice(data: Box<[T]>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(<[T]>::into_thin(data)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reader load: safely read the current slice.
+    ///
+    /// The lifetime of the returned slice is bound to the guard, exactly as for
+    /// `EpochPtr::load()` on sized values.
+    ///
+    /// 读取者 load：安全地读取当前切片。
+    /// 返回切片的生命周期被绑定到守卫，与有大小值的 `EpochPtr::load()` 完全相同。
+    #[inline]
+    pub fn load<'guard>(&self, _guard: &'guard PinGuard) -> &'guard [T] {
+        let ptr = self.ptr.load(Ordering::Acquire);
+        unsafe { <[T]>::deref_thin(ptr) }
+    }
+
+    /// Writer store: replace the slice and retire the old one.
+    ///
+    /// The old slice and all its elements are dropped once no reader can still observe it.
+    ///
+    /// 写入者 store：替换切片并退休旧切片。
+    /// 旧切片及其所有元素会在没有读者能再观察到它时被 drop。
+    #[inline]
+    pub fn store(&self, data: Box<[T]>, gc: &mut GcHandle)
+    where
+        T: Send,
+    {
+        let new_ptr = <[T]>::into_thin(data);
+        let old_ptr = self.ptr.swap(new_ptr, Ordering::Release);
+
+        if !old_ptr.is_null() {
+            unsafe {
+                gc.retire_thin::<[T]>(old_ptr);
+            }
+        }
+    }
+}
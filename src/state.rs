@@ -1,4 +1,12 @@
-use crate::sync::{Arc, AtomicUsize, Mutex};
+use crate::metrics::GcMetrics;
+use crate::registry::{PendingSlots, ReaderList};
+#[cfg(any(debug_assertions, feature = "metrics"))]
+use crate::sync::AtomicU64;
+use crate::sync::{Arc, AtomicUsize, Mutex, Ordering, fence};
+use std::boxed::Box;
+use std::ops::DerefMut;
+#[cfg(debug_assertions)]
+use std::time::{Duration, Instant};
 use std::vec::Vec;
 
 /// Default threshold for automatic garbage reclamation (count of retired nodes).
@@ -9,10 +17,18 @@ pub(crate) const AUTO_RECLAIM_THRESHOLD: usize = 64;
 /// 清理死读者槽的默认间隔（以回收周期为单位）。
 pub(crate) const DEFAULT_CLEANUP_INTERVAL: usize = 16;
 
+/// Number of objects `GcHandle::collect_cancellable()` reclaims between checks of its flag.
+/// `GcHandle::collect_cancellable()` 在两次检查其标志之间回收的对象数量。
+pub(crate) const CANCEL_CHECK_INTERVAL: usize = 64;
+
 /// Represents a reader that is not currently pinned to any epoch.
 /// 表示当前未被钉住到任何纪元的读者。
 pub(crate) const INACTIVE_EPOCH: usize = usize::MAX;
 
+/// Group id of readers that were not registered in a `ReaderGroup`.
+/// 未注册到 `ReaderGroup` 中的读者的组 id。
+pub(crate) const NO_GROUP: usize = 0;
+
 /// A slot allocated for a reader thread to record its active epoch.
 ///
 /// Cache-aligned to prevent false sharing between readers.
@@ -25,6 +41,148 @@ pub(crate) struct ReaderSlot {
     /// The epoch currently being accessed by the reader, or INACTIVE_EPOCH.
     /// 读者当前访问的纪元，或 INACTIVE_EPOCH。
     pub(crate) active_epoch: AtomicUsize,
+    /// When the current pin started, in nanoseconds since the domain was created.
+    /// Only recorded when a pin timeout is configured.
+    /// 当前 pin 开始的时间，以自域创建以来的纳秒数表示。仅在配置了 pin 超时时记录。
+    #[cfg(debug_assertions)]
+    pub(crate) pin_started_at: AtomicU64,
+    /// Number of times the reader became pinned (0 → 1 transitions).
+    /// 读者被钉住的次数（0 → 1 的转换）。
+    #[cfg(feature = "metrics")]
+    pub(crate) pin_total: AtomicU64,
+    /// Id of the `ReaderGroup` the reader was registered in, or `NO_GROUP`.
+    /// 读者注册时所在的 `ReaderGroup` 的 id，或 `NO_GROUP`。
+    pub(crate) group: AtomicUsize,
+    /// Index of the slot in the readers list, kept up to date by `ReaderList::push`.
+    /// Only meaningful with `ReaderStorage::Slab`, whose indices never move.
+    /// 槽在读者列表中的索引，由 `ReaderList::push` 维护。仅在索引不会移动的 `ReaderStorage::Slab` 下有意义。
+    pub(crate) key: AtomicUsize,
+    /// Bumped when a `LocalEpoch` takes the slot and again when it is dropped, so it is odd
+    /// exactly while the slot is owned. Lets a `ReaderToken` tell its reader from a later
+    /// owner of a recycled slot.
+    /// 在 `LocalEpoch` 获得槽时以及被 drop 时各递增一次，因此仅当槽被持有时为奇数。
+    /// 使 `ReaderToken` 能够区分其读者与复用槽的后续持有者。
+    pub(crate) generation: AtomicUsize,
+}
+
+impl ReaderSlot {
+    /// Create a new, inactive reader slot.
+    /// 创建一个新的、不活跃的读者槽。
+    pub(crate) fn new() -> Self {
+        ReaderSlot {
+            active_epoch: AtomicUsize::new(INACTIVE_EPOCH),
+            #[cfg(debug_assertions)]
+            pin_started_at: AtomicU64::new(0),
+            #[cfg(feature = "metrics")]
+            pin_total: AtomicU64::new(0),
+            group: AtomicUsize::new(NO_GROUP),
+            key: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of times this thread went through `SharedState::lock_readers()`, so tests can
+    /// check that a path skips the readers lock.
+    /// 此线程经过 `SharedState::lock_readers()` 的次数，以便测试检查某条路径跳过了读者锁。
+    pub(crate) static READERS_LOCKS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+impl SharedState {
+    /// Lock the readers list, first merging in any readers registered lock-free.
+    /// 锁定读者列表，并先合并所有以无锁方式注册的读者。
+    #[inline]
+    pub(crate) fn lock_readers(&self) -> impl DerefMut<Target = ReaderList> + '_ {
+        #[cfg(test)]
+        READERS_LOCKS.set(READERS_LOCKS.get() + 1);
+        let mut readers = self.readers.lock();
+        if let Some(pending) = &self.pending_readers {
+            pending.drain_into(&mut readers);
+        }
+        readers
+    }
+
+    /// Describe which domain a diagnostic is about: ` of domain "name"`, or nothing if unnamed.
+    /// 描述诊断信息所针对的域：` of domain "name"`，未命名时为空。
+    #[cfg(debug_assertions)]
+    pub(crate) fn describe(&self) -> String {
+        if self.name.is_empty() {
+            String::new()
+        } else {
+            format!(" of domain {:?}", self.name)
+        }
+    }
+
+    /// Get the epochs of the currently pinned readers, in ascending order.
+    /// 获取当前被钉住的读者的纪元，按升序排列。
+    pub(crate) fn pinned_epochs_sorted(&self) -> Vec<usize> {
+        let mut epochs: Vec<usize> = self
+            .lock_readers()
+            .iter()
+            .map(|slot| slot.active_epoch.load(Ordering::Acquire))
+            .filter(|&epoch| epoch != INACTIVE_EPOCH)
+            .collect();
+        epochs.sort_unstable();
+        epochs
+    }
+
+    /// Remove a reader's own slot from the readers list right away.
+    /// 立即从读者列表中移除读者自己的槽。
+    pub(crate) fn remove_reader(&self, slot: &Arc<ReaderSlot>) {
+        let mut readers = self.lock_readers();
+        if let Some(removed) = readers.remove(slot) {
+            if readers.moves_on_removal() {
+                self.slot_removals.fetch_add(1, Ordering::Release);
+            }
+            self.reader_count.fetch_sub(1, Ordering::Release);
+            if let Some(recycled) = &self.recycled_slots {
+                recycled.lock().push(removed);
+            }
+        }
+    }
+
+    /// Add a newly registered reader slot using the configured backend.
+    /// 使用配置的后端添加一个新注册的读者槽。
+    #[inline]
+    pub(crate) fn add_reader(&self, slot: Arc<ReaderSlot>) {
+        self.reader_count.fetch_add(1, Ordering::SeqCst);
+        // Pairs with the fence in `GcHandle::retire_or_free`: either the writer sees this
+        // reader, or this reader's first load sees the writer's latest store.
+        fence(Ordering::SeqCst);
+        match &self.pending_readers {
+            Some(pending) => pending.push(slot),
+            None => self.readers.lock().push(slot),
+        }
+    }
+}
+
+/// A cleanup closure deferred by a reader, run when it is dropped.
+///
+/// 由读者延迟的清理闭包，在被 drop 时运行。
+pub(crate) struct Deferred(Option<Box<dyn FnOnce() + Send>>);
+
+impl Deferred {
+    #[inline]
+    pub(crate) fn new(f: impl FnOnce() + Send + 'static) -> Self {
+        Deferred(Some(Box::new(f)))
+    }
+}
+
+impl Drop for Deferred {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
+impl std::fmt::Debug for Deferred {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Deferred")
+    }
 }
 
 /// Global shared state for the epoch GC domain.
@@ -44,5 +202,43 @@ pub(crate) struct SharedState {
     pub(crate) min_active_epoch: AtomicUsize,
     /// List of all registered reader slots. Protected by a Mutex.
     /// 所有注册读者槽的列表。由 Mutex 保护。
-    pub(crate) readers: Mutex<Vec<Arc<ReaderSlot>>>,
+    pub(crate) readers: Mutex<ReaderList>,
+    /// Number of registered reader slots, so `collect()` can skip the readers lock when zero.
+    /// 已注册读者槽的数量，以便 `collect()` 在其为零时跳过读者锁。
+    pub(crate) reader_count: AtomicUsize,
+    /// Number of slots removed by eagerly unregistering readers, to detect it during a scan.
+    /// Not incremented with `ReaderStorage::Slab`, where removals never move other slots.
+    /// 被急切注销的读者移除的槽数量，用于在扫描期间检测这种情况。
+    /// 使用 `ReaderStorage::Slab` 时不递增，因为移除永远不会移动其他槽。
+    pub(crate) slot_removals: AtomicUsize,
+    /// Lock-free stack of new registrations, if the lock-free readers backend is selected.
+    /// 新注册的无锁栈（如果选择了无锁读者后端）。
+    pub(crate) pending_readers: Option<PendingSlots>,
+    /// Dead reader slots kept for reuse by `register_reader`, if slot recycling is enabled.
+    /// 如果启用了槽复用，则保存供 `register_reader` 复用的死读者槽。
+    pub(crate) recycled_slots: Option<Mutex<Vec<Arc<ReaderSlot>>>>,
+    /// Cleanup closures deferred by readers, waiting for the writer to retire them.
+    /// 由读者延迟、等待写入者退休的清理闭包。
+    pub(crate) deferred: Mutex<Vec<Deferred>>,
+    /// Whether readers first try to pin to the epoch of their previous pin.
+    /// 读者是否先尝试钉住到其上一次 pin 的纪元。
+    pub(crate) reader_epoch_cache: bool,
+    /// Name given with `EpochGcDomainBuilder::name()`, empty if none.
+    /// 通过 `EpochGcDomainBuilder::name()` 指定的名称，未指定时为空。
+    pub(crate) name: std::sync::Arc<str>,
+    /// Observability hooks injected with `EpochGcDomainBuilder::with_metrics()`, if any.
+    /// 通过 `EpochGcDomainBuilder::with_metrics()` 注入的可观测性钩子（如果有）。
+    pub(crate) metrics: Option<std::sync::Arc<dyn GcMetrics>>,
+    /// Total number of times a pin had to retry because its epoch was already stale.
+    /// 所有 pin 因其纪元已过时而不得不重试的总次数。
+    #[cfg(feature = "metrics")]
+    pub(crate) pin_spins: AtomicU64,
+    /// Reference point for pin timestamps.
+    /// pin 时间戳的参考点。
+    #[cfg(debug_assertions)]
+    pub(crate) created_at: Instant,
+    /// Maximum time a reader may stay pinned before `collect()` reports it.
+    /// 读者在 `collect()` 报告之前可以保持被钉住的最长时间。
+    #[cfg(debug_assertions)]
+    pub(crate) pin_timeout: Option<Duration>,
 }
@@ -4,29 +4,126 @@ pub use loom::cell::Cell;
 pub use std::cell::Cell;
 
 #[cfg(feature = "loom")]
-pub use loom::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+pub use loom::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering, fence};
 #[cfg(not(feature = "loom"))]
-pub use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+pub use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering, fence};
+
+#[cfg(feature = "loom")]
+pub use loom::sync::atomic::AtomicU64;
+#[cfg(not(feature = "loom"))]
+pub use std::sync::atomic::AtomicU64;
 
 #[cfg(feature = "loom")]
 pub use loom::sync::Arc;
 #[cfg(not(feature = "loom"))]
 pub use std::sync::Arc;
 
+// loom's `Arc` has no weak references, so `ReaderToken` is unavailable under loom
+#[cfg(not(feature = "loom"))]
+pub use std::sync::Weak;
+
+#[cfg(feature = "loom")]
+pub use loom::thread::yield_now;
+#[cfg(not(feature = "loom"))]
+pub use std::thread::yield_now;
+
 #[cfg(not(feature = "loom"))]
 pub use antidote::Mutex;
 
 #[cfg(feature = "loom")]
-#[derive(Debug, Default)]
-pub struct Mutex<T>(loom::sync::Mutex<T>);
+use std::sync::PoisonError;
+
+/// A loom-modelled mutex that never poisons, matching `antidote::Mutex`.
+///
+/// `loom::sync::Mutex` unwraps its inner `std::sync::Mutex`, so once a holder panics every later
+/// `lock()` panics too. Here the loom mutex only guards the `locked` flag and is never held while
+/// the caller runs, so a reader thread panicking mid-registration cannot cascade into a panic in
+/// `collect()`. Waiters block on a condvar, and `data` is a loom cell so races on it are still
+/// detected.
+#[cfg(feature = "loom")]
+#[derive(Debug)]
+pub struct Mutex<T> {
+    locked: loom::sync::Mutex<bool>,
+    unlocked: loom::sync::Condvar,
+    data: loom::cell::UnsafeCell<T>,
+}
+
+// SAFETY: `data` is only accessed through a `MutexGuard`, and `locked` admits one at a time
+#[cfg(feature = "loom")]
+unsafe impl<T: Send> Send for Mutex<T> {}
+#[cfg(feature = "loom")]
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+#[cfg(feature = "loom")]
+impl<T: Default> Default for Mutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
 
 #[cfg(feature = "loom")]
 impl<T> Mutex<T> {
     pub fn new(t: T) -> Self {
-        Self(loom::sync::Mutex::new(t))
+        Self {
+            locked: loom::sync::Mutex::new(false),
+            unlocked: loom::sync::Condvar::new(),
+            data: loom::cell::UnsafeCell::new(t),
+        }
+    }
+
+    /// Lock the mutex. The lock is released even if the holder panics, and is never poisoned.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        let mut locked = self.locked.lock().unwrap_or_else(PoisonError::into_inner);
+        while *locked {
+            locked = self
+                .unlocked
+                .wait(locked)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *locked = true;
+        drop(locked);
+        MutexGuard {
+            mutex: self,
+            data: Some(self.data.get_mut()),
+        }
     }
+}
+
+#[cfg(feature = "loom")]
+#[derive(Debug)]
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+    // released before the lock, so loom sees the access end while it is still held
+    data: Option<loom::cell::MutPtr<T>>,
+}
+
+#[cfg(feature = "loom")]
+impl<T> std::ops::Deref for MutexGuard<'_, T> {
+    type Target = T;
 
-    pub fn lock(&self) -> loom::sync::MutexGuard<'_, T> {
-        self.0.lock().unwrap()
+    fn deref(&self) -> &T {
+        // SAFETY: the guard holds the lock
+        unsafe { self.data.as_ref().unwrap().deref() }
+    }
+}
+
+#[cfg(feature = "loom")]
+impl<T> std::ops::DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the guard holds the lock
+        unsafe { self.data.as_ref().unwrap().deref() }
+    }
+}
+
+#[cfg(feature = "loom")]
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.data = None;
+        *self
+            .mutex
+            .locked
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = false;
+        self.mutex.unlocked.notify_one();
     }
 }
@@ -145,7 +145,7 @@ fn test_garbage_collection_trigger() {
 
     // 退休数据直到触发回收
     for i in 0..70 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 由于 AUTO_RECLAIM_THRESHOLD = 64，第 65 个退休会触发 collect
@@ -165,7 +165,7 @@ fn test_active_reader_protects_garbage() {
 
     // 退休数据直到触发回收
     for i in 0..70 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 由于读取者仍然活跃，垃圾不应该被完全清空
@@ -184,7 +184,7 @@ fn test_garbage_reclaimed_after_reader_drop() {
 
         // 在读取者活跃时退休数据
         for i in 0..70 {
-            gc.retire(Box::new(i as i32));
+            gc.retire(Box::new(i));
         }
 
         // 垃圾应该被保留
@@ -315,7 +315,7 @@ fn test_heavy_garbage_collection_cycles() {
     for cycle in 0..10 {
         // 在每个循环中退休大量数据
         for i in 0..100 {
-            gc.retire(Box::new((cycle * 100 + i) as i32));
+            gc.retire(Box::new(cycle * 100 + i));
         }
 
         // 触发回收
@@ -365,3 +365,289 @@ fn test_reader_holds_guard_during_updates() {
 
     reader.join().unwrap();
 }
+
+/// 测试14: 持有注册锁的线程 panic 后写入者仍可回收
+#[test]
+fn test_collect_after_panic_while_holding_readers_lock() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(0i32);
+
+    let panicking_domain = domain.clone();
+    let result = thread::spawn(move || {
+        let _readers = panicking_domain.shared.readers.lock();
+        panic!("reader thread panicked during registration");
+    })
+    .join();
+    assert!(result.is_err());
+
+    // 锁不会被毒化，注册和回收都能继续
+    let local_epoch = domain.register_reader();
+    ptr.store(1, &mut gc);
+    gc.collect();
+
+    let guard = local_epoch.pin();
+    assert_eq!(*ptr.load(&guard), 1);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试15: 分块扫描大量读取者时回收仍然正确
+#[test]
+fn test_chunked_scan_with_many_readers() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .scan_chunk(8)
+        .auto_reclaim_threshold(None)
+        .build();
+    let ptr = EpochPtr::new(0usize);
+
+    let readers: Vec<_> = (0..100).map(|_| domain.register_reader()).collect();
+
+    // 只有最后一块中的一个读取者被钉住在纪元 0
+    let guard = readers[97].pin();
+    ptr.store(1, &mut gc);
+    gc.collect();
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 2);
+
+    // 并发注册与分块扫描交错
+    let registering_domain = domain.clone();
+    let registrar = thread::spawn(move || {
+        let late: Vec<_> = (0..50)
+            .map(|_| registering_domain.register_reader())
+            .collect();
+        late.len()
+    });
+    for i in 3..20 {
+        ptr.store(i, &mut gc);
+        gc.collect();
+    }
+    assert_eq!(registrar.join().unwrap(), 50);
+
+    // 被钉住的读取者仍然保护着所有垃圾
+    assert_eq!(*ptr.load(&guard), 19);
+    assert_eq!(gc.total_garbage_count(), 19);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试16: EpochCell 在写入期间的并发读取
+#[test]
+fn test_epoch_cell_concurrent_reads_during_writes() {
+    use crate::EpochCell;
+
+    struct Config {
+        version: usize,
+        checksum: usize,
+    }
+
+    let mut cell = EpochCell::new(Config {
+        version: 0,
+        checksum: 0,
+    });
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = cell.reader_handle();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let local_epoch = handle.reader();
+                let mut last = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let guard = local_epoch.pin();
+                    let config = handle.read(&guard);
+                    // 读者总是看到完整一致且单调递增的配置
+                    assert_eq!(config.checksum, config.version * 2);
+                    assert!(config.version >= last);
+                    last = config.version;
+                }
+            })
+        })
+        .collect();
+
+    for version in 1..=500 {
+        cell.write(Config {
+            version,
+            checksum: version * 2,
+        });
+    }
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    let local_epoch = cell.reader();
+    assert_eq!(cell.read(&local_epoch.pin()).version, 500);
+}
+
+/// 测试17: try_pin 与不断推进纪元的写入者竞争
+#[test]
+fn test_try_pin_races_advancing_writer() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = Arc::new(EpochPtr::new(0usize));
+    let pinned = Arc::new(AtomicUsize::new(0));
+
+    let reader = {
+        let ptr = ptr.clone();
+        let pinned = pinned.clone();
+        thread::spawn(move || {
+            let local_epoch = domain.register_reader();
+            while pinned.load(Ordering::Relaxed) < 100 {
+                if let Some(guard) = local_epoch.try_pin() {
+                    let first = *ptr.load(&guard);
+                    // 被钉住期间读到的值必须保持有效且单调
+                    assert!(*ptr.load(&guard) >= first);
+                    pinned.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        })
+    };
+
+    // 写入者在读者完成之前持续推进纪元
+    let mut value = 0;
+    while pinned.load(Ordering::Relaxed) < 100 {
+        value += 1;
+        ptr.store(value, &mut gc);
+        gc.collect();
+    }
+
+    reader.join().unwrap();
+    gc.collect();
+}
+
+/// 测试18: 启用读者纪元缓存时的并发读写
+#[test]
+fn test_reader_epoch_cache_concurrent() {
+    let (mut gc, domain) = EpochGcDomain::builder().reader_epoch_cache(true).build();
+    let ptr = Arc::new(EpochPtr::new(vec![0usize; 16]));
+    let reads = Arc::new(AtomicUsize::new(0));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let domain = domain.clone();
+            let ptr = ptr.clone();
+            let reads = reads.clone();
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                while reads.load(Ordering::Relaxed) < 4000 {
+                    let guard = local_epoch.pin();
+                    let values = ptr.load(&guard);
+                    // 每个向量的所有元素都相同，被回收的内存会破坏这一点
+                    assert!(values.iter().all(|v| *v == values[0]));
+                    reads.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    let mut value = 0;
+    while reads.load(Ordering::Relaxed) < 4000 {
+        value += 1;
+        ptr.store(vec![value; 16], &mut gc);
+        gc.collect();
+    }
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    gc.collect();
+}
+
+/// 测试19: 写入者密集回收时 pin 的自旋次数会增长
+#[cfg(feature = "metrics")]
+#[test]
+fn test_total_pin_spins_grows() {
+    use std::sync::atomic::AtomicBool;
+    use std::time::{Duration, Instant};
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let done = Arc::new(AtomicBool::new(false));
+    assert_eq!(domain.total_pin_spins(), 0);
+
+    let readers: Vec<_> = (0..2)
+        .map(|_| {
+            let domain = domain.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                while !done.load(Ordering::Relaxed) {
+                    drop(local_epoch.pin());
+                }
+            })
+        })
+        .collect();
+
+    // 写入者在紧密循环中推进纪元，直到观察到自旋
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while domain.total_pin_spins() == 0 && Instant::now() < deadline {
+        gc.collect();
+    }
+    done.store(true, Ordering::Relaxed);
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert!(domain.total_pin_spins() > 0);
+}
+
+/// 测试20: 写入者等待群组成员完成一次 pin 周期，忽略群组之外的读者
+#[test]
+fn test_quiesce_group() {
+    use crate::ReaderGroup;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let group = ReaderGroup::new();
+    let pinned = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(AtomicBool::new(false));
+
+    // 群组之外的读者一直保持被钉住
+    let outsider = domain.register_reader();
+    let _outsider_guard = outsider.pin();
+
+    let members: Vec<_> = (0..2)
+        .map(|_| {
+            let domain = domain.clone();
+            let group = group.clone();
+            let pinned = pinned.clone();
+            let release = release.clone();
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader_in_group(&group);
+                let guard = local_epoch.pin();
+                pinned.fetch_add(1, Ordering::SeqCst);
+                while !release.load(Ordering::SeqCst) {
+                    thread::yield_now();
+                }
+                drop(guard);
+            })
+        })
+        .collect();
+
+    while pinned.load(Ordering::SeqCst) < 2 {
+        thread::yield_now();
+    }
+
+    let releaser = {
+        let release = release.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            release.store(true, Ordering::SeqCst);
+        })
+    };
+
+    // 只有在所有成员都解除钉住之后才会返回
+    gc.quiesce_group(&group);
+    assert!(release.load(Ordering::SeqCst));
+
+    // 没有成员被钉住时立即返回
+    gc.quiesce_group(&group);
+    gc.quiesce_group(&ReaderGroup::new());
+
+    releaser.join().unwrap();
+    for member in members {
+        member.join().unwrap();
+    }
+}
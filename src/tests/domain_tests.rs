@@ -0,0 +1,625 @@
+use crate::state::INACTIVE_EPOCH;
+use crate::sync::Ordering;
+/// 域配置测试模块
+/// 测试 EpochGcDomainBuilder 的配置选项和读者注册行为
+use crate::{EpochGcDomain, EpochPtr, GcMetrics, ReadersBackend};
+use std::sync::Arc;
+
+/// 测试1: 读者槽复用使分配数量保持有界
+#[test]
+fn test_reader_slot_recycling() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .reader_slot_recycling(true)
+        .cleanup_interval(1)
+        .build();
+    let ptr = EpochPtr::new(0i32);
+    let recycled = || domain.shared.recycled_slots.as_ref().unwrap().lock().len();
+
+    for i in 0..100 {
+        let local_epoch = domain.register_reader();
+        // 第一次注册之后，每次注册都取走了上一个读者留下的死槽
+        assert_eq!(recycled(), 0);
+        {
+            let guard = local_epoch.pin();
+            assert_eq!(*ptr.load(&guard), i);
+        }
+        drop(local_epoch);
+
+        ptr.store(i + 1, &mut gc);
+        gc.collect();
+        assert_eq!(recycled(), 1);
+    }
+
+    assert!(domain.shared.readers.lock().is_empty());
+}
+
+/// 测试2: 默认情况下不复用读者槽
+#[test]
+fn test_reader_slots_not_recycled_by_default() {
+    let (mut gc, domain) = EpochGcDomain::builder().cleanup_interval(1).build();
+
+    let first = domain.register_reader();
+    drop(first);
+    gc.collect();
+
+    // 死槽被直接释放，而不是放入空闲列表
+    assert!(domain.shared.recycled_slots.is_none());
+    assert!(domain.shared.readers.lock().is_empty());
+
+    let _second = domain.register_reader();
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+}
+
+/// 测试3: 两种读者注册后端的回收行为一致
+#[test]
+fn test_readers_backends_reclaim_identically() {
+    fn run_workload(backend: ReadersBackend) -> Vec<(usize, usize)> {
+        let (mut gc, domain) = EpochGcDomain::builder()
+            .readers_backend(backend)
+            .auto_reclaim_threshold(None)
+            .build();
+        let ptr = EpochPtr::new(0usize);
+        let readers: Vec<_> = (0..3).map(|_| domain.register_reader()).collect();
+        let mut trace = Vec::new();
+
+        for round in 0..6 {
+            // 每轮钉住不同的读者子集
+            let guards: Vec<_> = readers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (round + i) % 2 == 0)
+                .map(|(_, reader)| reader.pin())
+                .collect();
+
+            ptr.store(round + 1, &mut gc);
+            gc.collect();
+            trace.push((gc.total_garbage_count(), gc.pending_epochs()));
+            drop(guards);
+        }
+
+        // 后注册的读者在下一次回收时被看到
+        let late_reader = domain.register_reader();
+        let guard = late_reader.pin();
+        ptr.store(100, &mut gc);
+        gc.collect();
+        trace.push((gc.total_garbage_count(), gc.pending_epochs()));
+        drop(guard);
+
+        gc.collect();
+        trace.push((gc.total_garbage_count(), gc.pending_epochs()));
+        trace
+    }
+
+    assert_eq!(
+        run_workload(ReadersBackend::Mutex),
+        run_workload(ReadersBackend::LockFree)
+    );
+}
+
+/// 测试4: 无锁注册的读者在扫描时被合并
+#[test]
+fn test_lock_free_backend_merges_on_scan() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .readers_backend(ReadersBackend::LockFree)
+        .build();
+
+    let readers: Vec<_> = (0..5).map(|_| domain.register_reader()).collect();
+    // 注册时没有获取读者锁
+    assert!(domain.shared.readers.lock().is_empty());
+
+    let guard = readers[2].pin();
+    gc.retire(Box::new(1i32));
+    gc.collect();
+
+    // 回收时合并了所有新读者，且被钉住的读者保护了垃圾
+    assert_eq!(domain.shared.readers.lock().len(), 5);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试5: 永久钉住的读者使 try_store 受到背压而不是无限增长
+#[test]
+fn test_max_pending_garbage_try_store_backpressure() {
+    let (mut gc, domain) = EpochGcDomain::builder().max_pending_garbage(8).build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+
+    let guard = local_epoch.pin();
+    let mut rejected = 0;
+    for i in 1..=100 {
+        if let Err(value) = ptr.try_store(i, &mut gc) {
+            assert_eq!(value, i);
+            rejected += 1;
+        }
+        assert!(gc.total_garbage_count() <= 8);
+    }
+
+    // 前 8 次写入成功，之后的写入都被拒绝，且指针保持最后一次成功写入的值
+    assert_eq!(rejected, 92);
+    assert_eq!(*ptr.load(&guard), 8);
+    drop(guard);
+
+    // 读者解除钉住后，写入恢复
+    assert_eq!(ptr.try_store(101, &mut gc), Ok(()));
+    assert_eq!(gc.total_garbage_count(), 1);
+}
+
+/// 测试6: 队列已满时 store 阻塞，直到读者解除钉住
+#[test]
+fn test_max_pending_garbage_store_blocks() {
+    let (mut gc, domain) = EpochGcDomain::builder().max_pending_garbage(4).build();
+    let ptr = Arc::new(EpochPtr::new(0usize));
+    let (pinned_tx, pinned_rx) = std::sync::mpsc::channel();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+    let reader = {
+        let domain = domain.clone();
+        std::thread::spawn(move || {
+            let local_epoch = domain.register_reader();
+            let guard = local_epoch.pin();
+            pinned_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            drop(guard);
+        })
+    };
+    pinned_rx.recv().unwrap();
+
+    for i in 1..=4 {
+        ptr.store(i, &mut gc);
+    }
+    assert_eq!(gc.total_garbage_count(), 4);
+
+    // 在读者解除钉住之前，第 5 次写入会阻塞
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        release_tx.send(()).unwrap();
+    });
+    ptr.store(5, &mut gc);
+    assert!(gc.total_garbage_count() <= 4);
+
+    releaser.join().unwrap();
+    reader.join().unwrap();
+}
+
+/// 测试7: 急切读者在被 drop 时立即注销其槽
+#[test]
+fn test_eager_reader_unregisters_on_drop() {
+    let (mut gc, domain) = EpochGcDomain::builder().cleanup_interval(0).build();
+
+    let readers: Vec<_> = (0..3).map(|_| domain.register_reader_eager()).collect();
+    assert_eq!(domain.shared.readers.lock().len(), 3);
+
+    drop(readers);
+    // 无需任何回收，读者列表已经为空
+    assert_eq!(domain.shared.readers.lock().len(), 0);
+    assert_eq!(
+        domain
+            .shared
+            .reader_count
+            .load(std::sync::atomic::Ordering::SeqCst),
+        0
+    );
+
+    // 普通读者与急切读者混用时，只有急切读者被立即移除
+    let normal = domain.register_reader();
+    let eager = domain.register_reader_eager();
+    let guard = normal.pin();
+    drop(eager);
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+
+    // 剩余的读者仍然保护垃圾
+    let ptr = EpochPtr::new(1i32);
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 1);
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试8: 分块扫描期间急切读者的注销不会导致遗漏被钉住的读者
+#[test]
+fn test_eager_unregister_during_chunked_scan() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .scan_chunk(2)
+        .build();
+    let domain = Arc::new(domain);
+    let ptr = EpochPtr::new(0usize);
+
+    // 最后注册的读者被钉住，其余的急切读者不断被 drop
+    let churn: Vec<_> = (0..16).map(|_| domain.register_reader_eager()).collect();
+    let pinned = domain.register_reader();
+    let guard = pinned.pin();
+
+    let dropper = std::thread::spawn(move || drop(churn));
+    for i in 1..=50 {
+        ptr.store(i, &mut gc);
+        gc.collect();
+    }
+    dropper.join().unwrap();
+
+    // 被钉住的读者自始至终保护着所有退休的值
+    assert_eq!(gc.total_garbage_count(), 50);
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试9: 重置一个用过的域后，其行为与新域相同
+#[test]
+fn test_reset_domain() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(0i32);
+
+    {
+        let local_epoch = domain.register_reader();
+        let _guard = local_epoch.pin();
+        ptr.store(1, &mut gc);
+        gc.collect();
+        gc.collect();
+    }
+    assert!(domain.epoch() > 0);
+
+    // 仍有待回收的垃圾时拒绝重置
+    assert!(!gc.reset());
+    gc.collect();
+    assert!(gc.reset());
+    assert_eq!(domain.epoch(), 0);
+    assert_eq!(
+        gc.shared
+            .min_active_epoch
+            .load(std::sync::atomic::Ordering::Acquire),
+        0
+    );
+    assert!(gc.shared.readers.lock().is_empty());
+
+    // 重置后的域像新域一样工作
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(domain.epoch(), 1);
+    assert_eq!(gc.total_garbage_count(), 1);
+    assert_eq!(*ptr.load(&guard), 2);
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 存活的读者会阻止重置
+    assert!(!gc.reset());
+    assert_eq!(domain.epoch(), 2);
+    drop(local_epoch);
+    assert!(gc.reset());
+}
+
+/// 测试10: 无锁注册后端下的重置
+#[test]
+fn test_reset_lock_free_backend() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .readers_backend(ReadersBackend::LockFree)
+        .build();
+
+    drop(domain.register_reader());
+    gc.collect();
+    assert!(gc.reset());
+    assert_eq!(domain.epoch(), 0);
+
+    // 存活的读者同样会阻止重置
+    let local_epoch = domain.register_reader();
+    gc.collect();
+    assert!(!gc.reset());
+    assert_eq!(domain.epoch(), 1);
+    drop(local_epoch);
+}
+
+/// 测试11: slab 存储中的移除是 O(1) 的，且不会移动其他槽
+#[test]
+fn test_slab_storage_stable_keys() {
+    use crate::ReaderStorage;
+    use std::sync::atomic::Ordering;
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .reader_storage(ReaderStorage::Slab)
+        .cleanup_interval(1)
+        .build();
+
+    let readers: Vec<_> = (0..8).map(|_| domain.register_reader_eager()).collect();
+    let keys: Vec<_> = readers
+        .iter()
+        .map(|reader| reader.slot.key.load(Ordering::Relaxed))
+        .collect();
+    assert_eq!(keys, (0..8).collect::<Vec<_>>());
+
+    // 移除中间的读者：其他槽保持各自的索引，扫描也不需要重新开始
+    let mut readers = readers;
+    let removed = readers.remove(3);
+    drop(removed);
+    assert_eq!(domain.shared.readers.lock().len(), 7);
+    assert_eq!(domain.shared.slot_removals.load(Ordering::Acquire), 0);
+    for reader in &readers {
+        let key = reader.slot.key.load(Ordering::Relaxed);
+        let list = domain.shared.readers.lock();
+        assert!(crate::sync::Arc::ptr_eq(
+            list.get(key).unwrap(),
+            &reader.slot
+        ));
+    }
+
+    // 空闲的索引被下一次注册复用
+    let replacement = domain.register_reader();
+    assert_eq!(replacement.slot.key.load(Ordering::Relaxed), 3);
+    assert_eq!(domain.shared.readers.lock().span(), 8);
+
+    // 清理死槽同样只留下空洞
+    drop(replacement);
+    gc.collect();
+    assert_eq!(domain.shared.readers.lock().len(), 7);
+    assert_eq!(readers[6].slot.key.load(Ordering::Relaxed), 7);
+}
+
+/// 测试12: slab 存储下，回收期间的并发注册和注销仍然是正确的
+#[test]
+fn test_slab_storage_concurrent_registration() {
+    use crate::ReaderStorage;
+
+    for backend in [ReadersBackend::Mutex, ReadersBackend::LockFree] {
+        let (mut gc, domain) = EpochGcDomain::builder()
+            .reader_storage(ReaderStorage::Slab)
+            .readers_backend(backend)
+            .auto_reclaim_threshold(None)
+            .cleanup_interval(1)
+            .scan_chunk(2)
+            .build();
+        let ptr = EpochPtr::new(0usize);
+
+        let churn: Vec<_> = (0..16).map(|_| domain.register_reader_eager()).collect();
+        let pinned = domain.register_reader();
+        let guard = pinned.pin();
+
+        let churner = {
+            let domain = domain.clone();
+            std::thread::spawn(move || {
+                drop(churn);
+                for _ in 0..200 {
+                    let eager = domain.register_reader_eager();
+                    let normal = domain.register_reader();
+                    drop(eager.pin());
+                    drop(normal.pin());
+                }
+            })
+        };
+        for i in 1..=50 {
+            ptr.store(i, &mut gc);
+            gc.collect();
+        }
+        churner.join().unwrap();
+
+        // 被钉住的读者自始至终保护着所有退休的值
+        assert_eq!(gc.total_garbage_count(), 50);
+        assert_eq!(*ptr.load(&guard), 50);
+        drop(guard);
+        gc.collect();
+        assert_eq!(gc.total_garbage_count(), 0);
+        assert_eq!(domain.shared.readers.lock().len(), 1);
+    }
+}
+
+/// 测试13: 不持有 GcHandle 也可以推进纪元，之后钉住的读者看到新纪元，但回收仍由写入者负责
+#[test]
+fn test_advance_epoch_only() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(0i32);
+    let local_epoch = domain.register_reader();
+
+    ptr.store(1, &mut gc);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    // 从另一个线程推进纪元
+    let new_epoch =
+        std::thread::scope(|scope| scope.spawn(|| domain.advance_epoch_only()).join().unwrap());
+    assert_eq!(new_epoch, 1);
+    assert_eq!(domain.epoch(), 1);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    let guard = local_epoch.pin();
+    assert_eq!(guard.epoch(), 1);
+
+    // 在外部纪元之前退休的垃圾仍受钉住于该纪元的读者保护
+    gc.collect();
+    assert_eq!(domain.epoch(), 2);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(*ptr.load(&local_epoch.pin()), 1);
+}
+
+/// 测试14: 在指定 NUMA 节点上注册的读者，无论放置是否成功都能正确工作
+#[cfg(feature = "numa")]
+#[test]
+fn test_register_reader_on_node() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(0usize);
+
+    // 节点 0 总是存在；不存在的节点使放置被静默跳过
+    let readers = [
+        domain.register_reader_on_node(0),
+        domain.register_reader_on_node(1),
+        domain.register_reader_on_node(usize::MAX),
+    ];
+    assert_eq!(domain.shared.readers.lock().len(), 3);
+
+    let guards: Vec<_> = readers.iter().map(|reader| reader.pin()).collect();
+    ptr.store(1, &mut gc);
+    gc.collect();
+    // 被钉住的读者保护旧值
+    assert_eq!(gc.total_garbage_count(), 1);
+    for guard in &guards {
+        assert_eq!(*ptr.load(guard), 1);
+    }
+
+    drop(guards);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let local_epoch = domain.register_reader_on_node(0);
+            assert_eq!(*ptr.load(&local_epoch.pin()), 1);
+        });
+    });
+}
+
+/// 测试15: 上限为 1 时 try_store 最终返回 Err，并原样交还拥有堆内存的值
+#[test]
+fn test_try_store_returns_value_intact() {
+    let (mut gc, domain) = EpochGcDomain::builder().max_pending_garbage(1).build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("initial"));
+
+    let guard = local_epoch.pin();
+    assert_eq!(ptr.try_store(String::from("first"), &mut gc), Ok(()));
+
+    let value = String::from("rejected");
+    let (addr, capacity) = (value.as_ptr(), value.capacity());
+    let returned = ptr.try_store(value, &mut gc).unwrap_err();
+    // 未执行 store：值和其分配都原样交还
+    assert_eq!(returned, "rejected");
+    assert_eq!((returned.as_ptr(), returned.capacity()), (addr, capacity));
+    assert_eq!(ptr.load(&guard), "first");
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    drop(guard);
+    assert_eq!(ptr.try_store(returned, &mut gc), Ok(()));
+    assert_eq!(ptr.load(&local_epoch.pin()), "rejected");
+}
+
+/// 测试16: 注入的 GcMetrics 钩子在注册、最外层 pin 和每次回收时以正确的参数触发
+#[test]
+fn test_with_metrics_hooks() {
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct Counting {
+        registers: AtomicUsize,
+        pins: AtomicUsize,
+        collects: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl GcMetrics for Counting {
+        fn on_collect(&self, reclaimed: usize, epoch: usize) {
+            self.collects.lock().unwrap().push((reclaimed, epoch));
+        }
+
+        fn on_pin(&self) {
+            self.pins.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_register(&self) {
+            self.registers.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let metrics = Arc::new(Counting::default());
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .with_metrics(metrics.clone())
+        .build();
+
+    let reader = domain.register_reader();
+    let _other = domain.register_reader();
+    let _writer_local = gc.local_reader();
+    assert_eq!(metrics.registers.load(Ordering::Relaxed), 3);
+
+    // 嵌套 pin 只计一次
+    let guard = reader.pin();
+    let nested = reader.pin();
+    assert_eq!(metrics.pins.load(Ordering::Relaxed), 1);
+
+    let ptr = EpochPtr::new(0i32);
+    for i in 1..=3 {
+        ptr.store(i, &mut gc);
+    }
+    gc.collect();
+    drop(nested);
+    drop(guard);
+    gc.collect();
+    assert_eq!(*metrics.collects.lock().unwrap(), [(0, 1), (3, 2)]);
+
+    drop(reader.pin());
+    assert_eq!(metrics.pins.load(Ordering::Relaxed), 2);
+}
+
+/// 测试17: 域名称可以从域及其克隆读取，并出现在 Debug 输出和诊断信息中
+#[test]
+fn test_domain_name() {
+    let (_gc, unnamed) = EpochGcDomain::new();
+    assert_eq!(unnamed.name(), "");
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .name("cache")
+        .auto_reclaim_threshold(None)
+        .build();
+    let clone = domain.clone();
+    assert_eq!(domain.name(), "cache");
+    assert_eq!(clone.name(), "cache");
+
+    let _local_epoch = clone.register_reader();
+    let ptr = EpochPtr::new(0i32);
+    ptr.store(1, &mut gc);
+    assert_eq!(
+        format!("{domain:?}"),
+        r#"EpochGcDomain { name: "cache", epoch: 0, readers: 1 }"#
+    );
+
+    #[cfg(debug_assertions)]
+    {
+        use crate::garbage::WARNINGS;
+
+        WARNINGS.with_borrow_mut(Vec::clear);
+        drop(gc);
+        let warnings = WARNINGS.with_borrow_mut(std::mem::take);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].starts_with(r#"GcHandle of domain "cache" dropped"#),
+            "{}",
+            warnings[0]
+        );
+    }
+}
+
+/// 测试18: 复用的槽被完全重置，遗忘守卫的前一个持有者留下的纪元不会延续到新读者
+#[test]
+fn test_recycled_slot_is_reset() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .reader_slot_recycling(true)
+        .cleanup_interval(1)
+        .build();
+    let ptr = EpochPtr::new(0i32);
+
+    let local_epoch = domain.register_reader();
+    std::mem::forget(local_epoch.pin());
+    drop(local_epoch);
+    assert_eq!(gc.cleanup_dead_readers(), 1);
+    assert_eq!(
+        domain.shared.recycled_slots.as_ref().unwrap().lock().len(),
+        1
+    );
+
+    let local_epoch = domain.register_reader();
+    assert_eq!(
+        local_epoch.slot.active_epoch.load(Ordering::Relaxed),
+        INACTIVE_EPOCH
+    );
+
+    // 新读者未被钉住，因此不会阻挡回收
+    ptr.store(1, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
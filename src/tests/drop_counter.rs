@@ -0,0 +1,21 @@
+/// drop 计数值
+/// 在 drop 时递增共享计数器，用于验证值被回收的时机和次数
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 携带一个负载的值，drop 时递增共享的计数器
+#[derive(Clone)]
+pub(crate) struct Counted<T = ()>(pub(crate) T, pub(crate) Arc<AtomicUsize>);
+
+impl Counted {
+    /// 不带负载的计数值
+    pub(crate) fn new(drops: &Arc<AtomicUsize>) -> Self {
+        Counted((), Arc::clone(drops))
+    }
+}
+
+impl<T> Drop for Counted<T> {
+    fn drop(&mut self) {
+        self.1.fetch_add(1, Ordering::SeqCst);
+    }
+}
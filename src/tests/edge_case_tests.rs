@@ -35,7 +35,7 @@ fn test_exactly_reach_reclaim_threshold() {
 
     // 退休 64 个数据（AUTO_RECLAIM_THRESHOLD = 64）
     for i in 0..64 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 应该还没有自动回收
@@ -58,7 +58,7 @@ fn test_exceed_reclaim_threshold() {
 
     // 退休 100 个数据
     for i in 0..100 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 由于没有活跃读取者，垃圾会被回收
@@ -237,7 +237,7 @@ fn test_writer_cleanup_on_drop() {
         let (mut gc, _domain) = EpochGcDomain::new();
 
         for i in 0..50 {
-            gc.retire(Box::new(i as i32));
+            gc.retire(Box::new(i));
         }
 
         // gc 在这里被 drop
@@ -269,7 +269,7 @@ fn test_alternating_epoch_advancement() {
     for cycle in 0..10 {
         // 在每个循环中退休大量数据
         for i in 0..100 {
-            gc.retire(Box::new((cycle * 100 + i) as i32));
+            gc.retire(Box::new(cycle * 100 + i));
         }
 
         // 触发回收
@@ -317,7 +317,7 @@ fn test_garbage_protection_across_epochs() {
     {
         let _guard = local_epoch.pin();
         for i in 0..50 {
-            gc.retire(Box::new(i as i32));
+            gc.retire(Box::new(i));
         }
 
         // 垃圾应该被保留
@@ -380,3 +380,46 @@ fn test_stress_high_frequency_operations() {
         }
     }
 }
+
+/// 测试21: 大型数据结构在堆上原地初始化
+#[test]
+fn test_store_in_place_large_data() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    struct LargeData {
+        data: [u64; 1000],
+    }
+
+    let ptr = EpochPtr::new(LargeData { data: [0; 1000] });
+    for round in 1..=3u64 {
+        unsafe {
+            ptr.store_in_place(
+                |slot| {
+                    let data = std::ptr::addr_of_mut!((*slot.as_mut_ptr()).data).cast::<u64>();
+                    for i in 0..1000 {
+                        data.add(i).write(round * 1000 + i as u64);
+                    }
+                },
+                &mut gc,
+            );
+        }
+
+        let guard = local_epoch.pin();
+        let value = ptr.load(&guard);
+        assert!(
+            value
+                .data
+                .iter()
+                .enumerate()
+                .all(|(i, &x)| x == round * 1000 + i as u64)
+        );
+    }
+
+    // 初始化过程中 panic 时，指针保持不变
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+        ptr.store_in_place(|_| panic!("init failed"), &mut gc);
+    }));
+    assert!(result.is_err());
+    assert_eq!(ptr.load(&local_epoch.pin()).data[999], 3999);
+}
@@ -0,0 +1,1287 @@
+/// GcHandle API 测试模块
+/// 测试写入者侧的观测、配置和回收控制接口
+use super::drop_counter::Counted;
+use crate::{EpochGcDomain, EpochPtr, Generation, LagStats, POISON_BYTE};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// 测试1: 待回收纪元数量
+#[test]
+fn test_pending_epochs() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    assert_eq!(gc.pending_epochs(), 0);
+
+    // 读取者钉住在纪元 0，阻止所有回收
+    let guard = local_epoch.pin();
+
+    gc.retire(Box::new(1i32));
+    gc.collect();
+    gc.retire(Box::new(2i32));
+    gc.collect();
+    gc.retire(Box::new(3i32));
+
+    // 三个不同的纪元各持有一袋垃圾
+    assert_eq!(gc.pending_epochs(), 3);
+
+    // 读取者释放后，回收应该清空所有纪元
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.pending_epochs(), 0);
+}
+
+/// 测试2: 有截止时间的回收在多次调用间轮转推进
+#[test]
+fn test_collect_until_resumes_across_calls() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let dropped = Arc::new(AtomicUsize::new(0));
+
+    // 在读取者保护下，于四个不同纪元各退休两个对象
+    let guard = local_epoch.pin();
+    for _ in 0..4 {
+        gc.retire(Box::new(Counted::new(&dropped)));
+        gc.retire(Box::new(Counted::new(&dropped)));
+        gc.collect();
+    }
+    assert_eq!(gc.pending_epochs(), 4);
+    drop(guard);
+
+    // 截止时间已过：每次调用只回收一个袋子，但会从上次停止处继续
+    for remaining in (0..4).rev() {
+        let done = gc.collect_until(Instant::now());
+        assert_eq!(gc.pending_epochs(), remaining);
+        assert_eq!(done, remaining == 0);
+    }
+
+    // 每个袋子最终都被回收
+    assert_eq!(dropped.load(Ordering::SeqCst), 8);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试3: 截止时间充足时一次回收全部
+#[test]
+fn test_collect_until_with_generous_deadline() {
+    let (mut gc, _domain) = EpochGcDomain::new();
+
+    for i in 0..10 {
+        gc.retire(Box::new(i));
+    }
+
+    assert!(gc.collect_until(Instant::now() + Duration::from_secs(60)));
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试4: 合并另一个句柄的垃圾
+#[test]
+fn test_absorb_other_handle() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let (mut old_gc, _old_domain) = EpochGcDomain::new();
+    let dropped = Arc::new(AtomicUsize::new(0));
+
+    // 旧域的纪元编号与新域不同
+    for _ in 0..3 {
+        old_gc.collect();
+    }
+    for _ in 0..5 {
+        old_gc.retire(Box::new(Counted::new(&dropped)));
+    }
+
+    // 新域中有一个被钉住的读取者
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+
+    // 旧域中没有读者
+    unsafe { gc.absorb(old_gc) };
+    assert_eq!(gc.total_garbage_count(), 5);
+    assert_eq!(gc.pending_epochs(), 1);
+
+    // 读取者仍被钉住，合并来的垃圾不能被回收
+    gc.collect();
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(dropped.load(Ordering::SeqCst), 5);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试5: 按类型统计待回收垃圾
+#[cfg(debug_assertions)]
+#[test]
+fn test_retired_type_counts() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let _guard = local_epoch.pin();
+
+    gc.retire(Box::new(1i32));
+    gc.retire(Box::new(2i32));
+    gc.retire(Box::new(String::from("leak")));
+    gc.retire(Box::new(vec![0u8; 4]));
+    gc.retire(Box::new(3i32));
+
+    let counts = gc.retired_type_counts();
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[std::any::type_name::<i32>()], 3);
+    assert_eq!(counts[std::any::type_name::<String>()], 1);
+    assert_eq!(counts[std::any::type_name::<Vec<u8>>()], 1);
+}
+
+/// 测试6: 运行时调整自动回收阈值
+#[test]
+fn test_set_auto_reclaim_threshold_at_runtime() {
+    let (mut gc, domain) = EpochGcDomain::builder().auto_reclaim_threshold(4).build();
+
+    for i in 0..5 {
+        gc.retire(Box::new(i));
+    }
+    // 超过阈值 4，触发了自动回收
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(domain.epoch(), 1);
+
+    // 提高阈值后同样数量的退休不再触发回收
+    gc.set_auto_reclaim_threshold(100);
+    for i in 0..5 {
+        gc.retire(Box::new(i));
+    }
+    assert_eq!(gc.total_garbage_count(), 5);
+    assert_eq!(domain.epoch(), 1);
+
+    // 禁用自动回收
+    gc.set_auto_reclaim_threshold(None);
+    for i in 0..200 {
+        gc.retire(Box::new(i));
+    }
+    assert_eq!(gc.total_garbage_count(), 205);
+    assert_eq!(domain.epoch(), 1);
+}
+
+/// 测试7: 运行时调整清理间隔
+#[test]
+fn test_set_cleanup_interval_at_runtime() {
+    let (mut gc, domain) = EpochGcDomain::builder().cleanup_interval(0).build();
+
+    drop(domain.register_reader());
+    gc.collect();
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+
+    gc.set_cleanup_interval(1);
+    gc.collect();
+    assert!(domain.shared.readers.lock().is_empty());
+}
+
+/// 测试8: 隔离模式下已回收对象的内存被毒化
+#[test]
+fn test_assert_safe_reclaim_quarantine() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    gc.assert_safe_reclaim();
+    let local_epoch = domain.register_reader();
+    let dropped = Arc::new(AtomicUsize::new(0));
+
+    let ptr = EpochPtr::new(Counted([1u64; 4], dropped.clone()));
+    let guard = local_epoch.pin();
+    let old_value = ptr.load(&guard);
+    assert_eq!(old_value.0, [1; 4]);
+    let old_addr = old_value as *const Counted<[u64; 4]> as *const ();
+
+    ptr.store(Counted([2; 4], dropped.clone()), &mut gc);
+    gc.collect();
+
+    // 读取者仍被钉住，旧值既没有被 drop 也没有被隔离
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+    assert!(!gc.is_quarantined(old_addr));
+
+    drop(guard);
+    gc.collect();
+
+    // 旧值的析构函数已运行，但内存保留在隔离区并被毒化
+    assert_eq!(dropped.load(Ordering::SeqCst), 1);
+    assert_eq!(gc.quarantined_count(), 1);
+    assert!(gc.is_quarantined(old_addr));
+    assert!(gc.quarantine_intact());
+    let bytes = unsafe {
+        std::slice::from_raw_parts(
+            old_addr as *const u8,
+            std::mem::size_of::<Counted<[u64; 4]>>(),
+        )
+    };
+    assert!(bytes.iter().all(|&byte| byte == POISON_BYTE));
+
+    gc.clear_quarantine();
+    assert_eq!(gc.quarantined_count(), 0);
+}
+
+/// 测试9: 批量写入的旧值共享同一个纪元袋子
+#[test]
+fn test_write_batch_shares_one_epoch_bag() {
+    // 阈值为 1 时，逐个 store 会在中途触发自动回收并推进纪元
+    let (mut gc, domain) = EpochGcDomain::builder().auto_reclaim_threshold(1).build();
+    let local_epoch = domain.register_reader();
+    let ptrs: Vec<_> = (0..3).map(EpochPtr::new).collect();
+
+    let guard = local_epoch.pin();
+    for (i, ptr) in ptrs.iter().enumerate() {
+        ptr.store(i + 10, &mut gc);
+    }
+    assert!(gc.pending_epochs() > 1);
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 批量写入时，所有旧值都落在同一个袋子中
+    let guard = local_epoch.pin();
+    let mut batch = gc.batch();
+    for (i, ptr) in ptrs.iter().enumerate() {
+        batch.store(ptr, i + 20);
+    }
+    assert_eq!(batch.len(), 3);
+    batch.commit();
+
+    assert_eq!(gc.total_garbage_count(), 3);
+    assert_eq!(gc.pending_epochs(), 1);
+    for (i, ptr) in ptrs.iter().enumerate() {
+        assert_eq!(*ptr.load(&guard), i + 20);
+    }
+
+    // 它们也会一起被回收
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试10: 经过 K 次回收仍存活的垃圾被提升到老年代
+#[test]
+fn test_collect_generation_promotes_after_k_cycles() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .promote_after(3)
+        .build();
+    let slow_reader = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+
+    let guard = slow_reader.pin();
+    ptr.store(1, &mut gc);
+
+    // 前两次年轻代回收后仍留在年轻代
+    for _ in 0..2 {
+        gc.collect_generation(Generation::Young);
+        assert_eq!(gc.garbage.old_len(), 0);
+        assert_eq!(gc.total_garbage_count(), 1);
+    }
+
+    // 第三次后被提升
+    gc.collect_generation(Generation::Young);
+    assert_eq!(gc.garbage.old_len(), 1);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    // 新垃圾仍然进入年轻代
+    ptr.store(2, &mut gc);
+    assert_eq!(gc.garbage.old_len(), 1);
+    assert_eq!(gc.total_garbage_count(), 2);
+    assert_eq!(gc.pending_epochs(), 2);
+    drop(guard);
+}
+
+/// 测试11: 被提升的垃圾最终被完整回收
+#[test]
+fn test_collect_generation_eventual_reclamation() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .promote_after(1)
+        .build();
+    let slow_reader = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+
+    let guard = slow_reader.pin();
+    ptr.store(1, &mut gc);
+    gc.collect_generation(Generation::Young);
+    assert_eq!(gc.garbage.old_len(), 1);
+    drop(guard);
+
+    // 年轻代回收不会扫描老年代，但会回收年轻的垃圾
+    ptr.store(2, &mut gc);
+    gc.collect_generation(Generation::Young);
+    assert_eq!(gc.garbage.old_len(), 1);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    // 完整回收会回收所有垃圾
+    gc.collect_generation(Generation::Old);
+    assert_eq!(gc.garbage.old_len(), 0);
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 提升过的垃圾在 collect() 中同样可以被回收
+    let guard = slow_reader.pin();
+    ptr.store(3, &mut gc);
+    gc.collect_generation(Generation::Young);
+    assert_eq!(gc.garbage.old_len(), 1);
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试12: 退休令牌只在保护它的读者解除钉住并回收后才翻转
+#[test]
+fn test_retire_token_flips_after_unpin_and_collect() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("old"));
+
+    let guard = local_epoch.pin();
+    let old = ptr.load(&guard);
+    let token = ptr.store_tracked(String::from("new"), &mut gc).unwrap();
+    assert!(!token.is_reclaimed());
+
+    // 读者仍被钉住，回收不能释放旧值
+    gc.collect();
+    assert!(!token.is_reclaimed());
+    assert_eq!(old, "old");
+
+    // 仅解除钉住还不够，需要回收运行
+    drop(guard);
+    assert!(!token.is_reclaimed());
+    gc.collect();
+    assert!(token.is_reclaimed());
+
+    // 空指针没有旧值可以跟踪
+    let empty = EpochPtr::<i32>::null();
+    assert!(empty.store_tracked(1, &mut gc).is_none());
+}
+
+/// 测试13: 回收报告显示死读者槽被清理
+#[test]
+fn test_collect_detailed_reports_dead_reader_cleanup() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .cleanup_interval(4)
+        .build();
+    let _alive = domain.register_reader();
+    drop(domain.register_reader());
+
+    let ptr = EpochPtr::new(0i32);
+    ptr.store(1, &mut gc);
+    ptr.store(2, &mut gc);
+
+    let report = gc.collect_detailed();
+    assert_eq!(report.reclaimed, 2);
+    assert_eq!(report.dead_readers_removed, 0);
+    assert_eq!(report.epoch, 1);
+
+    // 第 4 个周期触发清理
+    let reports: Vec<_> = (0..3).map(|_| gc.collect_detailed()).collect();
+    assert_eq!(reports[0].dead_readers_removed, 0);
+    assert_eq!(reports[1].dead_readers_removed, 0);
+    assert_eq!(reports[2].dead_readers_removed, 1);
+    assert_eq!(reports[2].reclaimed, 0);
+    assert_eq!(reports[2].epoch, 4);
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+}
+
+/// 测试14: 读者提议、写入者提交的跨线程交接
+#[test]
+fn test_commit_if_reader_proposal_handoff() {
+    struct Proposal {
+        expected: usize,
+        value: String,
+    }
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = Arc::new(EpochPtr::new(String::from("v1")));
+    let (proposal_tx, proposal_rx) = std::sync::mpsc::channel::<Proposal>();
+    let (decision_tx, decision_rx) = std::sync::mpsc::channel::<bool>();
+
+    let reader = {
+        let domain = domain.clone();
+        let ptr = ptr.clone();
+        std::thread::spawn(move || {
+            let local_epoch = domain.register_reader();
+            // 读者在被钉住期间捕获指针标识并等待写入者的决定
+            for _ in 0..2 {
+                let guard = local_epoch.pin();
+                let current = ptr.load(&guard);
+                proposal_tx
+                    .send(Proposal {
+                        expected: current as *const String as usize,
+                        value: format!("{current}+"),
+                    })
+                    .unwrap();
+                let committed = decision_rx.recv().unwrap();
+                drop(guard);
+                if !committed {
+                    return false;
+                }
+            }
+            true
+        })
+    };
+
+    // 第一个提议：指针未变，提交成功
+    let proposal = proposal_rx.recv().unwrap();
+    let result = gc.commit_if(
+        &ptr,
+        proposal.expected as *const String,
+        proposal.value,
+        |current| current == "v1",
+    );
+    assert!(result.is_ok());
+    decision_tx.send(result.is_ok()).unwrap();
+
+    // 第二个提议：写入者在此期间更新了指针，提交失败
+    let proposal = proposal_rx.recv().unwrap();
+    ptr.store(String::from("v3"), &mut gc);
+    let result = gc.commit_if(
+        &ptr,
+        proposal.expected as *const String,
+        proposal.value,
+        |_| true,
+    );
+    assert_eq!(result, Err(String::from("v1++")));
+    decision_tx.send(result.is_ok()).unwrap();
+
+    assert!(!reader.join().unwrap());
+    let local_epoch = domain.register_reader();
+    assert_eq!(ptr.load(&local_epoch.pin()), "v3");
+}
+
+/// 测试15: 没有读者时关闭会回收所有垃圾
+#[test]
+fn test_shutdown_drains_all_garbage() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    // 一个已注册但未被钉住的读者使旧值进入垃圾队列
+    let _local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+    for i in 1..=10 {
+        ptr.store(i, &mut gc);
+    }
+
+    let report = gc.shutdown(Instant::now() + Duration::from_secs(1));
+    assert_eq!(report.reclaimed, 10);
+    assert_eq!(report.leaked, 0);
+    assert_eq!(report.pinned_readers, 0);
+    assert!(report.is_clean());
+}
+
+/// 测试16: 残留的被钉住读者会在关闭报告中被标记，其可见的垃圾不会被释放
+#[test]
+fn test_shutdown_flags_lingering_reader() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("old"));
+
+    let guard = local_epoch.pin();
+    let old = ptr.load(&guard);
+    ptr.store(String::from("new"), &mut gc);
+
+    let report = gc.shutdown(Instant::now() + Duration::from_millis(20));
+    assert_eq!(report.reclaimed, 0);
+    assert_eq!(report.leaked, 1);
+    assert_eq!(report.pinned_readers, 1);
+    assert!(!report.is_clean());
+
+    // 旧值被泄漏而不是被释放，读者仍可安全访问
+    assert_eq!(old, "old");
+}
+
+/// 测试17: 没有读者时回收不会获取读者锁
+#[test]
+fn test_collect_skips_readers_lock_without_readers() {
+    use crate::state::READERS_LOCKS;
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .cleanup_interval(1)
+        .build();
+    let ptr = EpochPtr::new(0i32);
+    ptr.store(1, &mut gc);
+
+    READERS_LOCKS.set(0);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(READERS_LOCKS.get(), 0);
+
+    // 读者注册后走正常路径；读者被清理后计数回到零
+    drop(domain.register_reader());
+    assert_eq!(domain.shared.reader_count.load(Ordering::SeqCst), 1);
+    gc.collect();
+    assert!(READERS_LOCKS.get() > 0);
+    assert_eq!(domain.shared.reader_count.load(Ordering::SeqCst), 0);
+
+    READERS_LOCKS.set(0);
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(READERS_LOCKS.get(), 0);
+}
+
+/// 测试18: 按需清理死读者槽
+#[test]
+fn test_cleanup_dead_readers_on_demand() {
+    let (mut gc, domain) = EpochGcDomain::builder().cleanup_interval(0).build();
+    let alive = domain.register_reader();
+    let wave: Vec<_> = (0..5).map(|_| domain.register_reader()).collect();
+    drop(wave);
+
+    // 周期性清理被禁用，回收不会移除它们
+    gc.collect();
+    assert_eq!(domain.shared.readers.lock().len(), 6);
+
+    assert_eq!(gc.cleanup_dead_readers(), 5);
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+    assert_eq!(gc.cleanup_dead_readers(), 0);
+
+    // 存活的读者不受影响
+    let guard = alive.pin();
+    let ptr = EpochPtr::new(1i32);
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 1);
+    drop(guard);
+}
+
+/// 测试19: 使用自定义析构函数退休非 Box 的内存
+#[test]
+fn test_retire_raw_with_custom_dtor() {
+    use std::alloc::{Layout, alloc, dealloc};
+
+    static FREED: AtomicUsize = AtomicUsize::new(0);
+    const LAYOUT: Layout = match Layout::from_size_align(32, 8) {
+        Ok(layout) => layout,
+        Err(_) => panic!(),
+    };
+
+    // 模拟 malloc/free 风格的分配
+    unsafe fn free_raw(ptr: *mut ()) {
+        unsafe { dealloc(ptr as *mut u8, LAYOUT) };
+        FREED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+
+    let guard = local_epoch.pin();
+    let raw = unsafe { alloc(LAYOUT) };
+    assert!(!raw.is_null());
+    unsafe { gc.retire_raw(raw as *mut (), free_raw) };
+
+    gc.collect();
+    assert_eq!(FREED.load(Ordering::SeqCst), 0);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(FREED.load(Ordering::SeqCst), 1);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试20: 自动回收受批大小限制，手动回收仍然完整
+#[test]
+fn test_collect_batch_size() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(8)
+        .collect_batch_size(3)
+        .build();
+    let _local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+
+    for i in 1..=9 {
+        ptr.store(i, &mut gc);
+    }
+    // 第 9 次退休触发自动回收，但只回收了 3 个对象
+    assert_eq!(gc.total_garbage_count(), 6);
+
+    for i in 10..=12 {
+        ptr.store(i, &mut gc);
+    }
+    assert_eq!(gc.total_garbage_count(), 6);
+
+    // 剩余的垃圾由手动回收一次性回收
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试21: 使用指定的最小活跃纪元精确控制回收哪些纪元的袋子
+#[test]
+fn test_collect_with_min_boundary() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+
+    // 在纪元 0..=3 中各退休一个对象，被钉住的读者阻止回收
+    let guard = local_epoch.pin();
+    for value in 1..=4 {
+        ptr.store(value, &mut gc);
+        gc.collect();
+    }
+    drop(guard);
+    assert_eq!(domain.epoch(), 4);
+    assert_eq!(gc.pending_epochs(), 4);
+
+    // 最小值 2：只有纪元 0 和 1 的袋子被回收
+    unsafe { gc.collect_with_min(2) };
+    assert_eq!(domain.epoch(), 5);
+    assert_eq!(gc.pending_epochs(), 2);
+    assert_eq!(gc.total_garbage_count(), 2);
+
+    // 最小值 0：什么都不回收
+    unsafe { gc.collect_with_min(0) };
+    assert_eq!(gc.pending_epochs(), 2);
+
+    // 最小值等于新纪元：全部回收
+    unsafe { gc.collect_with_min(7) };
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试22: 最小活跃纪元不能晚于新纪元
+#[test]
+#[should_panic(expected = "newer than the new epoch")]
+fn test_collect_with_min_rejects_future_epoch() {
+    let (mut gc, _domain) = EpochGcDomain::new();
+    unsafe { gc.collect_with_min(5) };
+}
+
+/// 测试23: 回收耗时直方图记录每一次 collect
+#[cfg(feature = "metrics")]
+#[test]
+fn test_collect_latency_histogram() {
+    use crate::COLLECT_LATENCY_BUCKETS;
+
+    let (mut gc, _domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    assert_eq!(gc.collect_latency_histogram(), [0; COLLECT_LATENCY_BUCKETS]);
+
+    let ptr = EpochPtr::new(vec![0u8; 64]);
+    let mut collects = 0;
+    for garbage in [0, 10, 1000, 20_000] {
+        for _ in 0..garbage {
+            ptr.store(vec![1u8; 64], &mut gc);
+        }
+        gc.collect();
+        collects += 1;
+    }
+
+    let histogram = gc.collect_latency_histogram();
+    assert_eq!(histogram.iter().sum::<u64>(), collects);
+}
+
+/// 测试24: 没有注册读者时 store 立即释放旧值，垃圾队列从不增长
+#[test]
+fn test_writer_only_store_frees_immediately() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let ptr = EpochPtr::new(Counted::new(&drops));
+
+    for i in 1..=1000 {
+        ptr.store(Counted::new(&drops), &mut gc);
+        assert_eq!(gc.total_garbage_count(), 0);
+        assert_eq!(drops.load(Ordering::SeqCst), i);
+    }
+    assert_eq!(domain.epoch(), 0);
+
+    // 一旦有读者注册，旧值又会被退休
+    let local_epoch = domain.register_reader();
+    ptr.store(Counted::new(&drops), &mut gc);
+    assert_eq!(gc.total_garbage_count(), 1);
+    assert_eq!(drops.load(Ordering::SeqCst), 1000);
+    drop(local_epoch);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 1001);
+}
+
+/// 测试25: 启用 panic_on_reader_leak 时，drop 句柄时仍被钉住的读者会导致 panic
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "reader(s) are still pinned")]
+fn test_panic_on_reader_leak() {
+    let (gc, domain) = EpochGcDomain::builder().panic_on_reader_leak(true).build();
+    let local_epoch = domain.register_reader();
+    let _guard = local_epoch.pin();
+    drop(gc);
+}
+
+/// 测试26: 读者都未被钉住时 drop 句柄不会 panic，未启用时也不会
+#[test]
+fn test_panic_on_reader_leak_clean_drop() {
+    let (gc, domain) = EpochGcDomain::builder().panic_on_reader_leak(true).build();
+    let local_epoch = domain.register_reader();
+    drop(local_epoch.pin());
+    drop(gc);
+
+    let (gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let _guard = local_epoch.pin();
+    drop(gc);
+}
+
+/// 测试27: 增量扫描跳过稳定的读者，同时仍能发现它们解除钉住
+#[test]
+fn test_incremental_scan_with_stable_readers() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .cleanup_interval(0)
+        .build();
+    let ptr = EpochPtr::new(0usize);
+    let min_active_epoch =
+        |gc: &crate::GcHandle| gc.shared.min_active_epoch.load(Ordering::Acquire);
+
+    // 许多长期存活的读者都被钉住在同一个旧纪元
+    gc.collect();
+    let stable_epoch = domain.epoch();
+    let stable: Vec<_> = (0..32).map(|_| domain.register_reader()).collect();
+    let mut guards: Vec<_> = stable.iter().map(|reader| Some(reader.pin())).collect();
+    let churning = domain.register_reader();
+
+    for i in 1..=20 {
+        let churn_guard = churning.pin();
+        ptr.store(i, &mut gc);
+        gc.collect();
+        drop(churn_guard);
+        assert_eq!(min_active_epoch(&gc), stable_epoch);
+        assert!(gc.min_holder.is_some());
+    }
+
+    // 逐个解除钉住被记录为最小值持有者的读者：只要还有读者停留在旧纪元，最小值就不变
+    for _ in 0..31 {
+        let holder = gc.min_holder.unwrap();
+        guards[holder] = None;
+        gc.collect();
+        assert_eq!(min_active_epoch(&gc), stable_epoch);
+    }
+    assert_eq!(gc.total_garbage_count(), 20);
+
+    // 最后一个稳定的读者解除钉住后，最小值前进，所有垃圾都被回收
+    guards.clear();
+    let churn_guard = churning.pin();
+    gc.collect();
+    assert_eq!(min_active_epoch(&gc), domain.epoch() - 1);
+    assert_eq!(gc.total_garbage_count(), 0);
+    drop(churn_guard);
+    gc.collect();
+    assert_eq!(min_active_epoch(&gc), domain.epoch());
+    assert_eq!(gc.min_holder, None);
+}
+
+/// 测试28: 回收报告中的纪元范围与被清空的袋子一致
+#[test]
+fn test_collect_detailed_reclaimable_epochs() {
+    let dropped = Arc::new(std::sync::Mutex::new(Vec::new()));
+    struct RetiredAt(usize, Arc<std::sync::Mutex<Vec<usize>>>);
+    impl Drop for RetiredAt {
+        fn drop(&mut self) {
+            self.1.lock().unwrap().push(self.0);
+        }
+    }
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let first = domain.register_reader();
+    let second = domain.register_reader();
+
+    // 在纪元 0、1、2 各退休一个对象：第一个读者钉住于纪元 0，第二个钉住于纪元 1
+    let first_guard = first.pin();
+    let mut guard = None;
+    for epoch in 0..3 {
+        assert_eq!(domain.epoch(), epoch);
+        if epoch == 1 {
+            guard = Some(second.pin());
+        }
+        gc.retire(Box::new(RetiredAt(epoch, dropped.clone())));
+        if epoch < 2 {
+            gc.collect();
+        }
+    }
+    assert!(dropped.lock().unwrap().is_empty());
+
+    // 只剩纪元 1 的读者：只有纪元 0 的袋子被清空
+    drop(first_guard);
+    let report = gc.collect_detailed();
+    assert_eq!(report.safe_epoch, Some(0));
+    assert_eq!(report.reclaimable_epochs(), Some(0..=0));
+    assert_eq!(*dropped.lock().unwrap(), vec![0]);
+    assert_eq!(report.reclaimed, 1);
+
+    // 读者解除钉住后，其余的袋子都被清空
+    drop(guard);
+    let report = gc.collect_detailed();
+    assert_eq!(report.epoch, 4);
+    assert_eq!(report.reclaimable_epochs(), Some(0..=3));
+    assert_eq!(*dropped.lock().unwrap(), vec![0, 1, 2]);
+
+    // 被钉住于纪元 0 的读者使范围为空
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let _guard = local_epoch.pin();
+    gc.retire(Box::new(RetiredAt(0, dropped.clone())));
+    let report = gc.collect_detailed();
+    assert_eq!(report.reclaimable_epochs(), None);
+    assert_eq!(report.reclaimed, 0);
+}
+
+/// 测试29: 空闲的写入者在辅助线程的请求下回收垃圾，无需再次 store
+#[test]
+fn test_auto_collect_on_idle() {
+    enum Message {
+        Store,
+        Collect,
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (mut gc, domain) = {
+        let tx = tx.clone();
+        EpochGcDomain::builder()
+            .auto_collect_on_idle(Duration::from_millis(10), move || {
+                let _ = tx.send(Message::Collect);
+            })
+            .build()
+    };
+    let ptr = EpochPtr::new(Counted::new(&drops));
+    let local_epoch = domain.register_reader();
+
+    // 一次突发写入，期间读者被钉住，因此第一次空闲回收无法释放任何东西
+    let guard = local_epoch.pin();
+    for _ in 0..5 {
+        tx.send(Message::Store).unwrap();
+    }
+    let (mut stored, mut served) = (0, false);
+    while stored < 5 || !served {
+        match rx.recv_timeout(Duration::from_secs(30)).unwrap() {
+            Message::Store => {
+                ptr.store(Counted::new(&drops), &mut gc);
+                stored += 1;
+            }
+            Message::Collect => served |= gc.collect_if_requested(),
+        }
+    }
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    assert_eq!(gc.total_garbage_count(), 5);
+
+    // 读者解除钉住后，没有任何 store，辅助线程的下一次请求回收了所有垃圾
+    drop(guard);
+    while gc.total_garbage_count() > 0 {
+        match rx.recv_timeout(Duration::from_secs(30)).unwrap() {
+            Message::Store => unreachable!(),
+            Message::Collect => {
+                gc.collect_if_requested();
+            }
+        }
+    }
+    assert_eq!(drops.load(Ordering::SeqCst), 5);
+    assert!(!gc.collect_if_requested());
+}
+
+/// 测试30: 调试构建中重复退休同一个指针会立即 panic，而不是导致双重释放
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "was retired twice")]
+fn test_double_retire_detected() {
+    unsafe fn drop_u64(ptr: *mut ()) {
+        drop(unsafe { Box::from_raw(ptr as *mut u64) });
+    }
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let _local_epoch = domain.register_reader();
+
+    let raw = Box::into_raw(Box::new(5u64)) as *mut ();
+    unsafe {
+        gc.retire_raw(raw, drop_u64);
+        gc.retire_raw(raw, drop_u64);
+    }
+}
+
+/// 测试31: 回收之后地址可以被再次退休，零大小的值不会被误报
+#[test]
+fn test_retire_after_reclaim_and_zero_sized() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+
+    // 被回收的分配可能被分配器复用，不能被视为重复退休
+    for i in 0..100u64 {
+        gc.retire(Box::new(i));
+        gc.collect();
+    }
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 所有零大小的 Box 共享同一个悬垂地址
+    let ptr = EpochPtr::new(());
+    let _guard = local_epoch.pin();
+    for _ in 0..10 {
+        ptr.store((), &mut gc);
+    }
+    assert_eq!(gc.total_garbage_count(), 10);
+}
+
+/// 测试32: drain_safe 交还可回收的对象供调用者向下转型复用，仍受保护的对象保留在队列中
+#[test]
+fn test_drain_safe_downcast() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut gc, domain) = EpochGcDomain::new();
+    let first = domain.register_reader();
+    let second = domain.register_reader();
+    let ptr = EpochPtr::new(vec![0u8; 16]);
+
+    // 纪元 0 中退休的值，以及一个没有类型信息的原始指针对象
+    let guard = first.pin();
+    ptr.store(vec![1u8; 32], &mut gc);
+    ptr.store(vec![2u8; 64], &mut gc);
+    unsafe {
+        let raw = Box::into_raw(Box::new(drops.clone())) as *mut ();
+        gc.retire_raw(raw, |ptr| drop(Box::from_raw(ptr as *mut Arc<AtomicUsize>)));
+    }
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 3);
+
+    // 纪元 1 中退休的值仍受第二个读者保护
+    let protecting = second.pin();
+    drop(guard);
+    ptr.store(vec![3u8; 128], &mut gc);
+
+    let drained: Vec<_> = gc.drain_safe().collect();
+    // 原始指针对象没有类型信息，被正常回收
+    assert_eq!(Arc::strong_count(&drops), 1);
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    let mut capacities: Vec<_> = drained
+        .into_iter()
+        .map(|value| {
+            let vec = value
+                .downcast::<Vec<u8>>()
+                .expect("retired value is a Vec<u8>");
+            vec.capacity()
+        })
+        .collect();
+    capacities.sort_unstable();
+    assert_eq!(capacities, [16, 32]);
+
+    drop(protecting);
+    let rest: Vec<_> = gc.drain_safe().collect();
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].downcast_ref::<Vec<u8>>().unwrap(), &vec![2u8; 64]);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试33: CollectGuard 在被 drop 时恰好回收一次，包括提前返回的路径
+#[test]
+fn test_collect_on_drop() {
+    fn update(gc: &mut crate::GcHandle, ptr: &EpochPtr<u32>, early: bool) -> Option<u32> {
+        let mut gc = gc.collect_on_drop();
+        ptr.store(1, &mut gc);
+        if early {
+            return None;
+        }
+        ptr.store(2, &mut gc);
+        Some(gc.total_garbage_count() as u32)
+    }
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0u32);
+
+    // 两次 store 的旧值在守卫存活期间仍在队列中
+    assert_eq!(update(&mut gc, &ptr, false), Some(2));
+    assert_eq!(domain.epoch(), 1);
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    assert_eq!(update(&mut gc, &ptr, true), None);
+    assert_eq!(domain.epoch(), 2);
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 被钉住的读者使守卫的回收保留垃圾，但回收仍然只运行一次
+    let guard = local_epoch.pin();
+    update(&mut gc, &ptr, false);
+    assert_eq!(domain.epoch(), 3);
+    assert_eq!(gc.total_garbage_count(), 2);
+    drop(guard);
+}
+
+/// 测试34: 合并相邻袋子减少待回收纪元数量，且不会提前回收仍被读者持有的对象
+#[test]
+fn test_coalesce_threshold() {
+    let build = |coalesce: bool| {
+        let builder = EpochGcDomain::builder().auto_reclaim_threshold(None);
+        let (mut gc, domain) = if coalesce {
+            builder.coalesce_threshold(4).build()
+        } else {
+            builder.build()
+        };
+        gc.assert_safe_reclaim();
+        (gc, domain)
+    };
+
+    // 不合并时，缓慢的读者使每次回收都留下一个袋子
+    let (mut gc, domain) = build(false);
+    let slow = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+    let guard = slow.pin();
+    for i in 1..=16 {
+        ptr.store(i, &mut gc);
+        gc.collect();
+    }
+    assert_eq!(gc.pending_epochs(), 16);
+    drop(guard);
+
+    let (mut gc, domain) = build(true);
+    let slow = domain.register_reader();
+    let fast = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+    let slow_guard = slow.pin();
+    for i in 1..=16 {
+        ptr.store(i, &mut gc);
+        gc.collect();
+        assert!(gc.pending_epochs() <= 4);
+    }
+    assert_eq!(gc.total_garbage_count(), 16);
+
+    // 较新的读者持有的值在它解除钉住之前不会被回收，即使它所在的袋子被合并
+    let fast_guard = fast.pin();
+    let held = ptr.load(&fast_guard);
+    let held_addr = held as *const usize as *const ();
+    for i in 17..=32 {
+        ptr.store(i, &mut gc);
+        gc.collect();
+    }
+    drop(slow_guard);
+    for _ in 0..8 {
+        gc.collect();
+        assert!(!gc.is_quarantined(held_addr));
+        assert_eq!(*held, 16);
+    }
+    assert!(gc.total_garbage_count() < 32);
+
+    drop(fast_guard);
+    gc.collect();
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert!(gc.is_quarantined(held_addr));
+    assert!(gc.quarantine_intact());
+}
+
+/// 测试35: 退休了垃圾却从未回收的句柄在 drop 时发出警告，回收过的句柄则不会
+#[cfg(debug_assertions)]
+#[test]
+fn test_warn_on_uncollected_drop() {
+    use crate::garbage::WARNINGS;
+
+    let take_warnings = || WARNINGS.with_borrow_mut(std::mem::take);
+    take_warnings();
+
+    // 禁用自动回收且从不调用 collect()，垃圾只能在 drop 时释放
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let _reader = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+    ptr.store(1, &mut gc);
+    ptr.store(2, &mut gc);
+    drop(gc);
+    let warnings = take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].contains("2 retired object(s)"),
+        "{}",
+        warnings[0]
+    );
+
+    // 回收过一次之后，drop 时剩余的垃圾属于正常情况
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let _reader = domain.register_reader();
+    ptr.store(3, &mut gc);
+    gc.collect();
+    ptr.store(4, &mut gc);
+    drop(gc);
+    assert!(take_warnings().is_empty());
+}
+
+/// 测试36: 回收途中设置取消标志会提前返回，剩余垃圾留给之后的调用
+#[test]
+fn test_collect_cancellable() {
+    use std::sync::atomic::AtomicBool;
+
+    // 第 100 个对象被 drop 时设置取消标志，模拟回收途中到来的关闭请求
+    struct CancelAt {
+        dropped: Arc<AtomicUsize>,
+        cancel: Arc<AtomicBool>,
+    }
+
+    impl Drop for CancelAt {
+        fn drop(&mut self) {
+            if self.dropped.fetch_add(1, Ordering::Relaxed) + 1 == 100 {
+                self.cancel.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let _local_epoch = domain.register_reader();
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let cancel = Arc::new(AtomicBool::new(false));
+    for _ in 0..1000 {
+        gc.retire(Box::new(CancelAt {
+            dropped: dropped.clone(),
+            cancel: cancel.clone(),
+        }));
+    }
+
+    let reclaimed = gc.collect_cancellable(&cancel);
+    assert!((100..1000).contains(&reclaimed), "reclaimed {reclaimed}");
+    assert_eq!(dropped.load(Ordering::Relaxed), reclaimed);
+    assert_eq!(gc.total_garbage_count(), 1000 - reclaimed);
+
+    // 标志仍被设置时不回收任何内容
+    assert_eq!(gc.collect_cancellable(&cancel), 0);
+
+    cancel.store(false, Ordering::Relaxed);
+    assert_eq!(gc.collect_cancellable(&cancel), 1000 - reclaimed);
+    assert_eq!(dropped.load(Ordering::Relaxed), 1000);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试37: 读者滞后统计按最近秩计算最小值、中位数和 p99，并忽略未被钉住的读者
+#[test]
+fn test_reader_lag_stats() {
+    let (gc, domain) = EpochGcDomain::new();
+    let readers: Vec<_> = (0..101).map(|_| domain.register_reader()).collect();
+    assert_eq!(gc.reader_lag_stats(), LagStats::default());
+    assert_eq!(domain.p99_reader_lag(), None);
+
+    for _ in 0..100 {
+        domain.advance_epoch_only();
+    }
+
+    // 100 个读者分别钉住在纪元 1..=100，滞后量为 99..=0；最后一个读者未被钉住
+    let guards: Vec<_> = readers[..100]
+        .iter()
+        .zip(1..=100)
+        .map(|(reader, epoch)| reader.pin_at(epoch))
+        .collect();
+
+    assert_eq!(
+        domain.reader_epochs_sorted(),
+        (1..=100).collect::<Vec<usize>>()
+    );
+    assert_eq!(
+        gc.reader_lag_stats(),
+        LagStats {
+            pinned: 100,
+            min: 0,
+            median: 49,
+            p99: 98,
+        }
+    );
+    assert_eq!(domain.p99_reader_lag(), Some(98));
+
+    drop(guards);
+    assert!(domain.reader_epochs_sorted().is_empty());
+    assert_eq!(gc.reader_lag_stats(), LagStats::default());
+}
+
+/// 测试38: 启用 panic_on_reader_leak 时，shutdown 仍会报告残留的被钉住读者而不是在 drop 句柄时 panic
+#[test]
+fn test_shutdown_reports_pinned_reader_with_panic_on_reader_leak() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .panic_on_reader_leak(true)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("old"));
+
+    let guard = local_epoch.pin();
+    let old = ptr.load(&guard);
+    ptr.store(String::from("new"), &mut gc);
+
+    let report = gc.shutdown(Instant::now() + Duration::from_millis(20));
+    assert_eq!(report.leaked, 1);
+    assert_eq!(report.pinned_readers, 1);
+    assert_eq!(old, "old");
+}
+
+/// 测试39: store_no_autocollect 退休的垃圾同样会让空闲辅助线程请求回收
+#[test]
+fn test_auto_collect_on_idle_after_store_no_autocollect() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_collect_on_idle(Duration::from_millis(10), move || {
+            let _ = tx.send(());
+        })
+        .build();
+    let _local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0u64);
+
+    for value in 1..=3 {
+        ptr.store_no_autocollect(value, &mut gc);
+    }
+    assert_eq!(gc.total_garbage_count(), 3);
+
+    while gc.total_garbage_count() > 0 {
+        rx.recv_timeout(Duration::from_secs(30)).unwrap();
+        gc.collect_if_requested();
+    }
+}
+
+/// 测试40: 启用自动回收、只做了少量 store 的短生命周期句柄在 drop 时不会发出警告
+#[cfg(debug_assertions)]
+#[test]
+fn test_no_warning_with_auto_reclaim() {
+    use crate::garbage::WARNINGS;
+
+    WARNINGS.with_borrow_mut(std::mem::take);
+    let (mut gc, domain) = EpochGcDomain::new();
+    let _reader = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+    for value in 1..=3 {
+        ptr.store(value, &mut gc);
+    }
+    assert_eq!(gc.total_garbage_count(), 3);
+    drop(gc);
+    assert!(WARNINGS.with_borrow(Vec::is_empty));
+}
+
+/// 测试41: 另一个域的读者延迟、尚未被取走的清理闭包随 absorb 一起被接管，并由此句柄运行
+#[test]
+fn test_absorb_takes_deferred_cleanups() {
+    let (mut gc, _domain) = EpochGcDomain::new();
+    let (old_gc, old_domain) = EpochGcDomain::new();
+    let ran = Arc::new(AtomicUsize::new(0));
+
+    {
+        let old_reader = old_domain.register_reader();
+        let guard = old_reader.pin();
+        let ran = ran.clone();
+        guard.defer(move || {
+            ran.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    // 旧域的读者都已解除钉住
+    unsafe { gc.absorb(old_gc) };
+    assert_eq!(gc.total_garbage_count(), 1);
+    drop(old_domain);
+    assert_eq!(ran.load(Ordering::SeqCst), 0);
+
+    gc.collect();
+    assert_eq!(ran.load(Ordering::SeqCst), 1);
+}
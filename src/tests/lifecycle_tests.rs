@@ -282,7 +282,7 @@ fn test_large_garbage_safe_reclamation() {
 
     // 退休大量数据
     for i in 0..1000 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 由于没有活跃读取者，垃圾会被回收
@@ -389,7 +389,7 @@ fn test_writer_garbage_management() {
     {
         let _guard = local_epoch.pin();
         for i in 0..50 {
-            gc.retire(Box::new(i as i32));
+            gc.retire(Box::new(i));
         }
 
         // 垃圾应该被保留
@@ -417,7 +417,7 @@ fn test_multiple_readers_garbage_protection() {
 
     // 退休数据
     for i in 0..100 {
-        gc.retire(Box::new(i as i32));
+        gc.retire(Box::new(i));
     }
 
     // 由于所有读取者都活跃，垃圾应该被保留
@@ -452,7 +452,7 @@ fn test_complete_lifecycle_scenario() {
 
         // 退休一些数据
         for i in 0..50 {
-            gc.retire(Box::new(i as i32));
+            gc.retire(Box::new(i));
         }
 
         // 再次推进纪元
@@ -0,0 +1,135 @@
+/// EpochList 测试模块
+/// 测试基于 EpochPtr 的单链表的正确性和节点回收
+use super::drop_counter::Counted;
+use crate::{EpochGcDomain, EpochList};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// 测试1: from_iter 保持迭代顺序
+#[test]
+fn test_from_iter_order() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let list: EpochList<i32> = (1..=5).collect();
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+
+    assert_eq!(
+        list.iter(&guard).copied().collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+    assert_eq!(list.front(&guard), Some(&1));
+    assert!(!list.is_empty());
+
+    let empty: EpochList<i32> = std::iter::empty().collect();
+    assert!(empty.is_empty());
+    assert_eq!(empty.front(&guard), None);
+}
+
+/// 测试2: push_front 和 pop_front 的正确性
+#[test]
+fn test_push_pop() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let list = EpochList::new();
+    let local_epoch = domain.register_reader();
+
+    for i in 0..4 {
+        list.push_front(i, &mut gc);
+    }
+    assert_eq!(
+        list.iter(&local_epoch.pin()).copied().collect::<Vec<_>>(),
+        [3, 2, 1, 0]
+    );
+
+    assert!(list.pop_front(&mut gc));
+    assert!(list.pop_front(&mut gc));
+    list.push_front(9, &mut gc);
+    assert_eq!(
+        list.iter(&local_epoch.pin()).copied().collect::<Vec<_>>(),
+        [9, 1, 0]
+    );
+
+    assert!(list.pop_front(&mut gc));
+    assert!(list.pop_front(&mut gc));
+    assert!(list.pop_front(&mut gc));
+    assert!(!list.pop_front(&mut gc));
+    assert!(list.is_empty());
+    gc.collect();
+}
+
+/// 测试3: 被弹出的节点只有在读者解除钉住之后才会被回收
+#[test]
+fn test_popped_nodes_reclaimed_after_unpin() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let list: EpochList<Counted<usize>> = (0..3).map(|i| Counted(i, drops.clone())).collect();
+    let local_epoch = domain.register_reader();
+
+    let guard = local_epoch.pin();
+    let front = list.front(&guard).unwrap();
+    assert!(list.pop_front(&mut gc));
+    assert!(list.pop_front(&mut gc));
+    gc.collect();
+
+    // 读者仍然可以访问被弹出的节点及其后继
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    assert_eq!(front.0, 0);
+    let mut rest = list.iter(&guard);
+    assert_eq!(rest.next().map(|c| c.0), Some(2));
+    drop(guard);
+
+    // 回收只 drop 被弹出的值，不会 drop 仍在链表中的节点
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+    assert_eq!(list.front(&local_epoch.pin()).map(|c| c.0), Some(2));
+
+    drop(list);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+/// 测试4: 长链表的 drop 不会栈溢出
+#[test]
+fn test_drop_long_list() {
+    let list: EpochList<usize> = (0..200_000).collect();
+    drop(list);
+}
+
+/// 测试5: 读者遍历时写入者并发压入和弹出
+#[test]
+fn test_concurrent_traversal() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let list: Arc<EpochList<Vec<usize>>> = Arc::new((0..16).map(|i| vec![i; 8]).collect());
+    let reads = Arc::new(AtomicUsize::new(0));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let domain = domain.clone();
+            let list = list.clone();
+            let reads = reads.clone();
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                while reads.load(Ordering::Relaxed) < 2000 {
+                    let guard = local_epoch.pin();
+                    for values in list.iter(&guard) {
+                        assert!(values.iter().all(|v| *v == values[0]));
+                    }
+                    reads.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    let mut next = 16;
+    while reads.load(Ordering::Relaxed) < 2000 {
+        list.pop_front(&mut gc);
+        list.push_front(vec![next; 8], &mut gc);
+        next += 1;
+    }
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    gc.collect();
+}
@@ -0,0 +1,396 @@
+/// loom 测试模块
+/// 在 loom 模型检查器下穷举读者与写入者之间的交错
+use crate::{EpochGcDomain, EpochPtr, ReadersBackend};
+use loom::sync::Arc;
+use loom::thread;
+
+/// 测试1: try_pin 与快速推进纪元的写入者竞争时，要么安全钉住，要么返回 None
+#[test]
+fn test_try_pin_races_advancing_writer() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(0usize));
+
+        let reader = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                if let Some(guard) = local_epoch.try_pin() {
+                    let value = *ptr.load(&guard);
+                    assert!(value <= 2);
+                }
+            })
+        };
+
+        for value in 1..=2 {
+            ptr.store(value, &mut gc);
+            gc.collect();
+        }
+
+        reader.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试2: 缓存的纪元路径永远不会钉住到一个不安全的纪元
+#[test]
+fn test_cached_epoch_never_pins_unsafe_epoch() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::builder().reader_epoch_cache(true).build();
+        let ptr = Arc::new(EpochPtr::new(0usize));
+
+        let reader = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                // 第一次 pin 填充缓存，第二次 pin 走缓存路径
+                for _ in 0..2 {
+                    let guard = local_epoch.pin();
+                    let value = *ptr.load(&guard);
+                    assert!(value <= 2);
+                }
+            })
+        };
+
+        for value in 1..=2 {
+            ptr.store(value, &mut gc);
+            gc.collect();
+        }
+
+        reader.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试3: 两个辅助线程通过 compare_and_set_with_backoff 竞争同一个指针，旧值恰好被回收一次
+#[test]
+fn test_compare_and_set_with_backoff_no_double_free() {
+    use loom::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counted(Arc<AtomicUsize>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let drops = Arc::new(AtomicUsize::new(0));
+        let ptr = Arc::new(EpochPtr::new(Counted(Arc::clone(&drops))));
+
+        let helpers: Vec<_> = (1..=2)
+            .map(|_| {
+                let (domain, ptr, drops) = (domain.clone(), Arc::clone(&ptr), Arc::clone(&drops));
+                thread::spawn(move || {
+                    let local_epoch = domain.register_reader();
+                    let guard = local_epoch.pin();
+                    let current = ptr.load(&guard);
+                    let new = Counted(drops);
+                    ptr.compare_and_set_with_backoff(current, new, &guard)
+                        .is_ok()
+                })
+            })
+            .collect();
+
+        let successes = helpers
+            .into_iter()
+            .map(|helper| helper.join().unwrap() as usize)
+            .sum::<usize>();
+        assert!(successes >= 1);
+
+        // 每个失败交还的值在辅助线程中被 drop，每个被替换的值在回收时被 drop
+        gc.collect();
+        assert_eq!(drops.load(Ordering::Relaxed), 2);
+        drop(ptr);
+        assert_eq!(drops.load(Ordering::Relaxed), 3);
+    });
+}
+
+/// 非原子地写入的载荷：loom 会检查每次读取和回收时的写入是否与构造它的线程建立了 happens-before 关系
+struct Payload(loom::cell::UnsafeCell<usize>);
+
+// 只在 loom 下使用，所有访问都经过 loom 的因果检查
+unsafe impl Sync for Payload {}
+
+impl Payload {
+    fn new(value: usize) -> Self {
+        Payload(loom::cell::UnsafeCell::new(value))
+    }
+
+    fn get(&self) -> usize {
+        self.0.with(|value| unsafe { *value })
+    }
+}
+
+impl Drop for Payload {
+    fn drop(&mut self) {
+        // 回收时写入，使被仍持有守卫的读者读取到的值回收会被 loom 报告为数据竞争
+        self.0.with_mut(|value| unsafe { *value = usize::MAX });
+    }
+}
+
+/// 测试4: 读者加载与写入者的 cas_tagged 交错时，总能看到完全初始化的值，且该值不会在守卫存活时被回收
+#[test]
+fn test_cas_tagged_publishes_and_protects() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let writer_local = gc.local_reader();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+
+        let reader = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let guard = local_epoch.pin();
+                assert!(ptr.load(&guard).get() <= 1);
+            })
+        };
+
+        {
+            let guard = writer_local.pin();
+            let expected = ptr.load_tagged(&guard);
+            assert!(ptr.cas_tagged(expected, Payload::new(1), &mut gc).is_ok());
+        }
+        gc.collect();
+
+        reader.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试5: 辅助线程通过 compare_and_set_with_backoff 发布的值被写入者的 store 替换并回收时，
+/// 写入者的回收与辅助线程对该值的构造之间存在 happens-before 关系
+#[test]
+fn test_writer_reclaims_value_published_by_helper() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+
+        let helper = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let guard = local_epoch.pin();
+                let current = ptr.load(&guard);
+                let _ = ptr.compare_and_set_with_backoff(current, Payload::new(1), &guard);
+            })
+        };
+
+        // 不经过 store() 的写入路径，被替换的值可能是辅助线程刚发布的
+        let mut batch = gc.batch();
+        batch.store(&ptr, Payload::new(2));
+        batch.commit();
+        ptr.store_tracked(Payload::new(3), &mut gc);
+
+        helper.join().unwrap();
+        gc.collect();
+        drop(ptr);
+    });
+}
+
+/// 测试6: 另一个线程通过 init_once 发布的值（无需钉住）被写入者的 store 变体替换、并在 drop 句柄时回收，
+/// 写入者的回收与该值的构造之间存在 happens-before 关系
+#[test]
+fn test_writer_reclaims_value_published_by_init_once() {
+    loom::model(|| {
+        let (mut gc, _domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::null());
+
+        let initializer = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let _ = ptr.init_once(Payload::new(1));
+            })
+        };
+
+        // 这些 store 变体不经过 collect()，被替换的值在 drop 句柄时才被回收
+        let mut batch = gc.batch();
+        batch.store(&ptr, Payload::new(2));
+        batch.commit();
+        ptr.store_no_autocollect(Payload::new(3), &mut gc);
+        ptr.store_tracked(Payload::new(4), &mut gc);
+        drop(gc);
+
+        initializer.join().unwrap();
+    });
+}
+
+/// 测试7: reset 与无锁后端的 register_reader 加 pin 竞争时，要么重置失败并恢复纪元，
+/// 要么读者钉住到重置后的纪元，之后的回收都不会释放读者仍持有的值
+#[test]
+fn test_reset_races_lock_free_registration() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::builder()
+            .readers_backend(ReadersBackend::LockFree)
+            .build();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+        // 推进纪元，使重置确实会回退它
+        ptr.store(Payload::new(1), &mut gc);
+        gc.collect();
+
+        let reader = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                if let Some(guard) = local_epoch.try_pin() {
+                    assert!(ptr.load(&guard).get() <= 2);
+                }
+            })
+        };
+
+        gc.reset();
+        ptr.store(Payload::new(2), &mut gc);
+        gc.collect();
+
+        reader.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试8: 另一个线程的 advance_epoch_only 与 swap_blocking 交错时，钉住于该纪元的读者
+/// 在 swap_blocking 交还旧值之前已经解除钉住
+#[test]
+fn test_swap_blocking_races_advance_epoch_only() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+
+        let reader = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                domain.advance_epoch_only();
+                if let Some(guard) = local_epoch.try_pin() {
+                    assert!(ptr.load(&guard).get() <= 1);
+                }
+            })
+        };
+
+        // 交还的旧值在此被 drop，写入它会与仍持有它的读者构成数据竞争
+        drop(ptr.swap_blocking(Payload::new(1), &mut gc));
+
+        reader.join().unwrap();
+    });
+}
+
+/// 测试9: 辅助线程的 compare_and_set_with_backoff 与写入者的 fetch_update 和 rcu 竞争时，
+/// 写入者读取的辅助线程发布的值已经完全初始化
+#[test]
+fn test_writer_reads_value_published_by_helper() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+        let arc_ptr = Arc::new(EpochPtr::new(std::sync::Arc::new(Payload::new(0))));
+
+        let helper = {
+            let (ptr, arc_ptr) = (Arc::clone(&ptr), Arc::clone(&arc_ptr));
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let guard = local_epoch.pin();
+                let current = ptr.load(&guard);
+                let _ = ptr.compare_and_set_with_backoff(current, Payload::new(1), &guard);
+                let current = arc_ptr.load(&guard);
+                let new = std::sync::Arc::new(Payload::new(1));
+                let _ = arc_ptr.compare_and_set_with_backoff(current, new, &guard);
+            })
+        };
+
+        let _ = ptr.fetch_update(|value| Some(Payload::new(value.get() + 10)), &mut gc);
+        let previous = arc_ptr.rcu(|value| Payload::new(value.get() + 10), &mut gc);
+        assert!(previous.get() <= 1);
+
+        helper.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试10: 辅助线程的 compare_and_set_with_backoff 与写入者的 commit_if 竞争时，
+/// 传给 f 的辅助线程发布的值已经完全初始化
+#[test]
+fn test_commit_if_reads_value_published_by_helper() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+
+        let helper = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let guard = local_epoch.pin();
+                let current = ptr.load(&guard);
+                let _ = ptr.compare_and_set_with_backoff(current, Payload::new(1), &guard);
+            })
+        };
+
+        // 让辅助线程有机会先完成 CAS
+        thread::yield_now();
+        let expected = ptr.as_ptr();
+        let _ = gc.commit_if(&ptr, expected, Payload::new(2), |value| value.get() <= 1);
+
+        helper.join().unwrap();
+        gc.collect();
+    });
+}
+
+/// 测试11: 另一个线程持有读者注册锁时 panic 后，写入者的 collect 仍能获取该锁并回收
+#[test]
+fn test_collect_after_panic_while_holding_readers_lock() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = EpochPtr::new(Payload::new(0));
+        let local_epoch = domain.register_reader();
+
+        let panicking = thread::spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _readers = domain.shared.readers.lock();
+                panic!("reader thread panicked during registration");
+            }));
+            assert!(result.is_err());
+        });
+
+        ptr.store(Payload::new(1), &mut gc);
+        panicking.join().unwrap();
+        gc.collect();
+
+        let guard = local_epoch.pin();
+        assert_eq!(ptr.load(&guard).get(), 1);
+        assert_eq!(gc.total_garbage_count(), 0);
+    });
+}
+
+/// 测试12: 辅助线程的 CAS 落在 commit_if 的检查与发布之间时，commit_if 失败而不是覆盖它
+#[test]
+fn test_commit_if_does_not_overwrite_helper_cas() {
+    loom::model(|| {
+        let (mut gc, domain) = EpochGcDomain::new();
+        let ptr = Arc::new(EpochPtr::new(Payload::new(0)));
+        let expected = ptr.as_ptr();
+
+        let helper = {
+            let ptr = Arc::clone(&ptr);
+            thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let guard = local_epoch.pin();
+                let current = ptr.load(&guard);
+                current.get() == 0
+                    && ptr
+                        .compare_and_set_with_backoff(current, Payload::new(1), &guard)
+                        .is_ok()
+            })
+        };
+
+        let committed = gc
+            .commit_if(&ptr, expected, Payload::new(2), |_| true)
+            .is_ok();
+        let helped = helper.join().unwrap();
+
+        // 恰好有一方替换了初始值，最终值是它的值
+        assert!(committed != helped);
+        let local_epoch = gc.local_reader();
+        let expected_value = if committed { 2 } else { 1 };
+        assert_eq!(ptr.load(&local_epoch.pin()).get(), expected_value);
+        drop(local_epoch);
+        gc.collect();
+    });
+}
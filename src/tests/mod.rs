@@ -1,4 +1,15 @@
 mod basic_tests;
 mod concurrent_tests;
+mod counting_alloc;
+mod domain_tests;
+mod drop_counter;
 mod edge_case_tests;
+mod gc_handle_tests;
 mod lifecycle_tests;
+mod list_tests;
+#[cfg(feature = "loom")]
+mod loom_tests;
+mod model_tests;
+mod ptr_api_tests;
+mod reader_api_tests;
+mod stress_tests;
@@ -0,0 +1,75 @@
+/// 模型测试模块
+/// 通过确定性模拟检查回收安全不变量
+use crate::model::{Model, Op};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// 按给定权重生成读者编号小于 `readers` 的随机操作
+fn op(readers: usize) -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (0..readers).prop_map(Op::Pin),
+        2 => (0..readers).prop_map(Op::Unpin),
+        3 => Just(Op::Store),
+        2 => Just(Op::Collect),
+    ]
+}
+
+/// 测试1: 被钉住的读者保护在其钉住之后被替换的值
+#[test]
+fn test_model_pinned_reader_protects_value() {
+    let mut model = Model::new(1);
+    model.apply(Op::Pin(0));
+    model.apply(Op::Store);
+    model.apply(Op::Collect);
+    model.apply(Op::Collect);
+    assert_eq!(model.reclaimed(), 0);
+
+    model.apply(Op::Unpin(0));
+    model.apply(Op::Collect);
+    assert_eq!(model.reclaimed(), 1);
+    model.finish();
+}
+
+/// 测试2: 在读者钉住之前被替换的值可以被回收
+#[test]
+fn test_model_value_replaced_before_pin_is_reclaimable() {
+    let mut model = Model::new(2);
+    model.apply(Op::Store);
+    model.apply(Op::Collect);
+    model.apply(Op::Pin(0));
+    model.apply(Op::Store);
+    model.apply(Op::Collect);
+    // 第一个值在读者钉住之前就被回收了，第二个受到保护
+    assert_eq!(model.reclaimed(), 1);
+    model.finish();
+}
+
+/// 测试3: 自动回收同样遵守安全不变量
+#[test]
+fn test_model_auto_reclaim_respects_pins() {
+    let mut ops = vec![Op::Pin(1)];
+    ops.extend(std::iter::repeat_n(Op::Store, 20));
+    ops.push(Op::Unpin(1));
+    ops.extend(std::iter::repeat_n(Op::Store, 20));
+    Model::run(3, ops);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(500))]
+
+    /// 测试4: 随机生成的短操作序列
+    #[test]
+    fn test_model_generated_short_sequences(ops in vec(op(2), 0..30)) {
+        Model::run(2, ops);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(100))]
+
+    /// 测试5: 随机生成的长操作序列和更多读者
+    #[test]
+    fn test_model_generated_long_sequences(ops in vec(op(5), 0..300)) {
+        Model::run(5, ops);
+    }
+}
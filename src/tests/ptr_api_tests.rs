@@ -0,0 +1,1261 @@
+/// EpochPtr API 测试模块
+/// 测试 EpochPtr 的扩展读写接口
+use super::drop_counter::Counted;
+use crate::sync::AtomicPtr;
+use crate::{AtomicEpochCell, EpochGcDomain, EpochPtr, InlineValue};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 测试1: 指针身份比较
+#[test]
+fn test_ptr_eq() {
+    let raw = Box::into_raw(Box::new(7i32));
+    let a = EpochPtr {
+        ptr: AtomicPtr::new(raw),
+        _marker: PhantomData,
+    };
+    let b = EpochPtr {
+        ptr: AtomicPtr::new(raw),
+        _marker: PhantomData,
+    };
+    let c = EpochPtr::new(7i32);
+
+    // 指向同一分配的指针相等，值相同但分配不同的指针不相等
+    assert!(EpochPtr::ptr_eq(&a, &b));
+    assert!(EpochPtr::ptr_eq(&a, &a));
+    assert!(!EpochPtr::ptr_eq(&a, &c));
+
+    // b 与 a 共享同一分配，避免重复释放
+    std::mem::forget(b);
+}
+
+/// 测试2: 读取拥有所有权的克隆
+#[test]
+fn test_load_cloned() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("config-v1"));
+
+    let guard = local_epoch.pin();
+    let owned = ptr.load_cloned(&guard);
+    drop(guard);
+
+    // guard 释放后写入者可以回收旧值，克隆仍然有效
+    ptr.store(String::from("config-v2"), &mut gc);
+    gc.collect();
+
+    assert_eq!(owned, "config-v1");
+}
+
+/// 测试3: 切片指针的读取、替换和回收
+#[test]
+fn test_slice_ptr_store_and_reclaim() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    let table: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+    let ptr: EpochPtr<[u32]> = EpochPtr::from_boxed_slice(table);
+
+    {
+        let guard = local_epoch.pin();
+        let values = ptr.load(&guard);
+        assert_eq!(values, &[1, 2, 3]);
+        assert_eq!(values[1], 2);
+    }
+
+    // 替换为更长的切片
+    ptr.store((10..20).collect::<Vec<u32>>().into_boxed_slice(), &mut gc);
+    {
+        let guard = local_epoch.pin();
+        let values = ptr.load(&guard);
+        assert_eq!(values.len(), 10);
+        assert_eq!(values[9], 19);
+    }
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 旧切片中的每个元素都应被 drop 恰好一次
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let tracked: Vec<Counted<u32>> = (0..4).map(|i| Counted(i, dropped.clone())).collect();
+    let ptr = EpochPtr::from_boxed_slice(tracked.into_boxed_slice());
+    {
+        let guard = local_epoch.pin();
+        assert_eq!(ptr.load(&guard)[3].0, 3);
+    }
+
+    ptr.store(Vec::new().into_boxed_slice(), &mut gc);
+    assert_eq!(dropped.load(Ordering::SeqCst), 0);
+    gc.collect();
+    assert_eq!(dropped.load(Ordering::SeqCst), 4);
+
+    // 空切片也能正确处理
+    let guard = local_epoch.pin();
+    assert!(ptr.load(&guard).is_empty());
+}
+
+/// 测试4: 无读取者时直接取回旧分配
+#[test]
+fn test_store_recycling_without_readers() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(vec![1u8; 16]);
+
+    // 读取者已注册但未被钉住
+    let old = ptr.store_recycling(vec![2u8; 16], &mut gc);
+    assert_eq!(old.as_deref(), Some(&vec![1u8; 16]));
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    let guard = local_epoch.pin();
+    assert_eq!(ptr.load(&guard)[0], 2);
+}
+
+/// 测试5: 有活跃读取者时旧值被退休
+#[test]
+fn test_store_recycling_with_active_reader() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(1i32);
+
+    let guard = local_epoch.pin();
+    let old_value = ptr.load(&guard);
+
+    assert!(ptr.store_recycling(2, &mut gc).is_none());
+    assert_eq!(gc.total_garbage_count(), 1);
+
+    // 读取者持有的旧引用仍然有效
+    assert_eq!(*old_value, 1);
+    drop(guard);
+
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试6: fetch_update 递增计数器
+#[test]
+fn test_fetch_update_counter() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let counter = EpochPtr::new(0u64);
+
+    for _ in 0..10 {
+        assert_eq!(counter.fetch_update(|n| Some(n + 1), &mut gc), Ok(()));
+    }
+
+    let guard = local_epoch.pin();
+    assert_eq!(*counter.load(&guard), 10);
+}
+
+/// 测试7: fetch_update 返回 None 时放弃更新
+#[test]
+fn test_fetch_update_abort() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let counter = EpochPtr::new(5u64);
+
+    let result = counter.fetch_update(|n| if *n >= 5 { None } else { Some(n + 1) }, &mut gc);
+    assert_eq!(result, Err(()));
+
+    // 没有旧值被退休，值保持不变
+    assert_eq!(gc.total_garbage_count(), 0);
+    let guard = local_epoch.pin();
+    assert_eq!(*counter.load(&guard), 5);
+}
+
+/// 测试8: 内联原子单元存储 u32 且不产生垃圾
+#[test]
+fn test_atomic_epoch_cell_u32() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let cell = AtomicEpochCell::new(1u32);
+
+    for i in 2..100u32 {
+        cell.store(i, &mut gc);
+    }
+
+    let guard = local_epoch.pin();
+    assert_eq!(cell.load(&guard), 99);
+    // 更新不分配也不退休
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(domain.epoch(), 0);
+}
+
+/// 测试9: 内联原子单元存储 repr(transparent) 包装类型
+#[test]
+fn test_atomic_epoch_cell_transparent_wrapper() {
+    #[repr(transparent)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Flags(u16);
+    unsafe impl InlineValue for Flags {}
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let cell = AtomicEpochCell::new(Flags(0b0001));
+
+    {
+        let guard = local_epoch.pin();
+        let flags = cell.load(&guard);
+        assert_eq!(flags, Flags(0b0001));
+        cell.store(Flags(flags.0 | 0b1000), &mut gc);
+        // 已读取的副本不受后续写入影响
+        assert_eq!(flags, Flags(0b0001));
+    }
+
+    let guard = local_epoch.pin();
+    assert_eq!(cell.load(&guard), Flags(0b1001));
+    assert_eq!(format!("{cell:?}"), "AtomicEpochCell(Flags(9))");
+}
+
+/// 测试10: 空指针的延迟初始化
+#[test]
+fn test_load_or_init() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::<String>::null();
+    let init_calls = AtomicUsize::new(0);
+
+    let guard = local_epoch.pin();
+    for _ in 0..3 {
+        let value = ptr.load_or_init(
+            &guard,
+            || {
+                init_calls.fetch_add(1, Ordering::SeqCst);
+                String::from("lazy")
+            },
+            &mut gc,
+        );
+        assert_eq!(value, "lazy");
+    }
+
+    // 只有第一次调用执行了初始化，且没有产生垃圾
+    assert_eq!(init_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(ptr.load(&guard), "lazy");
+}
+
+/// 测试11: 格式化指针的当前值
+#[test]
+fn test_debug_value() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(42i32);
+
+    {
+        let guard = local_epoch.pin();
+        let formatted = format!("{:?}", ptr.debug_value(&guard));
+        assert_eq!(formatted, "EpochPtr(42)");
+    }
+
+    ptr.store(-7, &mut gc);
+    let guard = local_epoch.pin();
+    assert!(format!("{:?}", ptr.debug_value(&guard)).contains("-7"));
+}
+
+/// 测试12: 不解引用的指针内省
+#[test]
+fn test_is_null_and_as_ptr() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    // 用值创建的指针
+    let populated = EpochPtr::new(1i32);
+    assert!(!populated.is_null());
+
+    // 空指针
+    let ptr = EpochPtr::<i32>::null();
+    assert!(ptr.is_null());
+    assert!(ptr.as_ptr().is_null());
+
+    // 初始化后的指针
+    ptr.store(5, &mut gc);
+    assert!(!ptr.is_null());
+    let guard = local_epoch.pin();
+    assert!(std::ptr::eq(ptr.as_ptr(), ptr.load(&guard)));
+    drop(guard);
+
+    // store 之后地址发生变化
+    let before = ptr.as_ptr();
+    ptr.store(6, &mut gc);
+    assert!(!std::ptr::eq(before, ptr.as_ptr()));
+}
+
+/// 测试13: EpochPtr 的 Send/Sync 跟随 T
+#[test]
+fn test_epoch_ptr_send_sync_follow_t() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    fn assert_send<T: Send>() {}
+
+    assert_send_sync::<EpochPtr<i32>>();
+    assert_send_sync::<EpochPtr<String>>();
+    assert_send_sync::<EpochPtr<[u8]>>();
+    // Cell 是 Send 但不是 Sync：EpochPtr 仍可被移动到其他线程
+    assert_send::<EpochPtr<std::cell::Cell<i32>>>();
+}
+
+/// 测试14: 每次成功的带标签 CAS 都会递增标签
+#[test]
+fn test_cas_tagged_increments_tag() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0u64);
+
+    for i in 1..=5u64 {
+        let guard = local_epoch.pin();
+        let (value, tag) = ptr.load_tagged(&guard);
+        assert_eq!(*value, i - 1);
+        assert_eq!(ptr.cas_tagged((value, tag), i, &mut gc), Ok(()));
+
+        let (value, new_tag) = ptr.load_tagged(&guard);
+        assert_eq!(*value, i);
+        assert_eq!(new_tag, (tag + 1) % std::mem::align_of::<u64>());
+    }
+
+    // 普通 store 同样递增标签，且未带标签的 API 不受影响
+    let guard = local_epoch.pin();
+    let (_, tag) = ptr.load_tagged(&guard);
+    drop(guard);
+    ptr.store(42, &mut gc);
+    let guard = local_epoch.pin();
+    assert_eq!(
+        ptr.load_tagged(&guard).1,
+        (tag + 1) % std::mem::align_of::<u64>()
+    );
+    assert_eq!(*ptr.load(&guard), 42);
+    assert!(std::ptr::eq(ptr.as_ptr(), ptr.load(&guard)));
+}
+
+/// 测试15: 指针相同但标签过时的 CAS 失败
+#[test]
+fn test_cas_tagged_stale_tag_fails() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(7u64);
+    let guard = local_epoch.pin();
+
+    let (value, tag) = ptr.load_tagged(&guard);
+    // 模拟 ABA：相同地址被重新存入，但标签已经前进
+    let raw = ptr.ptr.load(Ordering::Relaxed);
+    ptr.ptr
+        .store(raw.map_addr(|addr| addr + 1), Ordering::Relaxed);
+    assert!(std::ptr::eq(ptr.as_ptr(), value));
+
+    assert_eq!(ptr.cas_tagged((value, tag), 8, &mut gc), Err(8));
+    assert_eq!(*ptr.load(&guard), 7);
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 使用最新的标签则成功
+    let (value, tag) = ptr.load_tagged(&guard);
+    assert_eq!(tag, 1);
+    assert_eq!(ptr.cas_tagged((value, tag), 8, &mut gc), Ok(()));
+    assert_eq!(*ptr.load(&guard), 8);
+}
+
+/// 测试16: try_load 在有效守卫下返回 Ok，在被破坏的守卫下返回 Err
+#[cfg(debug_assertions)]
+#[test]
+fn test_try_load() {
+    use crate::LoadError;
+    use crate::state::INACTIVE_EPOCH;
+
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(3i32);
+    let guard = local_epoch.pin();
+
+    // 有效路径
+    assert_eq!(ptr.try_load(&guard), Ok(&3));
+    assert_eq!(
+        EpochPtr::<i32>::null().try_load(&guard),
+        Err(LoadError::Null)
+    );
+
+    // 被破坏的 pin 计数
+    local_epoch.pin_count.set(0);
+    assert_eq!(ptr.try_load(&guard), Err(LoadError::NotPinned));
+    local_epoch.pin_count.set(1);
+
+    // 被破坏的槽状态
+    let epoch = local_epoch.slot.active_epoch.load(Ordering::Relaxed);
+    local_epoch
+        .slot
+        .active_epoch
+        .store(INACTIVE_EPOCH, Ordering::Relaxed);
+    assert_eq!(ptr.try_load(&guard), Err(LoadError::SlotInactive));
+    local_epoch
+        .slot
+        .active_epoch
+        .store(epoch, Ordering::Relaxed);
+
+    assert_eq!(ptr.try_load(&guard), Ok(&3));
+}
+
+/// 测试17: 使用显式内存顺序的 load
+#[test]
+fn test_load_ordered() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(1i32);
+    ptr.store(2, &mut gc);
+
+    let guard = local_epoch.pin();
+    for order in [Ordering::Acquire, Ordering::Relaxed, Ordering::SeqCst] {
+        // 值由本线程存储，因此 Relaxed 也能看到其初始化
+        let value = unsafe { ptr.load_ordered(&guard, order) };
+        assert_eq!(*value, 2);
+        assert!(std::ptr::eq(value, ptr.load(&guard)));
+    }
+}
+
+/// 测试18: 对 load 无效的内存顺序会 panic
+#[test]
+#[should_panic(expected = "release load")]
+fn test_load_ordered_rejects_release() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(1i32);
+    let guard = local_epoch.pin();
+    let _ = unsafe { ptr.load_ordered(&guard, Ordering::Release) };
+}
+
+/// 测试19: EpochArcSwap 的 rcu，对应 arc-swap 文档中的计数器示例
+#[test]
+fn test_epoch_arc_swap_rcu() {
+    use crate::EpochArcSwap;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let counter = Arc::new(EpochArcSwap::from_pointee(0usize));
+    let stop = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let domain = domain.clone();
+            let counter = counter.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let mut last = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let value = *counter.load(&local_epoch.pin());
+                    // 读者看到的值单调递增
+                    assert!(value >= last);
+                    last = value;
+                }
+            })
+        })
+        .collect();
+
+    for i in 0..1000 {
+        let previous = counter.rcu(|value| value + 1, &mut gc);
+        assert_eq!(*previous, i);
+    }
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+    assert_eq!(*counter.load(&guard), 1000);
+
+    // load_full 得到的 Arc 比守卫和替换存活得更久
+    let snapshot = counter.load_full(&guard);
+    drop(guard);
+    counter.store(Arc::new(0), &mut gc);
+    gc.collect();
+    assert_eq!(*snapshot, 1000);
+}
+
+/// 测试20: store_no_autocollect 不会在循环中途触发回收
+#[test]
+fn test_store_no_autocollect() {
+    let (mut gc, domain) = EpochGcDomain::builder().auto_reclaim_threshold(16).build();
+    let _local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0usize);
+
+    for i in 1..=1000 {
+        ptr.store_no_autocollect(i, &mut gc);
+    }
+    // 没有发生中途回收：纪元不变，所有旧值都在同一个袋子里
+    assert_eq!(domain.epoch(), 0);
+    assert_eq!(gc.total_garbage_count(), 1000);
+    assert_eq!(gc.pending_epochs(), 1);
+
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 普通 store 仍然遵循阈值
+    for i in 0..17 {
+        ptr.store(i, &mut gc);
+    }
+    assert_eq!(domain.epoch(), 2);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试21: swap_blocking 只在读者解除钉住后才返回旧值
+#[test]
+fn test_swap_blocking_waits_for_reader() {
+    use std::sync::atomic::AtomicBool;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = Arc::new(EpochPtr::new(String::from("old")));
+    let unpinned = Arc::new(AtomicBool::new(false));
+    let (pinned_tx, pinned_rx) = std::sync::mpsc::channel();
+
+    let reader = {
+        let domain = domain.clone();
+        let ptr = ptr.clone();
+        let unpinned = unpinned.clone();
+        std::thread::spawn(move || {
+            let local_epoch = domain.register_reader();
+            let guard = local_epoch.pin();
+            let value = ptr.load(&guard);
+            pinned_tx.send(()).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            assert_eq!(value, "old");
+            unpinned.store(true, Ordering::SeqCst);
+            drop(guard);
+        })
+    };
+    pinned_rx.recv().unwrap();
+
+    let old = ptr.swap_blocking(String::from("new"), &mut gc);
+    assert!(unpinned.load(Ordering::SeqCst));
+    assert_eq!(old, "old");
+    assert_eq!(gc.total_garbage_count(), 0);
+    reader.join().unwrap();
+}
+
+/// 测试22: 稳定状态下 RecyclingEpochPtr 复用已回收的分配
+#[test]
+fn test_recycling_ptr_reuses_allocations() {
+    use crate::RecyclingEpochPtr;
+    use crate::tests::counting_alloc::allocations;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = RecyclingEpochPtr::new([0u64; 8]);
+
+    // 预热：建立回收池和垃圾袋
+    for i in 0..4 {
+        ptr.store([i; 8], &mut gc);
+        gc.collect();
+    }
+    assert!(ptr.pooled() > 0);
+
+    let before = allocations();
+    for i in 0..1000 {
+        ptr.store([i; 8], &mut gc);
+        gc.collect();
+    }
+    assert_eq!(allocations() - before, 0);
+
+    // 读者看到最新的值，且被钉住的读者仍然保护旧值
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+    let old = ptr.load(&guard);
+    assert_eq!(old[0], 999);
+    ptr.store([1000; 8], &mut gc);
+    gc.collect();
+    assert_eq!(old[0], 999);
+    assert_eq!(ptr.load(&guard)[0], 1000);
+    drop(guard);
+    gc.collect();
+}
+
+/// 测试23: RecyclingEpochPtr 正确 drop 所有值
+#[test]
+fn test_recycling_ptr_drops_values() {
+    use crate::RecyclingEpochPtr;
+
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let (mut gc, _domain) = EpochGcDomain::new();
+    let ptr = RecyclingEpochPtr::with_pool_capacity(Counted::new(&drops), 2);
+    for _ in 0..10 {
+        ptr.store(Counted::new(&drops), &mut gc);
+    }
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 10);
+    assert_eq!(ptr.pooled(), 2);
+
+    drop(ptr);
+    assert_eq!(drops.load(Ordering::SeqCst), 11);
+}
+
+/// 测试24: 在 boxed 与 Arc 表示之间往返转换
+#[test]
+fn test_arc_ptr_round_trip() {
+    use crate::LoadFull;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    let boxed = EpochPtr::new(vec![1, 2, 3]);
+    let arc_ptr = boxed.into_arc_ptr(&mut gc);
+    let snapshot = arc_ptr.load_full(&local_epoch.pin());
+    assert_eq!(*snapshot, [1, 2, 3]);
+
+    // 读者仍持有 Arc 时，值被克隆而不是移出
+    let boxed = arc_ptr.into_boxed_ptr(&mut gc);
+    assert_eq!(*boxed.load(&local_epoch.pin()), [1, 2, 3]);
+    assert_eq!(*snapshot, [1, 2, 3]);
+
+    let null: EpochPtr<i32> = EpochPtr::null();
+    assert!(null.into_arc_ptr(&mut gc).is_null());
+}
+
+/// 测试25: 转换后两种表示都能正常回收
+#[test]
+fn test_arc_ptr_conversion_reclamation() {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+
+    // 转换本身不会 drop 值：它被移动到新的表示中
+    let arc_ptr = EpochPtr::new(Counted::new(&drops)).into_arc_ptr(&mut gc);
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+    let guard = local_epoch.pin();
+    arc_ptr.store(Arc::new(Counted::new(&drops)), &mut gc);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(guard);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let boxed = arc_ptr.into_boxed_ptr(&mut gc);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let guard = local_epoch.pin();
+    boxed.store(Counted::new(&drops), &mut gc);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    drop(guard);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    drop(boxed);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+/// 测试26: EpochPtr<Option<T>> 在 Some 与 None 之间切换
+#[test]
+fn test_option_ptr_transitions() {
+    use crate::OptionPtr;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr: EpochPtr<Option<String>> = EpochPtr::new(None);
+
+    assert_eq!(ptr.load_some(&local_epoch.pin()), None);
+
+    ptr.store_some(String::from("a"), &mut gc);
+    let guard = local_epoch.pin();
+    let held = ptr.load_some(&guard);
+    ptr.store_none(&mut gc);
+    gc.collect();
+    // 被钉住的读者仍然看到旧的 Some 负载
+    assert_eq!(held.map(String::as_str), Some("a"));
+    assert_eq!(ptr.load_some(&guard), None);
+    drop(guard);
+
+    ptr.store_some(String::from("b"), &mut gc);
+    assert_eq!(
+        ptr.load_some(&local_epoch.pin()).map(String::as_str),
+        Some("b")
+    );
+}
+
+/// 测试27: 被替换的 Some 负载在回收时被 drop
+#[test]
+fn test_option_ptr_drops_payload() {
+    use crate::OptionPtr;
+
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(Some(Counted::new(&drops)));
+
+    let guard = local_epoch.pin();
+    ptr.store_none(&mut gc);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(guard);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    ptr.store_some(Counted::new(&drops), &mut gc);
+    ptr.store_some(Counted::new(&drops), &mut gc);
+    gc.collect();
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    drop(ptr);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
+
+/// 测试28: 过度对齐的分配为 u8 提供标签位
+#[test]
+fn test_new_tagged_u8() {
+    use crate::TagAligned;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new_tagged(0u8);
+    assert_eq!(ptr.as_ptr().addr() % 8, 0);
+
+    // 每次更新都递增标签，并在 8 次之后回绕
+    for i in 1..=8u8 {
+        ptr.store(TagAligned(i), &mut gc);
+        assert_eq!(ptr.as_ptr().addr() % 8, 0);
+        let guard = local_epoch.pin();
+        let (value, tag) = ptr.load_tagged(&guard);
+        assert_eq!(**value, i);
+        assert_eq!(tag, usize::from(i) % 8);
+    }
+
+    // 过时的标签使 CAS 失败
+    let guard = local_epoch.pin();
+    let (value, tag) = ptr.load_tagged(&guard);
+    assert!(
+        ptr.cas_tagged((value, tag), TagAligned(20), &mut gc)
+            .is_ok()
+    );
+    assert_eq!(
+        ptr.cas_tagged((value, tag), TagAligned(21), &mut gc),
+        Err(TagAligned(21))
+    );
+    assert_eq!(**ptr.load(&guard), 20);
+}
+
+/// 测试29: 过度对齐的值被正确回收
+#[test]
+fn test_new_tagged_reclamation() {
+    use crate::TagAligned;
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    struct Byte(#[allow(dead_code)] u8);
+    impl Drop for Byte {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    assert_eq!(std::mem::align_of::<Byte>(), 1);
+    assert_eq!(std::mem::align_of::<TagAligned<Byte>>(), 8);
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new_tagged(Byte(0));
+
+    let guard = local_epoch.pin();
+    for i in 1..=3 {
+        ptr.store(TagAligned(Byte(i)), &mut gc);
+    }
+    gc.collect();
+    assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+    drop(guard);
+    gc.collect();
+    assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+
+    drop(ptr);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+}
+
+/// 测试30: 延迟产生值的 store 在跳过时不会调用闭包
+#[test]
+fn test_store_with_lazy_value() {
+    let (mut gc, domain) = EpochGcDomain::builder().max_pending_garbage(2).build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("v0"));
+
+    ptr.store_with(|| String::from("v1"), &mut gc);
+    gc.collect();
+
+    // 被钉住的读者使垃圾队列填满
+    let guard = local_epoch.pin();
+    assert!(ptr.try_store_with(|| String::from("v2"), &mut gc));
+    assert!(ptr.try_store_with(|| String::from("v3"), &mut gc));
+    assert!(!ptr.try_store_with(
+        || panic!("the closure must not be called when the store is skipped"),
+        &mut gc
+    ));
+    assert_eq!(ptr.load(&guard), "v3");
+    drop(guard);
+
+    assert!(ptr.try_store_with(|| String::from("v4"), &mut gc));
+    assert_eq!(ptr.load(&local_epoch.pin()), "v4");
+}
+
+/// 测试31: EpochPtr<Arc<T>> 的 RCU 更新，旧的 Arc 在读者释放后被回收
+#[test]
+fn test_arc_ptr_rcu() {
+    use crate::LoadFull;
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(Arc::new(0usize));
+
+    for i in 0..10 {
+        let previous = ptr.rcu(|value| value + 1, &mut gc);
+        assert_eq!(*previous, i);
+    }
+    assert_eq!(*ptr.load_full(&local_epoch.pin()), 10);
+
+    // 被钉住的读者使旧的 Arc 保持存活，直到它解除钉住
+    let guard = local_epoch.pin();
+    let old = ptr.load(&guard);
+    let weak = Arc::downgrade(old);
+    drop(ptr.rcu(|value| value * 2, &mut gc));
+    gc.collect();
+    assert_eq!(weak.upgrade().as_deref(), Some(&10));
+    drop(guard);
+    gc.collect();
+    assert!(weak.upgrade().is_none());
+    assert_eq!(*ptr.load_full(&local_epoch.pin()), 20);
+}
+
+/// 测试32: 写入者静止时，未注册的监控线程可以通过 observe 读取值
+#[test]
+fn test_observe_with_quiescent_writer() {
+    let (mut gc, _domain) = EpochGcDomain::new();
+    let ptr = Arc::new(EpochPtr::new(String::from("v1")));
+    let (observed_tx, observed_rx) = std::sync::mpsc::channel();
+    let (stored_tx, stored_rx) = std::sync::mpsc::channel::<()>();
+
+    let monitor = {
+        let ptr = Arc::clone(&ptr);
+        std::thread::spawn(move || {
+            // 每次观察之前，写入者都通过通道交出了控制权，并且在收到回复之前不会再写入
+            while stored_rx.recv().is_ok() {
+                let value = unsafe { &*ptr.observe() };
+                observed_tx.send(value.clone()).unwrap();
+            }
+        })
+    };
+
+    for version in ["v2", "v3", "v4"] {
+        ptr.store(String::from(version), &mut gc);
+        gc.collect();
+        stored_tx.send(()).unwrap();
+        assert_eq!(observed_rx.recv().unwrap(), version);
+    }
+    drop(stored_tx);
+    monitor.join().unwrap();
+
+    assert!(!unsafe { ptr.observe() }.is_null());
+}
+
+/// 测试33: load_ref 返回的 Ref 通过 Deref 透明使用，并记录读取时的纪元
+#[test]
+fn test_load_ref() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("v1"));
+
+    {
+        let guard = local_epoch.pin();
+        let value = ptr.load_ref(&guard);
+        assert_eq!(*value, "v1");
+        assert_eq!(value.len(), 2);
+        assert_eq!(value.epoch(), 0);
+        assert_eq!(guard.epoch(), 0);
+    }
+
+    ptr.store(String::from("v2"), &mut gc);
+    gc.collect();
+    gc.collect();
+
+    let guard = local_epoch.pin();
+    let value = ptr.load_ref(&guard);
+    let copy = value;
+    assert_eq!(&*copy, "v2");
+    assert_eq!(copy.epoch(), domain.epoch());
+
+    // 嵌套的守卫共享最外层 pin 的纪元
+    gc.collect();
+    let nested = local_epoch.pin();
+    assert_eq!(ptr.load_ref(&nested).epoch(), value.epoch());
+    assert_eq!(value.into_ref(), ptr.load(&guard));
+}
+
+/// 测试34: 两阶段构造，第一次 init_once 成功，第二次交还值
+#[test]
+fn test_init_once() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr: EpochPtr<String> = EpochPtr::null();
+
+    assert_eq!(ptr.init_once(String::from("first")), Ok(()));
+    assert_eq!(
+        ptr.init_once(String::from("second")),
+        Err(String::from("second"))
+    );
+    assert_eq!(ptr.load(&local_epoch.pin()), "first");
+
+    // store 之后指针仍不为空
+    ptr.store(String::from("third"), &mut gc);
+    assert_eq!(ptr.init_once(String::from("fourth")).unwrap_err(), "fourth");
+
+    // 多个线程并发初始化时恰好一个获胜
+    let ptr = Arc::new(EpochPtr::<usize>::null());
+    let winners: usize = (0..8)
+        .map(|i| {
+            let ptr = Arc::clone(&ptr);
+            std::thread::spawn(move || ptr.init_once(i).is_ok() as usize)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum();
+    assert_eq!(winners, 1);
+    assert!(*ptr.load(&local_epoch.pin()) < 8);
+}
+
+/// 测试35: 辅助线程竞争 compare_and_set_with_backoff，每次成功都恰好退休一个旧值
+#[test]
+fn test_compare_and_set_with_backoff_contended() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(Counted(0, drops.clone()));
+
+    // 过时的期望值会失败并交还新值
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+    let stale = ptr.load(&guard);
+    assert!(
+        ptr.compare_and_set_with_backoff(stale, Counted(1, drops.clone()), &guard)
+            .is_ok()
+    );
+    let rejected = ptr
+        .compare_and_set_with_backoff(stale, Counted(2, drops.clone()), &guard)
+        .unwrap_err();
+    assert_eq!(rejected.0, 2);
+    drop(rejected);
+    assert_eq!(ptr.load(&guard).0, 1);
+    drop(guard);
+
+    // 多个辅助线程各自递增计数器，失败时重新加载并复用被拒绝的值，使其不被 drop
+    let successes = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let local_epoch = domain.register_reader();
+                for _ in 0..100 {
+                    let mut next = Counted(0, drops.clone());
+                    loop {
+                        let guard = local_epoch.pin();
+                        let current = ptr.load(&guard);
+                        next.0 = current.0 + 1;
+                        match ptr.compare_and_set_with_backoff(current, next, &guard) {
+                            Ok(()) => {
+                                successes.fetch_add(1, Ordering::Relaxed);
+                                break;
+                            }
+                            Err(rejected) => next = rejected,
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(ptr.load(&local_epoch.pin()).0, 401);
+    drop(local_epoch);
+    gc.collect();
+    // 1 个过时期望被拒绝的值，加上每次成功替换出的旧值
+    assert_eq!(
+        drops.load(Ordering::Relaxed),
+        1 + 1 + successes.load(Ordering::Relaxed)
+    );
+    drop(ptr);
+    assert_eq!(drops.load(Ordering::Relaxed), 403);
+}
+
+/// 测试36: load_many 在同一个守卫下按顺序读取多个指针
+#[test]
+fn test_load_many_under_one_guard() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let table: Vec<_> = (0..4).map(|i| EpochPtr::new(i * 10)).collect();
+
+    table[2].store(25, &mut gc);
+
+    let guard = local_epoch.pin();
+    let values = EpochPtr::load_many(&table, &guard);
+    assert_eq!(values.len(), 4);
+    let values: Vec<&i32> = values.collect();
+    assert_eq!(values, [&0, &10, &25, &30]);
+
+    // 守卫存活期间，之后的 store 不会回收已读取的值
+    table[0].store(5, &mut gc);
+    gc.collect();
+    assert_eq!(*values[0], 0);
+    assert_eq!(EpochPtr::load_many(&table, &guard).next_back(), Some(&30));
+    drop(guard);
+
+    assert!(
+        EpochPtr::load_many(&[] as &[EpochPtr<i32>], &local_epoch.pin())
+            .next()
+            .is_none()
+    );
+}
+
+/// 测试37: load_cmp 在同一个守卫下比较两个指针的值
+#[test]
+fn test_load_cmp() {
+    use std::cmp::Ordering as CmpOrdering;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let low = EpochPtr::new(String::from("apple"));
+    let high = EpochPtr::new(String::from("banana"));
+
+    let guard = local_epoch.pin();
+    assert_eq!(low.load_cmp(&high, &guard), CmpOrdering::Less);
+    assert_eq!(high.load_cmp(&low, &guard), CmpOrdering::Greater);
+    assert_eq!(low.load_cmp(&low, &guard), CmpOrdering::Equal);
+
+    // 值相等但分配不同的指针比较为相等
+    let same = EpochPtr::new(String::from("apple"));
+    assert_eq!(low.load_cmp(&same, &guard), CmpOrdering::Equal);
+    assert!(!EpochPtr::ptr_eq(&low, &same));
+    drop(guard);
+
+    // 比较的是当前值
+    low.store(String::from("cherry"), &mut gc);
+    let guard = local_epoch.pin();
+    assert_eq!(low.load_cmp(&high, &guard), CmpOrdering::Greater);
+
+    // 用于排序一组指针
+    let mut ptrs = [&low, &high, &same];
+    ptrs.sort_by(|a, b| a.load_cmp(b, &guard));
+    let sorted: Vec<&str> = ptrs.iter().map(|ptr| ptr.load(&guard).as_str()).collect();
+    assert_eq!(sorted, ["apple", "banana", "cherry"]);
+}
+
+/// 测试38: store_reporting 只在超过自动回收阈值并触发回收的那次 store 返回 true
+#[test]
+fn test_store_reporting() {
+    let (mut gc, domain) = EpochGcDomain::builder().auto_reclaim_threshold(2).build();
+    let reader = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+
+    assert!(!ptr.store_reporting(1, &mut gc));
+    assert!(!ptr.store_reporting(2, &mut gc));
+    assert_eq!(gc.total_garbage_count(), 2);
+
+    // 第三个退休对象超过阈值，回收在这次 store 中运行
+    assert!(ptr.store_reporting(3, &mut gc));
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(domain.epoch(), 1);
+
+    assert!(!ptr.store_reporting(4, &mut gc));
+    assert_eq!(*ptr.load(&reader.pin()), 4);
+}
+
+/// 测试39: map 从当前值构造一个独立的新指针，源指针不受影响
+#[test]
+fn test_map() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let source = EpochPtr::new(21i32);
+
+    let mapped: EpochPtr<String> = {
+        let guard = local_epoch.pin();
+        source.map(|v| format!("value={}", v * 2), &guard)
+    };
+
+    let guard = local_epoch.pin();
+    assert_eq!(*source.load(&guard), 21);
+    assert_eq!(mapped.load(&guard), "value=42");
+    drop(guard);
+
+    // 两个指针互不影响
+    source.store(1, &mut gc);
+    mapped.store("other".to_string(), &mut gc);
+    let guard = local_epoch.pin();
+    assert_eq!(*source.load(&guard), 1);
+    assert_eq!(mapped.load(&guard), "other");
+}
+
+/// 测试40: 按页对齐的类型以正确的对齐方式分配，并在普通回收、隔离回收和 drain_safe 中以匹配的布局释放
+/// 不依赖线程或睡眠，可以在 miri 下运行以检查分配与释放的布局是否一致
+#[test]
+fn test_over_aligned_reclaim() {
+    #[repr(align(4096))]
+    struct PageAligned {
+        id: usize,
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl Drop for PageAligned {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let page = |id| PageAligned {
+        id,
+        drops: drops.clone(),
+    };
+
+    for quarantine in [false, true] {
+        let (mut gc, domain) = EpochGcDomain::builder()
+            .auto_reclaim_threshold(None)
+            .build();
+        if quarantine {
+            gc.assert_safe_reclaim();
+        }
+        let local_epoch = domain.register_reader();
+        let ptr = EpochPtr::new(page(0));
+
+        // 每次 store 都会递增打包在低位中的版本标签，加载的引用仍必须按页对齐
+        for id in 1..=4 {
+            ptr.store(page(id), &mut gc);
+            let guard = local_epoch.pin();
+            let value = ptr.load(&guard);
+            assert_eq!(value.id, id);
+            assert_eq!(value as *const PageAligned as usize % 4096, 0);
+        }
+
+        gc.collect();
+        assert_eq!(gc.total_garbage_count(), 0);
+        if quarantine {
+            assert_eq!(gc.quarantined_count(), 4);
+            assert!(gc.quarantine_intact());
+            gc.clear_quarantine();
+        }
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+    // drain_safe 交还的值仍位于其按页对齐的分配中
+    let (mut gc, domain) = EpochGcDomain::new();
+    let _local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(page(0));
+    ptr.store(page(1), &mut gc);
+    let drained: Vec<_> = gc.drain_safe().collect();
+    assert_eq!(drained.len(), 1);
+    let value = drained
+        .into_iter()
+        .next()
+        .unwrap()
+        .downcast::<PageAligned>()
+        .ok()
+        .unwrap();
+    assert_eq!(value.id, 0);
+    assert_eq!(&*value as *const PageAligned as usize % 4096, 0);
+}
+
+/// 测试41: new_with 立即调用一次构造函数
+#[test]
+fn test_new_with() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let calls = AtomicUsize::new(0);
+
+    let ptr = EpochPtr::new_with(|| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        String::from("lazy")
+    });
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    let guard = local_epoch.pin();
+    assert_eq!(ptr.load(&guard), "lazy");
+}
+
+/// 测试42: 对初始为空的指针多次调用 get_or_insert_with，f 只运行一次
+#[test]
+fn test_get_or_insert_with_runs_once() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::<Vec<u32>>::null();
+    let calls = AtomicUsize::new(0);
+
+    for _ in 0..5 {
+        let guard = local_epoch.pin();
+        let value = ptr.get_or_insert_with(
+            &guard,
+            || {
+                calls.fetch_add(1, Ordering::Relaxed);
+                vec![1, 2, 3]
+            },
+            &mut gc,
+        );
+        assert_eq!(value, &[1, 2, 3]);
+    }
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // 已有值的指针不会调用 f，也不会退休任何东西
+    let guard = local_epoch.pin();
+    let value = ptr.get_or_insert_with(&guard, || unreachable!(), &mut gc);
+    assert_eq!(value, &[1, 2, 3]);
+    drop(guard);
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试43: 加载尚未初始化的空指针会 panic，而不是解引用 null
+#[test]
+#[should_panic(expected = "load of a null EpochPtr")]
+fn test_load_null_panics() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::<String>::null();
+    let guard = local_epoch.pin();
+    let _ = ptr.load(&guard);
+}
+
+/// 测试44: 基于 load 之外的其他解引用路径同样拒绝空指针
+#[test]
+fn test_null_rejected_by_every_load_path() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::<u64>::null();
+
+    let guard = local_epoch.pin();
+    assert!(
+        catch_unwind(AssertUnwindSafe(|| unsafe {
+            ptr.load_ordered(&guard, Ordering::Relaxed)
+        }))
+        .is_err()
+    );
+    assert!(catch_unwind(AssertUnwindSafe(|| ptr.load_tagged(&guard))).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| ptr.load_cloned(&guard))).is_err());
+    assert!(
+        catch_unwind(AssertUnwindSafe(|| guard
+            .clone()
+            .snapshot_then_release(&ptr, |v| *v)))
+        .is_err()
+    );
+    drop(guard);
+    assert!(
+        catch_unwind(AssertUnwindSafe(
+            || ptr.fetch_update(|v| Some(*v + 1), &mut gc)
+        ))
+        .is_err()
+    );
+
+    // 初始化之后所有路径都恢复正常
+    ptr.init_once(7).unwrap();
+    let guard = local_epoch.pin();
+    assert_eq!(*ptr.load(&guard), 7);
+}
+
+/// 测试45: 空指针转换得到的 Arc 指针在 load_full 时 panic，而不是解引用 null
+#[test]
+#[should_panic(expected = "load of a null EpochPtr")]
+fn test_converted_null_load_full_panics() {
+    use crate::LoadFull;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let arc_ptr = EpochPtr::<String>::null().into_arc_ptr(&mut gc);
+    let _ = arc_ptr.load_full(&local_epoch.pin());
+}
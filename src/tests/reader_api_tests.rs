@@ -0,0 +1,490 @@
+/// 读取者 API 测试模块
+/// 测试 LocalEpoch 和 PinGuard 的扩展接口
+use crate::state::INACTIVE_EPOCH;
+use crate::{EpochGcDomain, EpochPtr, ReaderSet};
+use std::sync::atomic::Ordering;
+
+/// 测试1: 读取快照后释放 pin
+#[test]
+fn test_snapshot_then_release() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(String::from("hello"));
+
+    let guard = local_epoch.pin();
+    let len = guard.snapshot_then_release(&ptr, |value| value.len());
+
+    assert_eq!(len, 5);
+    // 调用返回后槽应该处于不活跃状态
+    assert_eq!(
+        local_epoch.slot.active_epoch.load(Ordering::Acquire),
+        INACTIVE_EPOCH
+    );
+    assert_eq!(local_epoch.pin_count.get(), 0);
+}
+
+/// 测试2: pin 超时看门狗
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "exceeding the pin timeout")]
+fn test_pin_timeout_watchdog() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .pin_timeout(std::time::Duration::from_millis(1))
+        .build();
+    let local_epoch = domain.register_reader();
+
+    // 故意长时间持有 guard
+    let _guard = local_epoch.pin();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    gc.collect();
+}
+
+/// 测试3: 短暂的 pin 不会触发看门狗
+#[test]
+fn test_pin_timeout_short_pin() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .pin_timeout(std::time::Duration::from_secs(60))
+        .build();
+    let local_epoch = domain.register_reader();
+
+    let _guard = local_epoch.pin();
+    gc.collect();
+}
+
+/// 测试4: 同时钉住一组读者
+#[test]
+fn test_reader_set_pin_all() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let readers = ReaderSet::register(&domain, 4);
+    assert_eq!(readers.readers().len(), 4);
+
+    // 第一个读者提前钉住在纪元 0
+    let early_guard = readers.readers()[0].pin();
+    gc.collect();
+    gc.collect();
+
+    let guards = readers.pin_all();
+    assert_eq!(guards.len(), 4);
+    for reader in &readers.readers()[1..] {
+        assert_eq!(reader.slot.active_epoch.load(Ordering::Acquire), 2);
+    }
+
+    // 最小活跃纪元反映最早被钉住的读者
+    gc.collect();
+    assert_eq!(domain.shared.min_active_epoch.load(Ordering::Acquire), 0);
+
+    drop(early_guard);
+    drop(guards);
+    for reader in readers.readers() {
+        assert_eq!(
+            reader.slot.active_epoch.load(Ordering::Acquire),
+            INACTIVE_EPOCH
+        );
+    }
+}
+
+/// 测试5: 钉住到指定的旧纪元以检验回收边界
+#[test]
+fn test_pin_at_old_epoch() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let holder = domain.register_reader();
+    let lagging = domain.register_reader();
+
+    // 在纪元 0..4 各退休一袋垃圾
+    let hold = holder.pin();
+    for i in 0..4 {
+        gc.retire(Box::new(i));
+        gc.collect();
+    }
+    assert_eq!(gc.pending_epochs(), 4);
+    drop(hold);
+
+    // 落后的读者钉住在纪元 2：纪元 0 和 1 的垃圾可以回收，2 和 3 的必须保留
+    let guard = lagging.pin_at(2);
+    gc.collect();
+    assert_eq!(gc.pending_epochs(), 2);
+    assert_eq!(gc.total_garbage_count(), 2);
+
+    drop(guard);
+    gc.collect();
+    assert_eq!(gc.pending_epochs(), 0);
+}
+
+/// 测试6: 不能钉住到未来的纪元
+#[test]
+#[should_panic(expected = "newer than the global epoch")]
+fn test_pin_at_future_epoch_panics() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let _guard = local_epoch.pin_at(1);
+}
+
+/// 测试7: 写入者通过自身注册的读者读取自己的指针
+#[test]
+fn test_writer_local_reader() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let local_epoch = gc.local_reader();
+    let head = EpochPtr::new(Vec::<usize>::new());
+
+    // 与链表基准相同的模式：读取当前值，构造新值，再 store
+    for i in 0..100 {
+        let mut next = {
+            let guard = local_epoch.pin();
+            head.load(&guard).clone()
+        };
+        next.push(i);
+        head.store(next, &mut gc);
+    }
+
+    // 写入者本地读者在被钉住时同样会阻止回收
+    gc.collect();
+    let guard = local_epoch.pin();
+    assert_eq!(head.load(&guard).len(), 100);
+    head.store(Vec::new(), &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 1);
+    drop(guard);
+
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+}
+
+/// 测试8: 读者延迟的清理在写入者回收时安全地运行
+#[test]
+fn test_reader_deferred_cleanup() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let ran = Arc::new(AtomicBool::new(false));
+    let (deferred_tx, deferred_rx) = std::sync::mpsc::channel();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+    let reader = {
+        let domain = domain.clone();
+        let ran = ran.clone();
+        std::thread::spawn(move || {
+            let local_epoch = domain.register_reader();
+            let guard = local_epoch.pin();
+            guard.defer(move || ran.store(true, Ordering::SeqCst));
+            deferred_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            drop(guard);
+        })
+    };
+    deferred_rx.recv().unwrap();
+
+    // 延迟者仍被钉住：回收取走闭包，但不能运行它
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 1);
+    assert!(!ran.load(Ordering::SeqCst));
+
+    release_tx.send(()).unwrap();
+    reader.join().unwrap();
+
+    gc.collect();
+    assert!(ran.load(Ordering::SeqCst));
+    assert_eq!(gc.total_garbage_count(), 0);
+}
+
+/// 测试9: 每个读者槽的 pin 计数
+#[cfg(feature = "metrics")]
+#[test]
+fn test_reader_pin_counts() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let hot = domain.register_reader();
+    let _cold = domain.register_reader();
+
+    for _ in 0..10 {
+        let guard = hot.pin();
+        // 嵌套 pin 不会被重复计数
+        let _nested = guard.clone();
+        let _again = hot.pin();
+    }
+
+    let mut counts = domain.reader_pin_counts();
+    counts.sort_unstable();
+    assert_eq!(counts, vec![0, 10]);
+}
+
+/// 测试10: 无竞争时 try_pin 成功，且可以与 pin 嵌套
+#[test]
+fn test_try_pin_uncontended() {
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(7i32);
+    let local_epoch = domain.register_reader();
+
+    let guard = local_epoch.try_pin().expect("uncontended try_pin succeeds");
+    assert_eq!(*ptr.load(&guard), 7);
+
+    // 已经被钉住时嵌套调用总是成功
+    let nested = local_epoch.try_pin().expect("nested try_pin succeeds");
+    drop(guard);
+    ptr.store(8, &mut gc);
+    assert_eq!(gc.collect_detailed().reclaimed, 0);
+
+    drop(nested);
+    assert_eq!(gc.collect_detailed().reclaimed, 1);
+}
+
+/// 测试11: 读者纪元缓存在仍然有效时复用旧纪元，失效后刷新
+#[test]
+fn test_reader_epoch_cache() {
+    let (mut gc, domain) = EpochGcDomain::builder().reader_epoch_cache(true).build();
+    let ptr = EpochPtr::new(0i32);
+    let cached = domain.register_reader();
+    let holder = domain.register_reader();
+
+    drop(cached.pin());
+
+    // 另一个读者把最小活跃纪元保持在 0，缓存的纪元仍然有效
+    let hold = holder.pin();
+    gc.collect();
+    assert_eq!(domain.epoch(), 1);
+    {
+        let guard = cached.pin();
+        assert_eq!(cached.slot.active_epoch.load(Ordering::Acquire), 0);
+        ptr.store(1, &mut gc);
+        assert_eq!(*ptr.load(&guard), 1);
+    }
+    drop(hold);
+
+    // 所有读者都未钉住时回收，最小活跃纪元前进，缓存失效
+    gc.collect();
+    let guard = cached.pin();
+    assert_eq!(
+        cached.slot.active_epoch.load(Ordering::Acquire),
+        domain.epoch()
+    );
+    drop(guard);
+
+    // 被缓存读者钉住的值在它解除钉住之前不会被回收
+    let guard = cached.pin();
+    let value = ptr.load(&guard);
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(*value, 1);
+    drop(guard);
+    gc.collect();
+}
+
+/// 测试12: 模拟 FFI 的手动 pin / unpin 生命周期
+#[test]
+fn test_leak_and_force_unpin() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    let ptr = EpochPtr::new(1i32);
+    let local_epoch = domain.register_reader();
+
+    // 外部调用 1：pin 并泄漏守卫
+    let value: *const i32 = {
+        let guard = local_epoch.pin();
+        let value = ptr.load(&guard) as *const i32;
+        unsafe { guard.leak() };
+        value
+    };
+    assert_eq!(local_epoch.pin_count.get(), 1);
+    assert_eq!(local_epoch.slot.active_epoch.load(Ordering::Acquire), 0);
+
+    // 写入者替换值；泄漏的 pin 仍然保护旧值
+    ptr.store(2, &mut gc);
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 1);
+    assert_eq!(unsafe { *value }, 1);
+
+    // 嵌套的 pin 同样可以被泄漏
+    unsafe { local_epoch.pin().leak() };
+    assert_eq!(local_epoch.pin_count.get(), 2);
+
+    // 外部调用 2：强制解除钉住
+    unsafe { local_epoch.force_unpin() };
+    assert_eq!(local_epoch.pin_count.get(), 0);
+    assert_eq!(
+        local_epoch.slot.active_epoch.load(Ordering::Acquire),
+        INACTIVE_EPOCH
+    );
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0);
+
+    // 之后读者可以正常使用
+    let guard = local_epoch.pin();
+    assert_eq!(*ptr.load(&guard), 2);
+}
+
+/// 测试13: 线程局部读者在同一线程中被复用，并在线程退出时注销
+#[cfg(feature = "thread-local")]
+#[test]
+fn test_thread_local_reader() {
+    use std::rc::Rc;
+
+    let (mut gc, domain) = EpochGcDomain::new();
+    let ptr = EpochPtr::new(0i32);
+
+    for i in 0..5 {
+        let local_epoch = domain.thread_local_reader();
+        let guard = local_epoch.pin();
+        assert_eq!(*ptr.load(&guard), i);
+        drop(guard);
+        ptr.store(i + 1, &mut gc);
+    }
+    assert!(Rc::ptr_eq(
+        &domain.thread_local_reader(),
+        &domain.thread_local_reader()
+    ));
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+
+    // 另一个域在同一线程上有自己的读者
+    let (_other_gc, other_domain) = EpochGcDomain::new();
+    let other = other_domain.thread_local_reader();
+    assert!(!Rc::ptr_eq(&other, &domain.thread_local_reader()));
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+
+    // 另一个线程注册自己的读者，并在退出时移除其槽
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let local_epoch = domain.thread_local_reader();
+            let again = domain.thread_local_reader();
+            let _guard = local_epoch.pin();
+            let _nested = again.pin();
+            assert_eq!(local_epoch.pin_count.get(), 2);
+            assert_eq!(domain.shared.readers.lock().len(), 2);
+        });
+    });
+    assert_eq!(domain.shared.readers.lock().len(), 1);
+}
+
+/// 测试14: 读者令牌不保持槽存活，并在 LocalEpoch 被 drop 后报告读者已死亡
+#[cfg(not(feature = "loom"))]
+#[test]
+fn test_reader_token_liveness() {
+    let (mut gc, domain) = EpochGcDomain::builder().reader_slot_recycling(true).build();
+
+    let local_epoch = domain.register_reader();
+    let token = local_epoch.token();
+    assert!(token.is_alive());
+    assert_eq!(token.pinned_epoch(), None);
+
+    let guard = local_epoch.pin();
+    let epoch = guard.epoch();
+    // 监督线程可以持有令牌的克隆
+    let remote = token.clone();
+    let observed = std::thread::spawn(move || (remote.is_alive(), remote.pinned_epoch()))
+        .join()
+        .unwrap();
+    assert_eq!(observed, (true, Some(epoch)));
+    drop(guard);
+
+    drop(local_epoch);
+    assert!(!token.is_alive());
+    assert_eq!(token.pinned_epoch(), None);
+
+    // 令牌不影响清理：死亡的槽被移除并复用给新读者，但令牌仍然报告死亡
+    gc.cleanup_dead_readers();
+    assert_eq!(domain.shared.readers.lock().len(), 0);
+    let reused = domain.register_reader();
+    let _guard = reused.pin();
+    assert!(reused.token().is_alive());
+    assert!(!token.is_alive());
+    assert_eq!(token.pinned_epoch(), None);
+}
+
+/// 测试15: 降级期间读者不再阻挡回收，重新升级后钉住到当前纪元
+#[test]
+fn test_downgrade_upgrade() {
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    gc.assert_safe_reclaim();
+    let local_epoch = domain.register_reader();
+    let ptr = EpochPtr::new(0i32);
+
+    let guard = local_epoch.pin();
+    assert_eq!(*ptr.load(&guard), 0);
+    let pinned_at = guard.epoch();
+
+    let token = guard.downgrade();
+    assert_eq!(
+        local_epoch.slot.active_epoch.load(Ordering::Relaxed),
+        INACTIVE_EPOCH
+    );
+    for i in 1..=3 {
+        ptr.store(i, &mut gc);
+    }
+    gc.collect();
+    assert_eq!(gc.pending_epochs(), 0);
+    assert_eq!(gc.quarantined_count(), 3);
+
+    let guard = token.upgrade();
+    assert_eq!(guard.epoch(), domain.epoch());
+    assert!(guard.epoch() > pinned_at);
+    assert_eq!(*ptr.load(&guard), 3);
+}
+
+/// 测试16: 多个读者在写入者不断推进纪元时反复 pin，重试等待（自旋或 backoff）之后仍只会钉住到安全的纪元
+#[test]
+fn test_pin_retries_under_contention() {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    const STORES: usize = 2000;
+
+    let (mut gc, domain) = EpochGcDomain::builder()
+        .auto_reclaim_threshold(None)
+        .build();
+    gc.assert_safe_reclaim();
+    let ptr = Arc::new(EpochPtr::new(0usize));
+    let done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let (domain, ptr, done) = (domain.clone(), Arc::clone(&ptr), Arc::clone(&done));
+            std::thread::spawn(move || {
+                let local_epoch = domain.register_reader();
+                let mut last = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let guard = local_epoch.pin();
+                    let value = *ptr.load(&guard);
+                    assert!(value >= last && value <= STORES, "read {value}");
+                    last = value;
+                }
+            })
+        })
+        .collect();
+
+    for value in 1..=STORES {
+        ptr.store(value, &mut gc);
+        gc.collect();
+    }
+    done.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+
+    gc.collect();
+    assert!(gc.quarantine_intact());
+}
+
+/// 测试17: 长期存活的线程不会累积已被 drop 的域的线程局部读者
+#[cfg(all(feature = "thread-local", not(feature = "loom")))]
+#[test]
+fn test_thread_local_reader_forgets_dropped_domains() {
+    let weak = {
+        let (_gc, domain) = EpochGcDomain::new();
+        let _local_epoch = domain.thread_local_reader();
+        std::sync::Arc::downgrade(&domain.shared)
+    };
+    // 只剩线程局部变量持有第一个域的共享状态
+    assert!(weak.upgrade().is_some());
+
+    // 另一个域的下一次调用会丢弃它
+    let (_gc, domain) = EpochGcDomain::new();
+    let _local_epoch = domain.thread_local_reader();
+    assert!(weak.upgrade().is_none());
+}
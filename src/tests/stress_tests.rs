@@ -0,0 +1,241 @@
+/// 随机化压力测试模块
+/// 以确定性种子在多个线程中混合执行读者注册、drop、pin、store 和 collect，并检查不变量
+use crate::{EpochGcDomain, EpochPtr, GcHandle, ReaderStorage, ReadersBackend};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const CHECK: u64 = 0x5EED_CAFE_F00D_D00D;
+
+/// 用于生成操作序列的简单 xorshift 伪随机数生成器
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// 跟踪所有被创建和被 drop 的值
+#[derive(Default)]
+struct Tracker {
+    created: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+/// 带校验字段的值：被过早回收（隔离区毒化）时校验失败
+struct Tracked {
+    value: u64,
+    check: u64,
+    tracker: Arc<Tracker>,
+}
+
+impl Tracked {
+    fn new(value: u64, tracker: &Arc<Tracker>) -> Self {
+        tracker.created.fetch_add(1, Ordering::SeqCst);
+        Tracked {
+            value,
+            check: value ^ CHECK,
+            tracker: Arc::clone(tracker),
+        }
+    }
+
+    fn verify(&self, seed: u64) {
+        assert_eq!(
+            self.value ^ CHECK,
+            self.check,
+            "seed {seed}: reader observed a reclaimed value"
+        );
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        let dropped = self.tracker.dropped.fetch_add(1, Ordering::SeqCst) + 1;
+        assert!(dropped <= self.tracker.created.load(Ordering::SeqCst));
+    }
+}
+
+/// 一次运行的域配置，由种子决定
+fn build_domain(rng: &mut XorShift) -> (GcHandle, EpochGcDomain, ReadersBackend) {
+    let backend = [ReadersBackend::Mutex, ReadersBackend::LockFree][rng.below(2)];
+    let mut builder = EpochGcDomain::builder()
+        .readers_backend(backend)
+        .reader_storage([ReaderStorage::Vec, ReaderStorage::Slab][rng.below(2)])
+        .reader_slot_recycling(rng.below(2) == 0)
+        .reader_epoch_cache(rng.below(2) == 0)
+        .scan_chunk([0, 1, 3][rng.below(3)])
+        .cleanup_interval([0, 1, 4][rng.below(3)])
+        .auto_reclaim_threshold([None, Some(4)][rng.below(2)]);
+    if rng.below(2) == 0 {
+        builder = builder.coalesce_threshold(1 + rng.below(4));
+    }
+    let (gc, domain) = builder.build();
+    (gc, domain, backend)
+}
+
+/// 读者线程：随机注册、drop、pin 并读取
+fn run_reader(
+    seed: u64,
+    thread: u64,
+    ops: usize,
+    domain: EpochGcDomain,
+    ptrs: Arc<Vec<EpochPtr<Tracked>>>,
+    live: Arc<AtomicUsize>,
+) {
+    let mut rng = XorShift((seed ^ (thread + 1).wrapping_mul(0xA24B_AED4_963E_E407)) | 1);
+    let mut readers = Vec::new();
+
+    for _ in 0..ops {
+        match rng.below(10) {
+            0..=1 if readers.len() < 4 => {
+                let reader = if rng.below(2) == 0 {
+                    domain.register_reader()
+                } else {
+                    domain.register_reader_eager()
+                };
+                live.fetch_add(1, Ordering::SeqCst);
+                readers.push(reader);
+            }
+            2 if !readers.is_empty() => {
+                let reader = readers.swap_remove(rng.below(readers.len()));
+                live.fetch_sub(1, Ordering::SeqCst);
+                drop(reader);
+            }
+            _ if !readers.is_empty() => {
+                let reader = &readers[rng.below(readers.len())];
+                let guard = reader.pin();
+                for _ in 0..=rng.below(3) {
+                    ptrs[rng.below(ptrs.len())].load(&guard).verify(seed);
+                }
+                if rng.below(4) == 0 {
+                    // 嵌套 pin 并在外层 pin 期间让出执行
+                    let nested = reader.pin();
+                    std::thread::yield_now();
+                    ptrs[rng.below(ptrs.len())].load(&nested).verify(seed);
+                }
+            }
+            _ => std::thread::yield_now(),
+        }
+    }
+
+    live.fetch_sub(readers.len(), Ordering::SeqCst);
+}
+
+/// 检查读者计数不变量
+fn check_reader_counts(seed: u64, gc: &GcHandle, backend: ReadersBackend, live: &AtomicUsize) {
+    let readers = gc.shared.lock_readers();
+    let count = gc.shared.reader_count.load(Ordering::SeqCst);
+    // 计数在槽被加入之前增加、在槽被移除之后减少，两者都不会在持有锁时发生
+    assert!(
+        count >= readers.len(),
+        "seed {seed}: reader_count {count} is below the {} listed slots",
+        readers.len()
+    );
+    assert_eq!(readers.iter().count(), readers.len(), "seed {seed}");
+    // 使用 Mutex 后端时，注册完成的读者必然已在列表中（死槽可能仍残留）
+    if backend == ReadersBackend::Mutex {
+        let live = live.load(Ordering::SeqCst);
+        assert!(
+            live <= readers.len(),
+            "seed {seed}: {live} live readers but only {} listed slots",
+            readers.len()
+        );
+    }
+}
+
+/// 使用一个种子运行一次随机化压力测试
+fn run_seed(seed: u64, reader_threads: u64, ops: usize) {
+    let mut rng = XorShift(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1);
+    let tracker = Arc::new(Tracker::default());
+    let (mut gc, domain, backend) = build_domain(&mut rng);
+    gc.assert_safe_reclaim();
+
+    let ptrs: Arc<Vec<_>> = Arc::new(
+        (0..3)
+            .map(|i| EpochPtr::new(Tracked::new(i, &tracker)))
+            .collect(),
+    );
+    let live = Arc::new(AtomicUsize::new(0));
+
+    let handles: Vec<_> = (0..reader_threads)
+        .map(|thread| {
+            let domain = domain.clone();
+            let ptrs = Arc::clone(&ptrs);
+            let live = Arc::clone(&live);
+            std::thread::spawn(move || run_reader(seed, thread, ops, domain, ptrs, live))
+        })
+        .collect();
+
+    let mut next_value = 3;
+    for _ in 0..ops {
+        match rng.below(10) {
+            0..=5 => {
+                let ptr = &ptrs[rng.below(ptrs.len())];
+                ptr.store(Tracked::new(next_value, &tracker), &mut gc);
+                next_value += 1;
+            }
+            6..=7 => gc.collect(),
+            8 => {
+                gc.cleanup_dead_readers();
+            }
+            _ => check_reader_counts(seed, &gc, backend, &live),
+        }
+    }
+
+    for handle in handles {
+        if handle.join().is_err() {
+            panic!("seed {seed}: a reader thread panicked");
+        }
+    }
+
+    // 所有读者都已 drop：清理后不再残留任何槽，所有垃圾都可回收
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+    check_reader_counts(seed, &gc, backend, &live);
+    gc.cleanup_dead_readers();
+    assert_eq!(
+        gc.shared.reader_count.load(Ordering::SeqCst),
+        0,
+        "seed {seed}"
+    );
+    assert!(gc.shared.readers.lock().is_empty(), "seed {seed}");
+    gc.collect();
+    assert_eq!(gc.total_garbage_count(), 0, "seed {seed}");
+    assert!(
+        gc.quarantine_intact(),
+        "seed {seed}: reclaimed memory was written"
+    );
+
+    // 没有泄漏：每个被创建的值都恰好被 drop 一次
+    drop(ptrs);
+    drop(gc);
+    let created = tracker.created.load(Ordering::SeqCst);
+    assert_eq!(
+        tracker.dropped.load(Ordering::SeqCst),
+        created,
+        "seed {seed}: leaked values"
+    );
+    assert_eq!(created, next_value as usize, "seed {seed}");
+}
+
+/// 测试1: 许多种子，较短的操作序列
+#[test]
+fn test_randomized_registration_and_collection() {
+    for seed in 0..64 {
+        run_seed(seed, 3, 200);
+    }
+}
+
+/// 测试2: 少量种子，更长的操作序列和更多读者线程
+#[test]
+fn test_randomized_long_runs() {
+    for seed in 1000..1008 {
+        run_seed(seed, 6, 2000);
+    }
+}
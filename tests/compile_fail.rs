@@ -0,0 +1,17 @@
+//! Compile-fail tests for the thread-confinement guarantees
+//!
+//! Each case in `tests/ui` must fail to compile with exactly the error recorded in its
+//! `.stderr` file, so a case cannot pass because of an unrelated mistake.
+//!
+//! Run with: `cargo test --test compile_fail`
+//! Regenerate expectations with: `TRYBUILD=overwrite cargo test --test compile_fail`
+
+// The `testing` feature adds a quarantine to `GcHandle`, which changes the field rustc names
+// as the reason it is not `Sync`.
+#![cfg(not(any(feature = "loom", feature = "testing")))]
+
+#[test]
+fn thread_confinement() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
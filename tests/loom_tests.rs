@@ -810,8 +810,8 @@ fn loom_reader_monotonic_observation() {
             drop(guard2);
 
             // Values should be from the sequence 1, 2, 3
-            assert!(val1 >= 1 && val1 <= 3);
-            assert!(val2 >= 1 && val2 <= 3);
+            assert!((1..=3).contains(&val1));
+            assert!((1..=3).contains(&val2));
             // Note: SWMR does NOT guarantee monotonicity across different pin cycles
             // Reader might observe non-monotonic values (e.g., 3 then 2) depending on
             // thread interleaving. Both values must be valid, but order is not guaranteed.
@@ -861,7 +861,7 @@ fn loom_reader_holds_guard_during_updates() {
 
             // Value should be in valid range
             // 值应在有效范围内
-            assert!(initial_value >= 0 && initial_value <= 3);
+            assert!((0..=3).contains(&initial_value));
         });
 
         // Writer performs multiple updates
@@ -960,8 +960,8 @@ fn loom_guard_outlives_stores() {
             // But we might see new value on subsequent loads
             let current = *reader_ptr.load(&guard);
 
-            assert!(initial >= 1 && initial <= 3);
-            assert!(current >= 1 && current <= 3);
+            assert!((1..=3).contains(&initial));
+            assert!((1..=3).contains(&current));
         });
 
         // Writer performs multiple operations
@@ -0,0 +1,8 @@
+use swmr_epoch::EpochGcDomain;
+
+fn main() {
+    let (gc, _domain) = EpochGcDomain::new();
+    std::thread::scope(|s| {
+        s.spawn(|| gc.pending_epochs());
+    });
+}
@@ -0,0 +1,9 @@
+use swmr_epoch::EpochGcDomain;
+
+fn main() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    std::thread::scope(|s| {
+        s.spawn(|| drop(local_epoch.pin()));
+    });
+}
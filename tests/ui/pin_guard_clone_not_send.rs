@@ -0,0 +1,11 @@
+use swmr_epoch::EpochGcDomain;
+
+fn main() {
+    let (_gc, domain) = EpochGcDomain::new();
+    let local_epoch = domain.register_reader();
+    let guard = local_epoch.pin();
+    let clone = guard.clone();
+    std::thread::scope(|s| {
+        s.spawn(move || drop(clone));
+    });
+}